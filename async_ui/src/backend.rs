@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+
+/// A rendering target, such as the browser DOM.
+///
+/// Async UI renders by putting *nodes* into *containers* (which are nodes too).
+/// It keeps the children of each container in the order of the futures that
+/// rendered them, and removes them when those futures are dropped.
+/// A backend tells it how to do that for one kind of node.
+///
+/// The methods are infallible. If something goes wrong, the backend should panic.
+pub trait Backend: 'static {
+    /// A handle to a node.
+    /// Cloning the handle should give another handle to the same node.
+    type Node: Clone + Debug + 'static;
+
+    /// Insert `node` as a child of `container`, right before `reference`
+    /// (or at the end, if `reference` is `None`).
+    ///
+    /// `reference`, if given, is always a child of `container`.
+    /// If `node` is already a child of some container, it should be moved.
+    fn insert_before(container: &Self::Node, node: &Self::Node, reference: Option<&Self::Node>);
+
+    /// Remove `node`, which is a child of `container`, from `container`.
+    fn remove_child(container: &Self::Node, node: &Self::Node);
+
+    /// Create a node that doesn't display anything.
+    ///
+    /// These are used as markers, for example as the reference node for
+    /// [SiblingNodeFuture][crate::SiblingNodeFuture].
+    fn create_marker() -> Self::Node;
+
+    /// Report a problem with a node. Only called in debug builds.
+    ///
+    /// By default, this prints to stderr.
+    fn debug_log(message: &str, node: &Self::Node) {
+        eprintln!("{message} {node:?}");
+    }
+}
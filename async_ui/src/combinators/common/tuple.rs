@@ -1,6 +1,6 @@
 use super::super::utils::WakerArray;
 use crate::{
-    context::{DomContext, DOM_CONTEXT},
+    context::{RenderContext, RENDER_CONTEXT},
     dropping::DetachmentBlocker,
     position::ChildPosition,
 };
@@ -40,6 +40,9 @@ pub trait TupleMaybeReturn<R, O> {
 /// S = the type of the stored tuples = (F1::StoredItem, F2::StoredItem, ...).
 /// O = the return type of the combinator future.
 pub trait TupleWhenCompleted<S, O> {
+    /// Whether to poll the woken subfutures in order of their index
+    /// (rather than in the order they were woken).
+    const BIASED: bool = false;
     /// Called when all subfutures are completed and none caused the combinator to return early.
     /// The argument is an array of the kept item from each subfuture.
     fn when_completed(stored_items: S) -> O;
@@ -156,6 +159,9 @@ macro_rules! impl_common_tuple {
                     readiness.clear();
                     num_awake
                 };
+                if <B as TupleWhenCompleted<($(<B as TupleMaybeReturn<$F::Output, O>>::StoredItem,)+), O>>::BIASED {
+                    this.awake_list_buffer[..num_awake].sort_unstable();
+                }
 
 
                 for &index in this.awake_list_buffer.iter().take(num_awake) {
@@ -167,12 +173,12 @@ macro_rules! impl_common_tuple {
                     let ready = match index {
                         $(
                             $idx => {
-                                if let Poll::Ready(value) = DOM_CONTEXT.with(|parent: &DomContext| {
-                                    let ctx = DomContext::Child {
+                                if let Poll::Ready(value) = RENDER_CONTEXT.with(|parent: &RenderContext| {
+                                    let ctx = RenderContext::Child {
                                         parent,
                                         index: $idx as _,
                                     };
-                                    DOM_CONTEXT.set(&ctx, || futures.$F.as_mut().poll(&mut cx))
+                                    RENDER_CONTEXT.set(&ctx, || futures.$F.as_mut().poll(&mut cx))
                                 }) {
 									match B::maybe_return($idx, value) {
 										ControlFlow::Break(ret) => {
@@ -231,7 +237,7 @@ macro_rules! impl_common_tuple {
                 let this = self.project();
 
                 if !this.detachment_blocker.block_until_drop() {
-                    DOM_CONTEXT.with(|parent: &DomContext| parent.remove_child(ChildPosition::default()));
+                    RENDER_CONTEXT.with(|parent: &RenderContext| parent.remove_child(ChildPosition::default()));
                 }
 
                 $(
@@ -1,6 +1,6 @@
 use super::super::utils::{self, WakerVec};
 use crate::{
-    context::{DomContext, DOM_CONTEXT},
+    context::{RenderContext, RENDER_CONTEXT},
     dropping::DetachmentBlocker,
     position::ChildPosition,
 };
@@ -115,12 +115,12 @@ where
             }
             let fut = utils::get_pin_mut_from_vec(this.futures.as_mut(), index).unwrap();
             let mut cx = Context::from_waker(this.wakers.get(index).unwrap());
-            if let Poll::Ready(value) = DOM_CONTEXT.with(|parent: &DomContext| {
-                let ctx = DomContext::Child {
+            if let Poll::Ready(value) = RENDER_CONTEXT.with(|parent: &RenderContext| {
+                let ctx = RenderContext::Child {
                     parent,
                     index: index as _,
                 };
-                DOM_CONTEXT.set(&ctx, || fut.poll(&mut cx))
+                RENDER_CONTEXT.set(&ctx, || fut.poll(&mut cx))
             }) {
                 match B::maybe_return(index, value) {
                     ControlFlow::Continue(store) => {
@@ -172,7 +172,8 @@ where
         let this = self.project();
 
         if !this.detachment_blocker.block_until_drop() {
-            DOM_CONTEXT.with(|parent: &DomContext| parent.remove_child(ChildPosition::default()));
+            RENDER_CONTEXT
+                .with(|parent: &RenderContext| parent.remove_child(ChildPosition::default()));
         }
 
         for (filled, output) in this.filled.iter().zip(this.items.iter_mut()) {
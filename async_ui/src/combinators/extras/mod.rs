@@ -1,5 +1,8 @@
 use std::{future::Future, marker::PhantomData};
 
+#[cfg(panic = "unwind")]
+use crate::error_boundary::{ErrorBoundary, PanicPayload};
+
 /// Provides Future extension methods useful for writing UI.
 ///
 /// Implemented for every `Future` type.
@@ -12,7 +15,7 @@ pub trait UiFutureExt: Future + Sized {
     /// ```rust
     /// # async fn my_async_fn() {}
     /// # let _ = async {
-    /// use async_ui_web_core::combinators::UiFutureExt;
+    /// use async_ui::combinators::UiFutureExt;
     /// let fut = my_async_fn().pend_after::<std::convert::Infallible>();
     /// fut.await; // will never finish
     /// # };
@@ -21,7 +24,7 @@ pub trait UiFutureExt: Future + Sized {
     /// `f.pend_after()` is equivalent to
     /// ```rust
     /// # let f = async {};
-    /// # async { let _: i32 = 
+    /// # async { let _: i32 =
     /// async {
     /// 	f.await;
     /// 	std::future::pending().await
@@ -39,7 +42,7 @@ pub trait UiFutureExt: Future + Sized {
     /// `f.meanwhile(g)` is equivalent to
     /// ```rust
     /// # let (f, g) = (async {}, async {});
-    /// # use async_ui_web_core::combinators::{UiFutureExt, race};
+    /// # use async_ui::combinators::{UiFutureExt, race};
     /// # async {
     /// race((
     /// 	f,
@@ -47,7 +50,7 @@ pub trait UiFutureExt: Future + Sized {
     /// ))
     /// # };
     /// ```
-    /// 
+    ///
     /// Use this to display UI as side-effect of some async execution.
     /// For example, `load_data().meanwhile(spinner()).await`.
     fn meanwhile<F: Future>(
@@ -57,6 +60,39 @@ pub trait UiFutureExt: Future + Sized {
         use super::race::Race;
         (self, effect.pend_after()).race()
     }
+    /// Catch panics in this future and render a fallback instead.
+    ///
+    /// If this future panics while being polled, everything it rendered is
+    /// removed and the future made by `fallback` runs in its place.
+    /// See [ErrorBoundary] for details.
+    ///
+    /// Only available when compiling with `panic = "unwind"`, so not in the
+    /// browser (`wasm32-unknown-unknown` aborts on panic).
+    ///
+    /// ```rust
+    /// # async fn broken_widget() {}
+    /// # async fn show_error(_message: String) {}
+    /// # let _ = async {
+    /// use async_ui::combinators::UiFutureExt;
+    /// broken_widget()
+    ///     .catch_panic(|payload| {
+    ///         let message = payload
+    ///             .downcast_ref::<&str>()
+    ///             .map(|s| s.to_string())
+    ///             .unwrap_or_else(|| "something went wrong".into());
+    ///         show_error(message)
+    ///     })
+    ///     .await;
+    /// # };
+    /// ```
+    #[cfg(panic = "unwind")]
+    fn catch_panic<F, G>(self, fallback: F) -> ErrorBoundary<Self, F, G>
+    where
+        F: FnOnce(PanicPayload) -> G,
+        G: Future<Output = Self::Output>,
+    {
+        ErrorBoundary::new(self, fallback)
+    }
 }
 
 impl<F: Future> UiFutureExt for F {}
@@ -0,0 +1,408 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+use futures_core::Stream;
+
+use crate::{
+    context::{Location, RenderContext, RENDER_CONTEXT},
+    dropping::DetachmentBlocker,
+    position::{ChildPosition, PositionSegment},
+};
+
+/// A set of futures that can grow while it is running.
+///
+/// Like [join][super::join] on a [Vec], `JoinSet` drives many futures at once
+/// and renders them in order. Unlike `join`, more futures can be added
+/// (through a [JoinSetHandle]) while the set is being polled.
+/// Each future is rendered after all the futures added before it.
+///
+/// `JoinSet` is a [Stream] of the outputs of its futures, in the order they complete.
+/// A future is dropped as soon as it completes, so whatever it rendered is removed.
+///
+/// ```rust
+/// # use async_ui::combinators::{join, JoinSet};
+/// # async fn show_message(_: i32) {}
+/// # async fn next_message() -> i32 { 0 }
+/// # let _ = async {
+/// let mut messages = JoinSet::new();
+/// let handle = messages.handle();
+/// join((
+///     async {
+///         while let Some(()) = messages.join_next().await {}
+///     },
+///     async {
+///         loop {
+///             handle.push(show_message(next_message().await));
+///         }
+///     },
+/// ))
+/// .await;
+/// # };
+/// ```
+///
+/// The stream ends once there are no futures left and no [JoinSetHandle] that
+/// could add more.
+///
+/// Poll the set in only one place, so that its futures always render in the same spot.
+pub struct JoinSet<F: Future> {
+    children: BTreeMap<PositionSegment, Child<F>>,
+    next_segment: PositionSegment,
+    queue: Rc<RefCell<Queue<F>>>,
+    wakers: Arc<Wakers>,
+    outputs: VecDeque<F::Output>,
+    /// Where the set was last polled, which is where its futures render.
+    polled_at: Option<Location>,
+    detachment_blocker: DetachmentBlocker,
+}
+
+/// Adds futures to a [JoinSet]. Get one with [JoinSet::handle].
+pub struct JoinSetHandle<F> {
+    queue: Rc<RefCell<Queue<F>>>,
+}
+
+/// Futures waiting to be added to the set the next time it is polled.
+struct Queue<F> {
+    futures: Vec<F>,
+    waker: Option<Waker>,
+}
+
+struct Child<F> {
+    future: Pin<Box<F>>,
+    waker: Waker,
+}
+
+/// The segments of the futures that have been woken,
+/// and the waker of the set itself.
+#[derive(Default)]
+struct Wakers {
+    awake: Mutex<Vec<PositionSegment>>,
+    parent: Mutex<Option<Waker>>,
+}
+
+struct ChildWaker {
+    segment: PositionSegment,
+    wakers: Arc<Wakers>,
+}
+
+impl Wake for ChildWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wakers.awake.lock().unwrap().push(self.segment);
+        if let Some(parent) = &*self.wakers.parent.lock().unwrap() {
+            parent.wake_by_ref();
+        }
+    }
+}
+
+// The futures are boxed, and the outputs are never pinned.
+impl<F: Future> Unpin for JoinSet<F> {}
+
+impl<F: Future> Default for JoinSet<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Future> fmt::Debug for JoinSet<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinSet").field("len", &self.len()).finish()
+    }
+}
+
+impl<F: Future> JoinSet<F> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            next_segment: 0,
+            queue: Rc::new(RefCell::new(Queue {
+                futures: Vec::new(),
+                waker: None,
+            })),
+            wakers: Default::default(),
+            outputs: VecDeque::new(),
+            polled_at: None,
+            detachment_blocker: DetachmentBlocker,
+        }
+    }
+    /// Get a handle for adding futures to this set while it is running.
+    pub fn handle(&self) -> JoinSetHandle<F> {
+        JoinSetHandle {
+            queue: self.queue.clone(),
+        }
+    }
+    /// Add a future to the set. It will be rendered after all the futures added before it.
+    pub fn push(&mut self, future: F) {
+        self.queue.borrow_mut().futures.push(future);
+    }
+    /// The number of futures in the set that haven't completed.
+    pub fn len(&self) -> usize {
+        self.children.len() + self.queue.borrow().futures.len()
+    }
+    /// Whether all the futures in the set have completed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Wait for the next future to complete and get its output.
+    ///
+    /// Returns `None` once the [stream][Stream] ends.
+    pub async fn join_next(&mut self) -> Option<F::Output> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+    /// Poll the futures under their own positions in the current render context.
+    fn with_child_context<R>(segment: PositionSegment, f: impl FnOnce() -> R) -> R {
+        RENDER_CONTEXT.with(|parent: &RenderContext| {
+            let ctx = RenderContext::Child {
+                parent,
+                index: segment,
+            };
+            RENDER_CONTEXT.set(&ctx, f)
+        })
+    }
+}
+
+impl<F: Future> Stream for JoinSet<F> {
+    type Item = F::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.polled_at = RENDER_CONTEXT.with(|ctx: &RenderContext| ctx.location());
+        {
+            let mut parent = this.wakers.parent.lock().unwrap();
+            if !parent.as_ref().is_some_and(|w| w.will_wake(cx.waker())) {
+                *parent = Some(cx.waker().clone());
+            }
+        }
+        let added = {
+            let mut queue = this.queue.borrow_mut();
+            if !queue
+                .waker
+                .as_ref()
+                .is_some_and(|w| w.will_wake(cx.waker()))
+            {
+                queue.waker = Some(cx.waker().clone());
+            }
+            std::mem::take(&mut queue.futures)
+        };
+        let mut awake = std::mem::take(&mut *this.wakers.awake.lock().unwrap());
+        for future in added {
+            let segment = this.next_segment;
+            this.next_segment = segment
+                .checked_add(1)
+                .expect("too many futures added to JoinSet");
+            let waker = Waker::from(Arc::new(ChildWaker {
+                segment,
+                wakers: this.wakers.clone(),
+            }));
+            this.children.insert(
+                segment,
+                Child {
+                    future: Box::pin(future),
+                    waker,
+                },
+            );
+            awake.push(segment);
+        }
+        for segment in awake {
+            let Some(child) = this.children.get_mut(&segment) else {
+                // woken after completing, or woken twice
+                continue;
+            };
+            let mut child_cx = Context::from_waker(&child.waker);
+            let res =
+                Self::with_child_context(segment, || child.future.as_mut().poll(&mut child_cx));
+            if let Poll::Ready(output) = res {
+                // Drop it where it was rendered, so its nodes are removed.
+                let child = this.children.remove(&segment);
+                Self::with_child_context(segment, || drop(child));
+                this.outputs.push_back(output);
+            }
+        }
+        if let Some(output) = this.outputs.pop_front() {
+            Poll::Ready(Some(output))
+        } else if this.children.is_empty() && Rc::strong_count(&this.queue) == 1 {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<F: Future> Drop for JoinSet<F> {
+    fn drop(&mut self) {
+        // Remove everything at once; the children are dropped (with detachment blocked) after this.
+        // The set may be dropped somewhere other than where it was polled, so only remove
+        // the positions it gave out, relative to where it was polled.
+        if !self.detachment_blocker.block_until_drop() && RENDER_CONTEXT.is_set() {
+            let Some(polled_at) = &self.polled_at else {
+                return;
+            };
+            RENDER_CONTEXT.with(|ctx: &RenderContext| {
+                for &segment in self.children.keys() {
+                    let mut position = ChildPosition::default();
+                    position.wrap(segment);
+                    ctx.remove_child_at(polled_at, position);
+                }
+            });
+        }
+    }
+}
+
+impl<F> Clone for JoinSetHandle<F> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for JoinSetHandle<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinSetHandle").finish_non_exhaustive()
+    }
+}
+
+impl<F> JoinSetHandle<F> {
+    /// Add a future to the set. It will be rendered after all the futures added before it.
+    pub fn push(&self, future: F) {
+        let mut queue = self.queue.borrow_mut();
+        queue.futures.push(future);
+        if let Some(waker) = &queue.waker {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+impl<F> Drop for JoinSetHandle<F> {
+    fn drop(&mut self) {
+        // The set might be waiting for us to go away before ending.
+        if let Ok(queue) = self.queue.try_borrow() {
+            if let Some(waker) = &queue.waker {
+                waker.wake_by_ref();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures_lite::future::{block_on, poll_once};
+
+    use super::*;
+    use crate::{
+        combinators::join,
+        test_backend::{leaf, names, node, Names},
+        ContainerNodeFuture,
+    };
+
+    #[test]
+    fn outputs_in_completion_order() {
+        crate::combinators::block_for_testing(async {
+            let mut set = JoinSet::new();
+            let handle = set.handle();
+            set.push(Box::pin(async { 1 }) as Pin<Box<dyn Future<Output = i32>>>);
+            handle.push(Box::pin(std::future::ready(2)));
+            drop(handle);
+            let mut outputs = Vec::new();
+            while let Some(output) = set.join_next().await {
+                outputs.push(output);
+            }
+            assert_eq!(outputs, [1, 2]);
+        });
+    }
+
+    #[test]
+    fn push_while_running() {
+        let root = node("root");
+        let step = Cell::new(0);
+        let component = async {
+            let mut set = JoinSet::new();
+            let handle = set.handle();
+            join((
+                async { while let Some(()) = set.join_next().await {} },
+                async {
+                    handle.push(Box::pin(leaf("a")) as Pin<Box<dyn Future<Output = ()>>>);
+                    handle.push(Box::pin(async {}));
+                    poll_fn(|cx| {
+                        if step.get() == 0 {
+                            cx.waker().wake_by_ref();
+                            return Poll::Pending;
+                        }
+                        Poll::Ready(())
+                    })
+                    .await;
+                    handle.push(Box::pin(leaf("b")));
+                    std::future::pending::<()>().await;
+                },
+                leaf("after"),
+            ))
+            .await;
+        };
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            component,
+            root.clone(),
+        ));
+        // the set picks up the futures pushed after it was polled in the next poll
+        block_on(poll_once(fut.as_mut()));
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["a", "after"]);
+        step.set(1);
+        block_on(poll_once(fut.as_mut()));
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["a", "b", "after"]);
+        drop(fut);
+        assert_eq!(names(&root), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn dropped_elsewhere() {
+        let root = node("root");
+        let step = Cell::new(0);
+        let set = RefCell::new(Some(JoinSet::new()));
+        set.borrow_mut()
+            .as_mut()
+            .unwrap()
+            .push(Box::pin(leaf("a")) as Pin<Box<dyn Future<Output = ()>>>);
+        let component = join((
+            poll_fn(|cx| match set.borrow_mut().as_mut() {
+                Some(set) => Pin::new(set).poll_next(cx).map(|_| ()),
+                None => Poll::Pending,
+            }),
+            join((leaf("b"), async {
+                poll_fn(|cx| {
+                    if step.get() == 0 {
+                        cx.waker().wake_by_ref();
+                        return Poll::Pending;
+                    }
+                    Poll::Ready(())
+                })
+                .await;
+                // dropped in a different context from where it was polled
+                drop(set.borrow_mut().take());
+                std::future::pending::<()>().await;
+            })),
+        ));
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            component,
+            root.clone(),
+        ));
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["a", "b"]);
+        step.set(1);
+        block_on(poll_once(fut.as_mut()));
+        // only what the set rendered is removed
+        assert_eq!(names(&root), ["b"]);
+    }
+}
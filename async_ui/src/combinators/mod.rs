@@ -1,12 +1,14 @@
 mod common;
 mod extras;
 mod join;
+mod join_set;
 mod race;
 mod race_ok;
 mod try_join;
 mod utils;
 
 pub use extras::UiFutureExt;
+pub use join_set::{JoinSet, JoinSetHandle};
 
 /// Wait for multiple futures to complete.
 ///
@@ -20,7 +22,7 @@ pub use extras::UiFutureExt;
 /// * a [Vec] of Futures (signature: `Vec<F> -> Vec<F::Output>`)
 ///
 /// ```rust
-/// # use async_ui_web_core::combinators::join;
+/// # use async_ui::combinators::join;
 /// # let _ = async {
 /// async fn do_something(input: i32) -> i32 {
 ///     // ...make a network request of something...
@@ -70,7 +72,7 @@ pub fn join<F: join::Join>(f: F) -> F::Future {
 /// *   a [Vec] of Futures (signature: `Vec<F> -> F::Output`)
 ///
 /// ```rust
-/// # use async_ui_web_core::combinators::race;
+/// # use async_ui::combinators::race;
 /// # let _ = async {
 /// async fn do_something(input: i32) -> i32 {
 ///     // ...make a network request of something...
@@ -99,6 +101,27 @@ pub fn race<F: race::Race>(f: F) -> F::Future {
     f.race()
 }
 
+/// Like [race], but the subfutures are polled in order, so when several
+/// of them are ready at once, the first one wins.
+///
+/// Subfutures must be passed in as a tuple of up to 12 Futures,
+/// all with the same output type.
+///
+/// ```rust
+/// # use async_ui::combinators::race_biased;
+/// # let _ = async {
+/// let result = race_biased((
+///     std::future::ready("first"),
+///     std::future::ready("second"),
+/// ))
+/// .await;
+/// assert_eq!(result, "first");
+/// # };
+/// ```
+pub fn race_biased<F: race::RaceBiased>(f: F) -> F::Future {
+    f.race_biased()
+}
+
 /// Wait for all futures to complete successfully, or return early on error.
 ///
 /// TryJoin takes in many fallible (returns [Result]) "subfutures" and return
@@ -136,7 +159,9 @@ pub fn race_ok<F: race_ok::RaceOk>(f: F) -> F::Future {
 }
 
 #[cfg(test)]
-fn block_for_testing<F: core::future::Future>(f: F) -> F::Output {
-    use crate::context::{DomContext, DOM_CONTEXT};
-    DOM_CONTEXT.set(&DomContext::Null, || futures_lite::future::block_on(f))
+pub(crate) fn block_for_testing<F: core::future::Future>(f: F) -> F::Output {
+    use crate::context::{RenderContext, RENDER_CONTEXT};
+    RENDER_CONTEXT.set(&RenderContext::Detached, || {
+        futures_lite::future::block_on(f)
+    })
 }
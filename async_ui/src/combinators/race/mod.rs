@@ -26,3 +26,20 @@ pub trait Race {
 
 #[derive(Debug)]
 pub struct RaceBehavior;
+
+/// Like [Race], but when several futures are ready, the first one wins.
+///
+/// Only implemented for tuples.
+pub trait RaceBiased {
+    /// The resulting output type.
+    type Output;
+
+    /// Which kind of future are we turning this into?
+    type Future: Future<Output = Self::Output>;
+
+    /// Wait for the first future to complete, preferring earlier futures.
+    fn race_biased(self) -> Self::Future;
+}
+
+#[derive(Debug)]
+pub struct BiasedRaceBehavior;
@@ -1,5 +1,5 @@
 use super::super::common::{CombineTuple, TupleMaybeReturn, TupleWhenCompleted};
-use super::{Race as RaceTrait, RaceBehavior};
+use super::{BiasedRaceBehavior, Race as RaceTrait, RaceBehavior, RaceBiased};
 
 use core::convert::Infallible;
 use core::future::Future;
@@ -21,6 +21,19 @@ impl<S, O> TupleWhenCompleted<S, O> for RaceBehavior {
     }
 }
 
+impl<T> TupleMaybeReturn<T, T> for BiasedRaceBehavior {
+    type StoredItem = Infallible;
+    fn maybe_return(_: usize, res: T) -> ControlFlow<T, Self::StoredItem> {
+        ControlFlow::Break(res)
+    }
+}
+impl<S, O> TupleWhenCompleted<S, O> for BiasedRaceBehavior {
+    const BIASED: bool = true;
+    fn when_completed(_: S) -> O {
+        unreachable!() // should have early returned
+    }
+}
+
 macro_rules! impl_race_tuple {
     ($($F:ident)+) => {
         impl<T, $($F),+> RaceTrait for ($($F,)+)
@@ -37,6 +50,20 @@ macro_rules! impl_race_tuple {
                 ).combine()
             }
         }
+        impl<T, $($F),+> RaceBiased for ($($F,)+)
+        where $(
+            $F: Future<Output = T>,
+        )+ {
+            type Output = <Self::Future as Future>::Output;
+            type Future = <(($($F,)+), BiasedRaceBehavior, PhantomData<T>) as CombineTuple>::Combined;
+            fn race_biased(self) -> Self::Future {
+                (
+                    self,
+                    BiasedRaceBehavior,
+                    PhantomData
+                ).combine()
+            }
+        }
     };
 }
 
@@ -85,4 +112,43 @@ mod test {
             assert!(matches!(result, "hello" | "world"));
         });
     }
+
+    #[test]
+    fn biased() {
+        crate::combinators::block_for_testing(async {
+            let a = future::pending();
+            let b = future::ready("hello");
+            let c = future::ready("world");
+            assert_eq!((a, b, c).race_biased().await, "hello");
+        });
+    }
+
+    #[test]
+    fn biased_after_wake() {
+        use std::cell::{Cell, RefCell};
+        use std::task::Poll;
+        let ready = Cell::new(false);
+        let wakers = RefCell::new(Vec::new());
+        let fut = |name: &'static str| {
+            let (ready, wakers) = (&ready, &wakers);
+            future::poll_fn(move |cx| {
+                if ready.get() {
+                    Poll::Ready(name)
+                } else {
+                    wakers.borrow_mut().push(cx.waker().clone());
+                    Poll::Pending
+                }
+            })
+        };
+        crate::combinators::block_for_testing(async {
+            let mut race = std::pin::pin!((fut("first"), fut("second")).race_biased());
+            assert!(futures_lite::future::poll_once(race.as_mut())
+                .await
+                .is_none());
+            ready.set(true);
+            // wake the second one first
+            wakers.borrow_mut().drain(..).rev().for_each(|w| w.wake());
+            assert_eq!(race.await, "first");
+        });
+    }
 }
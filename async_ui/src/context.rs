@@ -0,0 +1,283 @@
+use std::{any::Any, cell::RefCell, collections::BTreeMap};
+
+use crate::{backend::Backend, position::ChildPosition};
+
+/// Where the current code would render.
+///
+/// The combinators (join, race, ...) only deal with positions, so they work
+/// the same for every [Backend]. Contexts that deal with nodes are behind
+/// the type-erased [NodeContext] trait, so that one thread local can serve
+/// all backends.
+pub(crate) enum RenderContext<'p> {
+    Node(&'p (dyn NodeContext + 'p)),
+    Child {
+        parent: &'p Self,
+        index: u32,
+    },
+    /// Outside of the UI tree, for example in a task spawned on a
+    /// [TaskScope][crate::TaskScope]. Nothing rendered here is shown.
+    Detached,
+}
+
+scoped_tls_hkt::scoped_thread_local!(
+    pub(crate) static RENDER_CONTEXT: for<'p> &'p RenderContext<'p>
+);
+
+/// Get the node that the current code would render into.
+///
+/// Returns `None` outside of the UI tree (when not polled by a node future,
+/// or in a task spawned on a [TaskScope][crate::TaskScope]).
+///
+/// Backends that create nodes ahead of rendering them (like hydration, which
+/// takes over existing nodes) can use this to tell where they will likely go.
+pub fn containing_node<B: Backend>() -> Option<B::Node> {
+    if !RENDER_CONTEXT.is_set() {
+        return None;
+    }
+    RENDER_CONTEXT.with(|ctx| ctx.find_containing_node::<B>())
+}
+
+pub(crate) type NodeGroup<B> = RefCell<BTreeMap<ChildPosition, <B as Backend>::Node>>;
+
+/// A context that renders nodes of some [Backend].
+///
+/// Nodes are passed in and out through a `&mut dyn Any` that is really a
+/// [Slot] of the right backend.
+pub(crate) trait NodeContext {
+    /// Put the input node at the given position.
+    fn add_child(&self, position: ChildPosition, slot: &mut dyn Any);
+    /// Remove the child at the given position and all its descendants.
+    fn remove_child(&self, position: ChildPosition);
+    /// Output the node that children are rendered in.
+    fn containing_node(&self, slot: &mut dyn Any);
+    /// Tells this context apart from others, across polls. This is the address
+    /// of its [NodeGroup], which doesn't move while the future owning it is alive.
+    fn key(&self) -> *const ();
+}
+
+/// Where a [RenderContext] renders: which [NodeContext], and at what position in it.
+/// Unlike the context itself, this can be kept, to remove nodes from elsewhere later.
+pub(crate) struct Location {
+    key: *const (),
+    position: ChildPosition,
+}
+
+pub(crate) struct Slot<B: Backend> {
+    pub input: Option<B::Node>,
+    pub output: Option<B::Node>,
+}
+
+impl<B: Backend> Slot<B> {
+    fn new(input: Option<B::Node>) -> Self {
+        Self {
+            input,
+            output: None,
+        }
+    }
+    pub fn downcast(slot: &mut dyn Any) -> &mut Self {
+        slot.downcast_mut().expect(
+            "Attempted to render a node from one backend inside a node from another backend.",
+        )
+    }
+}
+
+/// Children are rendered inside `container`.
+/// This is used by [ContainerNodeFuture][crate::ContainerNodeFuture].
+pub(crate) struct ContainerContext<'p, B: Backend> {
+    pub group: &'p NodeGroup<B>,
+    pub container: &'p B::Node,
+}
+
+/// Children are rendered right before `reference`, in the parent's containing node.
+/// This is used by [SiblingNodeFuture][crate::SiblingNodeFuture].
+pub(crate) struct SiblingContext<'p, B: Backend> {
+    pub parent: &'p RenderContext<'p>,
+    pub group: &'p NodeGroup<B>,
+    pub reference: &'p B::Node,
+}
+
+impl<'p> RenderContext<'p> {
+    /// Get the node where the current code would render in.
+    /// This is used by [SiblingNodeFuture][crate::SiblingNodeFuture] to decide where to add children.
+    pub fn get_containing_node<B: Backend>(&self) -> B::Node {
+        self.find_containing_node::<B>().unwrap_or_else(|| {
+            panic!(
+                "Attempted to render a sibling node outside the UI tree \
+                (for example, in a task spawned on a `TaskScope`)."
+            )
+        })
+    }
+    /// Like [get_containing_node][Self::get_containing_node], but `None` outside the UI tree.
+    fn find_containing_node<B: Backend>(&self) -> Option<B::Node> {
+        match self {
+            RenderContext::Node(ctx) => {
+                let mut slot = Slot::<B>::new(None);
+                ctx.containing_node(&mut slot);
+                slot.output
+            }
+            RenderContext::Child { parent, .. } => parent.find_containing_node::<B>(),
+            RenderContext::Detached => None,
+        }
+    }
+    /// Add a new node `new_child` ordered relative to existing siblings according to the given [ChildPosition].
+    pub fn add_child<B: Backend>(&self, mut position: ChildPosition, new_child: B::Node) {
+        match self {
+            RenderContext::Node(ctx) => {
+                let mut slot = Slot::<B>::new(Some(new_child));
+                ctx.add_child(position, &mut slot);
+            }
+            RenderContext::Child { parent, index } => {
+                position.wrap(*index);
+                parent.add_child::<B>(position, new_child)
+            }
+            RenderContext::Detached => log_detached_node::<B>(&new_child),
+        }
+    }
+    /// Where this context renders, or `None` outside the UI tree.
+    pub fn location(&self) -> Option<Location> {
+        let mut position = ChildPosition::default();
+        let mut ctx = self;
+        loop {
+            match ctx {
+                RenderContext::Node(node) => {
+                    return Some(Location {
+                        key: node.key(),
+                        position,
+                    })
+                }
+                RenderContext::Child { parent, index } => {
+                    position.wrap(*index);
+                    ctx = parent;
+                }
+                RenderContext::Detached => return None,
+            }
+        }
+    }
+    /// Remove the child at the given [ChildPosition], relative to `location`
+    /// rather than to this context, and all its descendants.
+    ///
+    /// Nothing is removed unless this context renders into the same
+    /// [NodeContext] as `location`: the nodes are out of reach otherwise.
+    pub fn remove_child_at(&self, location: &Location, mut position: ChildPosition) {
+        match self {
+            RenderContext::Node(node) if node.key() == location.key => {
+                position.wrap_all(&location.position);
+                node.remove_child(position);
+            }
+            RenderContext::Child { parent, .. } => parent.remove_child_at(location, position),
+            _ => {}
+        }
+    }
+    /// Remove the child at the given [ChildPosition] and all its descendants.
+    pub fn remove_child(&self, mut position: ChildPosition) {
+        match self {
+            RenderContext::Node(ctx) => ctx.remove_child(position),
+            RenderContext::Child { parent, index } => {
+                position.wrap(*index);
+                parent.remove_child(position);
+            }
+            RenderContext::Detached => {}
+        }
+    }
+}
+
+impl<B: Backend> NodeContext for ContainerContext<'_, B> {
+    fn add_child(&self, position: ChildPosition, slot: &mut dyn Any) {
+        let slot = Slot::<B>::downcast(slot);
+        let new_child = slot.input.take().unwrap();
+        let mut group = self.group.borrow_mut();
+        let reference_sibling = group.range((&position)..).next().map(|(_k, v)| v);
+        B::insert_before(self.container, &new_child, reference_sibling);
+        panic_if_duplicate_node::<B>(group.insert(position, new_child));
+    }
+    fn remove_child(&self, position: ChildPosition) {
+        let mut group = self.group.borrow_mut();
+        remove_children_here::<B>(&mut group, position, self.container);
+    }
+    fn containing_node(&self, slot: &mut dyn Any) {
+        Slot::<B>::downcast(slot).output = Some(self.container.clone());
+    }
+    fn key(&self) -> *const () {
+        (self.group as *const NodeGroup<B>).cast()
+    }
+}
+
+impl<B: Backend> NodeContext for SiblingContext<'_, B> {
+    fn add_child(&self, position: ChildPosition, slot: &mut dyn Any) {
+        let slot = Slot::<B>::downcast(slot);
+        let new_child = slot.input.take().unwrap();
+        let containing_node = self.parent.get_containing_node::<B>();
+        let mut group = self.group.borrow_mut();
+        let reference_sibling = group
+            .range((&position)..)
+            .next()
+            .map(|(_k, v)| v)
+            .unwrap_or(self.reference);
+        B::insert_before(&containing_node, &new_child, Some(reference_sibling));
+        panic_if_duplicate_node::<B>(group.insert(position, new_child));
+    }
+    fn remove_child(&self, position: ChildPosition) {
+        let mut group = self.group.borrow_mut();
+        let containing_node = self.parent.get_containing_node::<B>();
+        remove_children_here::<B>(&mut group, position, &containing_node);
+    }
+    fn containing_node(&self, slot: &mut dyn Any) {
+        Slot::<B>::downcast(slot).output = Some(self.parent.get_containing_node::<B>());
+    }
+    fn key(&self) -> *const () {
+        (self.group as *const NodeGroup<B>).cast()
+    }
+}
+
+pub(crate) fn remove_children_here<B: Backend>(
+    tree: &mut BTreeMap<ChildPosition, B::Node>,
+    position: ChildPosition,
+    container: &B::Node,
+) {
+    if position.is_root() {
+        tree.values().for_each(|child| {
+            B::remove_child(container, child);
+        });
+        tree.clear();
+    } else {
+        let next = position.next_sibling();
+        let range = (&position)..(&next);
+        while let Some((key, child)) = tree.range(range.clone()).next_back() {
+            B::remove_child(container, child);
+            tree.remove(&key.clone());
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn panic_if_duplicate_node<B: Backend>(node: Option<B::Node>) {
+    if let Some(node) = node {
+        B::debug_log(
+            "Attempted to insert two nodes at the same position.\n\
+            You probably either used a `join` implementation from outside Async UI,\
+            or tried to render something in a spawned Future.\n\
+            This message is only shown in debug builds.\n\
+            Check the code where you render this node:\
+            ",
+            &node,
+        );
+        panic!()
+    }
+}
+#[cfg(not(debug_assertions))]
+pub(crate) fn panic_if_duplicate_node<B: Backend>(_node: Option<B::Node>) {}
+
+#[cfg(debug_assertions)]
+fn log_detached_node<B: Backend>(node: &B::Node) {
+    B::debug_log(
+        "Attempted to render a node outside the UI tree.\n\
+        You probably tried to render something in a task spawned on a `TaskScope`.\n\
+        The node will not be shown.\n\
+        This message is only shown in debug builds.\n\
+        Check the code where you render this node:\
+        ",
+        node,
+    );
+}
+#[cfg(not(debug_assertions))]
+fn log_detached_node<B: Backend>(_node: &B::Node) {}
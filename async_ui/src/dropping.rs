@@ -44,6 +44,18 @@ impl DetachmentBlocker {
     }
 }
 
+/// Run `f` as if no ancestor has been removed.
+///
+/// Used by futures whose nodes are not inside their ancestors' nodes
+/// (like [PortalFuture][crate::PortalFuture]), so they still have to be removed
+/// when an ancestor is. The previous blocking is restored afterwards.
+pub(crate) fn ignore_blocking<R>(f: impl FnOnce() -> R) -> R {
+    let previous = IS_DROPPING.with(|cell| cell.replace(std::ptr::null()));
+    let res = f();
+    IS_DROPPING.with(|cell| cell.set(previous));
+    res
+}
+
 impl Drop for DetachmentBlocker {
     fn drop(&mut self) {
         let addr = self as *const DetachmentBlocker;
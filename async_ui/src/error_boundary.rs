@@ -0,0 +1,142 @@
+use std::{
+    any::Any,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+
+use crate::{context::RENDER_CONTEXT, dropping::DetachmentBlocker, position::ChildPosition};
+
+/// The value a panic was started with.
+/// Usually a `&'static str` or a `String`; see [std::panic::panic_any].
+pub type PanicPayload = Box<dyn Any + Send + 'static>;
+
+/// Future wrapper that catches panics in its child and renders a fallback instead.
+///
+/// If polling the child panics, everything the child rendered is removed,
+/// the child is dropped, and the fallback future
+/// (made from the [PanicPayload]) takes its place.
+///
+/// Get this by calling [catch_panic][crate::combinators::UiFutureExt::catch_panic].
+///
+/// This only exists when compiling with `panic = "unwind"`.
+/// **It can't catch panics in the browser**: `wasm32-unknown-unknown` aborts
+/// on panic, so a panic there still brings down the whole app.
+/// It is useful with native backends, and for server-side rendering.
+#[pin_project]
+pub struct ErrorBoundary<C, F, G> {
+    #[pin]
+    state: State<C, F, G>,
+}
+
+#[pin_project(project = StateProj)]
+enum State<C, F, G> {
+    Child {
+        #[pin]
+        future: C,
+        fallback: Option<F>,
+    },
+    Fallback {
+        #[pin]
+        future: G,
+    },
+}
+
+impl<C, F, G> ErrorBoundary<C, F, G>
+where
+    C: Future,
+    F: FnOnce(PanicPayload) -> G,
+    G: Future<Output = C::Output>,
+{
+    pub fn new(child: C, fallback: F) -> Self {
+        Self {
+            state: State::Child {
+                future: child,
+                fallback: Some(fallback),
+            },
+        }
+    }
+}
+
+impl<C, F, G> Future for ErrorBoundary<C, F, G>
+where
+    C: Future,
+    F: FnOnce(PanicPayload) -> G,
+    G: Future<Output = C::Output>,
+{
+    type Output = C::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.project().state;
+        let (payload, fallback) = match state.as_mut().project() {
+            StateProj::Child { future, fallback } => {
+                match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+                    Ok(res) => return res,
+                    Err(payload) => (payload, fallback.take().unwrap()),
+                }
+            }
+            StateProj::Fallback { future } => return future.poll(cx),
+        };
+        // Remove what the child rendered, then drop it without letting its
+        // descendants try to remove their (already removed) nodes.
+        let blocker = DetachmentBlocker;
+        if !blocker.block_until_drop() && RENDER_CONTEXT.is_set() {
+            RENDER_CONTEXT.with(|ctx| ctx.remove_child(ChildPosition::default()));
+        }
+        state.set(State::Fallback {
+            future: fallback(payload),
+        });
+        drop(blocker);
+        match state.project() {
+            StateProj::Fallback { future } => future.poll(cx),
+            StateProj::Child { .. } => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::future::{block_on, poll_once, yield_now};
+
+    use crate::{
+        combinators::{join, UiFutureExt},
+        test_backend::{leaf, names, node, Names},
+        ContainerNodeFuture,
+    };
+
+    #[test]
+    fn catch_panic() {
+        let root = node("root");
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            join((
+                leaf("before"),
+                join((
+                    leaf("a"),
+                    ContainerNodeFuture::<Names, _>::new(leaf("b"), node("c")),
+                    async {
+                        yield_now().await;
+                        panic!("broken");
+                    },
+                ))
+                .catch_panic(|payload| {
+                    assert_eq!(payload.downcast_ref(), Some(&"broken"));
+                    async {
+                        leaf("fallback").await;
+                        unreachable!()
+                    }
+                }),
+                leaf("after"),
+            )),
+            root.clone(),
+        ));
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["before", "a", "c", "after"]);
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["before", "fallback", "after"]);
+        drop(fut);
+        assert_eq!(names(&root), Vec::<&str>::new());
+    }
+}
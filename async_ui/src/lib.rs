@@ -1 +1,42 @@
+/*!
+# Async UI: UI where everything is a Future
 
+This crate contains the parts of Async UI that don't depend on where things
+are rendered: the combinators ([join][combinators::join],
+[race][combinators::race], ...), the logic that keeps rendered nodes in the
+order of the futures that rendered them, and the logic that removes nodes
+when their futures are dropped.
+
+Rendering targets implement [Backend]. The browser DOM implementation lives in
+`async_ui_web_core`; see [async_ui_web](https://docs.rs/async_ui_web) for the
+complete web framework.
+*/
+
+pub mod combinators;
+
+mod backend;
+mod context;
+mod dropping;
+#[cfg(panic = "unwind")]
+mod error_boundary;
+mod node_container;
+mod node_offscreen;
+mod node_portal;
+mod node_sibling;
+mod position;
+mod provide;
+mod task_scope;
+#[cfg(test)]
+mod test_backend;
+
+pub use backend::Backend;
+pub use context::containing_node;
+pub use dropping::DetachmentBlocker;
+#[cfg(panic = "unwind")]
+pub use error_boundary::{ErrorBoundary, PanicPayload};
+pub use node_container::ContainerNodeFuture;
+pub use node_offscreen::OffscreenRoot;
+pub use node_portal::PortalFuture;
+pub use node_sibling::SiblingNodeFuture;
+pub use provide::{provide, use_context, with_context, Provide};
+pub use task_scope::TaskScope;
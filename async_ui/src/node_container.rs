@@ -0,0 +1,150 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::{pin_project, pinned_drop};
+
+use crate::{
+    backend::Backend,
+    context::{ContainerContext, NodeContext, NodeGroup, RenderContext, RENDER_CONTEXT},
+    dropping::DetachmentBlocker,
+    position::ChildPosition,
+};
+
+/// Future wrapper where anything rendered in its child will appear as child of the node.
+/// On the web, all common components (`Div`, `Button`, etc.) uses this internally.
+#[pin_project(PinnedDrop)]
+pub struct ContainerNodeFuture<B: Backend, C> {
+    #[pin]
+    child_future: C,
+    group: NodeGroup<B>,
+    container: B::Node,
+    add_self: AddSelfMode,
+    drop: DetachmentBlocker,
+}
+
+/// Should the node be added to the parent?
+enum AddSelfMode {
+    ShouldNotAdd,
+    ShouldAdd,
+    Added,
+}
+
+impl<B: Backend, C: Future> ContainerNodeFuture<B, C> {
+    /// Return a future wrapping the given child future.
+    /// Any node rendered by the child future will appear inside the given node.
+    /// Upon first poll of the future `node` will be added to the parent.
+    pub fn new(child_future: C, node: B::Node) -> Self {
+        Self {
+            child_future,
+            group: Default::default(),
+            container: node,
+            add_self: AddSelfMode::ShouldAdd,
+            drop: DetachmentBlocker,
+        }
+    }
+    /// Like `new` but `node` won't be added to the parent (do that manually).
+    pub fn new_root(child_future: C, node: B::Node) -> Self {
+        Self {
+            child_future,
+            group: Default::default(),
+            container: node,
+            add_self: AddSelfMode::ShouldNotAdd,
+            drop: DetachmentBlocker,
+        }
+    }
+}
+impl<B: Backend, C: Future> Future for ContainerNodeFuture<B, C> {
+    type Output = C::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if matches!(this.add_self, AddSelfMode::ShouldAdd) {
+            *this.add_self = AddSelfMode::Added;
+            RENDER_CONTEXT
+                .with(|ctx| ctx.add_child::<B>(ChildPosition::default(), this.container.clone()));
+        }
+        let ctx = ContainerContext::<B> {
+            group: this.group,
+            container: this.container,
+        };
+        RENDER_CONTEXT.set(&RenderContext::Node(&ctx), || this.child_future.poll(cx))
+    }
+}
+
+#[pinned_drop]
+impl<B: Backend, C> PinnedDrop for ContainerNodeFuture<B, C> {
+    fn drop(self: Pin<&mut Self>) {
+        if matches!(self.add_self, AddSelfMode::Added) {
+            // we added our node, we should remove it
+            if !self.drop.block_until_drop() {
+                RENDER_CONTEXT.with(|ctx| {
+                    ctx.remove_child(ChildPosition::default());
+                })
+            }
+        } else {
+            // we didn't add our node
+            let this = self.project();
+            // we remove all the children below ourselves, so descendants don't need to
+            this.drop.block_until_drop();
+            ContainerContext::<B> {
+                group: this.group,
+                container: this.container,
+            }
+            .remove_child(ChildPosition::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{
+        combinators::{join, race},
+        containing_node,
+        test_backend::{leaf, names, node, Names},
+    };
+
+    #[test]
+    fn custom_backend() {
+        let root = node("root");
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            join((
+                race((leaf("a"), async {
+                    leaf("b").await;
+                })),
+                leaf("c"),
+            )),
+            root.clone(),
+        ));
+        futures_lite::future::block_on(futures_lite::future::poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["a", "b", "c"]);
+        drop(fut);
+        assert_eq!(names(&root), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn containing_node_is_the_container() {
+        let root = node("root");
+        let outer = node("outer");
+        assert!(containing_node::<Names>().is_none());
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            join((
+                async { containing_node::<Names>() },
+                ContainerNodeFuture::<Names, _>::new(
+                    async { containing_node::<Names>() },
+                    outer.clone(),
+                ),
+            )),
+            root.clone(),
+        ));
+        let (in_root, in_outer) =
+            futures_lite::future::block_on(futures_lite::future::poll_once(fut.as_mut())).unwrap();
+        assert!(Rc::ptr_eq(&in_root.unwrap(), &root));
+        assert!(Rc::ptr_eq(&in_outer.unwrap(), &outer));
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    backend::Backend,
+    context::{ContainerContext, NodeContext, NodeGroup, RenderContext, RENDER_CONTEXT},
+    dropping::DetachmentBlocker,
+    position::ChildPosition,
+};
+
+/// A container outside of the UI tree, for rendering futures out of sight.
+///
+/// Unlike [ContainerNodeFuture][crate::ContainerNodeFuture], it doesn't own
+/// the future it renders. Poll a future inside it with [poll][Self::poll]
+/// while the future is hidden, and poll the future in its real place otherwise.
+/// Keep one `OffscreenRoot` for as long as the future lives, so that
+/// everything it rendered while hidden is tracked in one place.
+///
+/// Nodes still rendered directly inside the container are removed when
+/// the `OffscreenRoot` is dropped. Drop the future first, with detachment
+/// [blocked][DetachmentBlocker], since its nodes are not in the surrounding tree.
+pub struct OffscreenRoot<B: Backend> {
+    group: NodeGroup<B>,
+    container: B::Node,
+    drop: DetachmentBlocker,
+}
+
+impl<B: Backend> OffscreenRoot<B> {
+    /// Render into `container`. The container itself is not added anywhere.
+    pub fn new(container: B::Node) -> Self {
+        Self {
+            group: Default::default(),
+            container,
+            drop: DetachmentBlocker,
+        }
+    }
+    /// The node things are rendered into.
+    pub fn container(&self) -> &B::Node {
+        &self.container
+    }
+    /// Poll `future` as if it were rendered inside the container.
+    pub fn poll<F: Future + ?Sized>(
+        &self,
+        future: Pin<&mut F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<F::Output> {
+        let ctx = ContainerContext::<B> {
+            group: &self.group,
+            container: &self.container,
+        };
+        RENDER_CONTEXT.set(&RenderContext::Node(&ctx), || future.poll(cx))
+    }
+}
+
+impl<B: Backend> Drop for OffscreenRoot<B> {
+    fn drop(&mut self) {
+        self.drop.block_until_drop();
+        ContainerContext::<B> {
+            group: &self.group,
+            container: &self.container,
+        }
+        .remove_child(ChildPosition::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::future::{block_on, poll_once};
+
+    use super::*;
+    use crate::{
+        combinators::join,
+        test_backend::{leaf, names, node, Names},
+    };
+
+    #[test]
+    fn offscreen() {
+        let hidden = OffscreenRoot::<Names>::new(node("hidden"));
+        let mut child = Box::pin(join((leaf("a"), leaf("b"))));
+        for _ in 0..2 {
+            block_on(poll_once(std::future::poll_fn(|cx| {
+                hidden.poll(child.as_mut(), cx)
+            })));
+            // polling again doesn't render anything twice
+            assert_eq!(names(hidden.container()), ["a", "b"]);
+        }
+        let container = hidden.container().clone();
+        let blocker = DetachmentBlocker;
+        blocker.block_until_drop();
+        drop(child);
+        drop(blocker);
+        drop(hidden);
+        assert_eq!(names(&container), Vec::<&str>::new());
+    }
+}
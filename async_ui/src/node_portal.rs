@@ -0,0 +1,164 @@
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::{pin_project, pinned_drop};
+
+use crate::{
+    backend::Backend,
+    context::{
+        panic_if_duplicate_node, remove_children_here, NodeContext, NodeGroup, RenderContext, Slot,
+        RENDER_CONTEXT,
+    },
+    dropping::{ignore_blocking, DetachmentBlocker},
+    position::ChildPosition,
+};
+
+/// Future wrapper where anything rendered in its child will appear inside
+/// some other node (the target), instead of where the future itself is.
+///
+/// Everything is still owned by the future: when it is dropped, the things
+/// it rendered are removed, even if an ancestor node was removed first.
+///
+/// On first poll, a marker node is appended to the target, and everything is
+/// rendered right before that marker. So multiple portals into the same target
+/// stay in the order they were first polled.
+#[pin_project(PinnedDrop)]
+pub struct PortalFuture<B: Backend, C> {
+    #[pin]
+    child_future: C,
+    group: NodeGroup<B>,
+    target: B::Node,
+    marker: Option<B::Node>,
+    drop: DetachmentBlocker,
+}
+
+impl<B: Backend, C: Future> PortalFuture<B, C> {
+    /// Return a future wrapping the given child future.
+    /// Any node rendered by the child future will appear inside `target`.
+    pub fn new(child_future: C, target: B::Node) -> Self {
+        Self {
+            child_future,
+            group: Default::default(),
+            target,
+            marker: None,
+            drop: DetachmentBlocker,
+        }
+    }
+}
+
+impl<B: Backend, C: Future> Future for PortalFuture<B, C> {
+    type Output = C::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let marker = this.marker.get_or_insert_with(|| {
+            let marker = B::create_marker();
+            B::insert_before(this.target, &marker, None);
+            marker
+        });
+        let ctx = PortalContext::<B> {
+            group: this.group,
+            target: this.target,
+            marker,
+        };
+        RENDER_CONTEXT.set(&RenderContext::Node(&ctx), || this.child_future.poll(cx))
+    }
+}
+
+#[pinned_drop]
+impl<B: Backend, C> PinnedDrop for PortalFuture<B, C> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        let Some(marker) = this.marker else {
+            return;
+        };
+        // Our nodes are not inside our ancestors' nodes,
+        // so remove them even if our ancestors have been removed.
+        ignore_blocking(|| {
+            this.drop.block_until_drop();
+            PortalContext::<B> {
+                group: this.group,
+                target: this.target,
+                marker,
+            }
+            .remove_child(ChildPosition::default());
+        });
+        B::remove_child(this.target, marker);
+    }
+}
+
+/// Children are rendered right before `marker`, in `target`.
+struct PortalContext<'p, B: Backend> {
+    group: &'p NodeGroup<B>,
+    target: &'p B::Node,
+    marker: &'p B::Node,
+}
+
+impl<B: Backend> NodeContext for PortalContext<'_, B> {
+    fn add_child(&self, position: ChildPosition, slot: &mut dyn Any) {
+        let slot = Slot::<B>::downcast(slot);
+        let new_child = slot.input.take().unwrap();
+        let mut group = self.group.borrow_mut();
+        let reference_sibling = group
+            .range((&position)..)
+            .next()
+            .map(|(_k, v)| v)
+            .unwrap_or(self.marker);
+        B::insert_before(self.target, &new_child, Some(reference_sibling));
+        panic_if_duplicate_node::<B>(group.insert(position, new_child));
+    }
+    fn remove_child(&self, position: ChildPosition) {
+        let mut group = self.group.borrow_mut();
+        remove_children_here::<B>(&mut group, position, self.target);
+    }
+    fn containing_node(&self, slot: &mut dyn Any) {
+        Slot::<B>::downcast(slot).output = Some(self.target.clone());
+    }
+    fn key(&self) -> *const () {
+        (self.group as *const NodeGroup<B>).cast()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::future::{block_on, poll_once};
+
+    use super::*;
+    use crate::{
+        combinators::{join, race},
+        test_backend::{leaf, names, node, Names},
+        ContainerNodeFuture,
+    };
+
+    #[test]
+    fn portal() {
+        let root = node("root");
+        let target = node("target");
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            join((
+                ContainerNodeFuture::<Names, _>::new(
+                    PortalFuture::<Names, _>::new(join((leaf("a"), leaf("b"))), target.clone()),
+                    node("parent"),
+                ),
+                PortalFuture::<Names, _>::new(
+                    race((leaf("c"), async {
+                        leaf("d").await;
+                    })),
+                    target.clone(),
+                ),
+                leaf("e"),
+            )),
+            root.clone(),
+        ));
+        block_on(poll_once(fut.as_mut()));
+        assert_eq!(names(&root), ["parent", "e"]);
+        assert_eq!(names(&target), ["a", "b", "", "c", "d", ""]);
+        drop(fut);
+        assert_eq!(names(&root), Vec::<&str>::new());
+        assert_eq!(names(&target), Vec::<&str>::new());
+    }
+}
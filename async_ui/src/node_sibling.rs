@@ -7,7 +7,8 @@ use std::{
 use pin_project::{pin_project, pinned_drop};
 
 use crate::{
-    context::{DomContext, NodeGroup, DOM_CONTEXT},
+    backend::Backend,
+    context::{NodeContext, NodeGroup, RenderContext, SiblingContext, RENDER_CONTEXT},
     dropping::DetachmentBlocker,
     position::ChildPosition,
 };
@@ -17,16 +18,16 @@ use crate::{
 ///
 /// Everything rendered will appear **before** the reference node.
 #[pin_project(PinnedDrop)]
-pub struct SiblingNodeFuture<C> {
+pub struct SiblingNodeFuture<B: Backend, C> {
     #[pin]
     child_future: C,
-    group: NodeGroup,
-    reference: web_sys::Node,
+    group: NodeGroup<B>,
+    reference: B::Node,
     drop: DetachmentBlocker,
 }
 
-impl<C: Future> SiblingNodeFuture<C> {
-    pub fn new(child_future: C, sibling: web_sys::Node) -> Self {
+impl<B: Backend, C: Future> SiblingNodeFuture<B, C> {
+    pub fn new(child_future: C, sibling: B::Node) -> Self {
         Self {
             child_future,
             group: Default::default(),
@@ -35,29 +36,29 @@ impl<C: Future> SiblingNodeFuture<C> {
         }
     }
 }
-impl<C: Future> Future for SiblingNodeFuture<C> {
+impl<B: Backend, C: Future> Future for SiblingNodeFuture<B, C> {
     type Output = C::Output;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
-        DOM_CONTEXT.with(|parent: &DomContext| {
-            let ctx = DomContext::Sibling {
+        RENDER_CONTEXT.with(|parent: &RenderContext| {
+            let ctx = SiblingContext::<B> {
                 group: this.group,
                 reference: this.reference,
                 parent,
             };
-            DOM_CONTEXT.set(&ctx, || this.child_future.poll(cx))
+            RENDER_CONTEXT.set(&RenderContext::Node(&ctx), || this.child_future.poll(cx))
         })
     }
 }
 
 #[pinned_drop]
-impl<C> PinnedDrop for SiblingNodeFuture<C> {
+impl<B: Backend, C> PinnedDrop for SiblingNodeFuture<B, C> {
     fn drop(self: Pin<&mut Self>) {
         let this = self.project();
         if !this.drop.block_until_drop() {
-            DOM_CONTEXT.with(|parent: &DomContext| {
-                (DomContext::Sibling {
+            RENDER_CONTEXT.with(|parent: &RenderContext| {
+                (SiblingContext::<B> {
                     group: this.group,
                     reference: this.reference,
                     parent,
@@ -1,5 +1,5 @@
 /*!
-For ordering DOM nodes relative to each other.
+For ordering nodes (DOM nodes, on the web) relative to each other.
 
 When multiple futures are combined (by join, race, etc.), we want to render
 them in order. For example, this arrangement of futures
@@ -55,7 +55,7 @@ should render to
 To acheive this, the combinators (join/race/...) give each child its index ([PositionSegment]).
 When a future wants to insert something, the indices are assembled into a path ([ChildPosition]).
 Paths are ordered and stored in a [BTreeMap][std::collections::BTreeMap], so we find the rendered element
-with the next higher path and `insertBefore` that element.
+with the next higher path and [insert before][crate::Backend::insert_before] that element.
 */
 
 use smallvec::SmallVec;
@@ -74,7 +74,7 @@ fn compare_slice_reversed<C: Ord>(s1: &[C], s2: &[C]) -> Ordering {
 }
 
 /// An index that combinators give their children.
-type PositionSegment = u32;
+pub(crate) type PositionSegment = u32;
 
 /// A path assembled from indices.
 ///
@@ -115,4 +115,9 @@ impl ChildPosition {
     pub fn is_root(&self) -> bool {
         self.0.is_empty()
     }
+    /// Add all the segments of `outer` to the path, as if by [wrap][Self::wrap]
+    /// for each of them.
+    pub fn wrap_all(&mut self, outer: &ChildPosition) {
+        self.0.extend_from_slice(&outer.0);
+    }
 }
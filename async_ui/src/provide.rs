@@ -0,0 +1,151 @@
+//! Passing values down the tree of futures.
+//!
+//! [provide] makes a value available to everything its child future
+//! (and the futures inside it) does while being polled.
+//! [use_context] gets the value back.
+//!
+//! This works the same way as the render context: the value is set in a
+//! scoped thread local for the duration of the child's poll.
+//! So futures polled from outside the tree (for example, tasks spawned on the
+//! app-wide executor) won't see it.
+
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+
+/// One provided value, and a link to the values provided further up.
+struct Scope<'p> {
+    value: &'p dyn Any,
+    parent: Option<&'p Scope<'p>>,
+}
+
+scoped_tls_hkt::scoped_thread_local!(
+    static PROVIDED: for<'p> &'p Scope<'p>
+);
+
+/// Make `value` available through [use_context] to everything inside `child_future`.
+///
+/// If a value of the same type was already provided further up,
+/// this one shadows it inside `child_future`.
+///
+/// ```rust
+/// # use async_ui::{provide, use_context};
+/// #[derive(Clone, PartialEq, Debug)]
+/// struct Theme {
+///     dark: bool,
+/// }
+/// async fn themed_widget() {
+///     let theme = use_context::<Theme>().unwrap();
+///     assert!(theme.dark);
+/// }
+/// # let _ = async {
+/// provide(Theme { dark: true }, themed_widget()).await;
+/// # };
+/// ```
+pub fn provide<T: 'static, C: Future>(value: T, child_future: C) -> Provide<T, C> {
+    Provide {
+        child_future,
+        value,
+    }
+}
+
+/// Get a clone of the nearest provided value of type `T`.
+///
+/// Returns `None` if no such value was [provided][provide], or if called
+/// from outside a poll.
+///
+/// To avoid expensive clones, provide an `Rc` (or something similar) of your value.
+pub fn use_context<T: Clone + 'static>() -> Option<T> {
+    with_context(T::clone)
+}
+
+/// Like [use_context] but borrow the value instead of cloning it.
+pub fn with_context<T: 'static, R>(f: impl FnOnce(&T) -> R) -> Option<R> {
+    if !PROVIDED.is_set() {
+        return None;
+    }
+    PROVIDED.with(|scope| {
+        let mut scope = Some(scope);
+        while let Some(s) = scope {
+            if let Some(value) = s.value.downcast_ref::<T>() {
+                return Some(f(value));
+            }
+            scope = s.parent;
+        }
+        None
+    })
+}
+
+/// Future wrapper that provides a value to its child future.
+/// Get this by calling [provide].
+#[pin_project]
+pub struct Provide<T, C> {
+    #[pin]
+    child_future: C,
+    value: T,
+}
+
+impl<T: 'static, C: Future> Future for Provide<T, C> {
+    type Output = C::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let value: &dyn Any = &*this.value;
+        let child_future = this.child_future;
+        if PROVIDED.is_set() {
+            PROVIDED.with(|parent| {
+                let scope = Scope {
+                    value,
+                    parent: Some(parent),
+                };
+                PROVIDED.set(&scope, || child_future.poll(cx))
+            })
+        } else {
+            let scope = Scope {
+                value,
+                parent: None,
+            };
+            PROVIDED.set(&scope, || child_future.poll(cx))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use futures_lite::future::yield_now;
+
+    use super::*;
+    use crate::combinators::{block_for_testing, join};
+
+    #[test]
+    fn nesting_and_shadowing() {
+        assert_eq!(use_context::<u32>(), None);
+        block_for_testing(provide(
+            1u32,
+            provide(Rc::new("outer"), async {
+                assert_eq!(use_context::<u32>(), Some(1));
+                join((
+                    provide(2u32, async {
+                        yield_now().await;
+                        // still visible after being woken and polled again
+                        assert_eq!(use_context::<u32>(), Some(2));
+                        assert_eq!(with_context(|s: &Rc<&str>| s.len()), Some(5));
+                    }),
+                    async {
+                        yield_now().await;
+                        assert_eq!(use_context::<u32>(), Some(1));
+                    },
+                ))
+                .await;
+                assert_eq!(use_context::<String>(), None);
+            }),
+        ));
+    }
+}
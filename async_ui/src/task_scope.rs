@@ -0,0 +1,156 @@
+//! Background tasks that live as long as a component.
+
+use std::{
+    future::{pending, Future},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_executor::{LocalExecutor, Task};
+use pin_project::pin_project;
+
+use crate::context::{RenderContext, RENDER_CONTEXT};
+
+/// A set of background tasks owned by a component.
+///
+/// Unlike tasks spawned on an app-wide executor, tasks spawned here
+/// can borrow from the component (they don't need to be `'static`),
+/// and they are all cancelled when the `TaskScope` is dropped.
+///
+/// The tasks only run while [run][TaskScope::run] is being polled,
+/// so put it alongside the rest of your component.
+///
+/// ```rust
+/// # use std::cell::Cell;
+/// # use async_ui::{combinators::join, TaskScope};
+/// # async fn tick() {}
+/// # async fn show_count(_count: &Cell<u32>) {}
+/// # let _ = async {
+/// let count = Cell::new(0);
+/// let scope = TaskScope::new();
+/// // No need to keep the `Task`; the scope keeps it running.
+/// scope
+///     .spawn(async {
+///         loop {
+///             tick().await;
+///             count.set(count.get() + 1);
+///         }
+///     })
+///     .detach();
+/// join((scope.run(), show_count(&count))).await;
+/// # };
+/// ```
+///
+/// Tasks run outside of the UI tree, so they shouldn't render anything.
+/// Anything they do render is not shown
+/// (and a message is logged in debug builds).
+pub struct TaskScope<'c> {
+    executor: LocalExecutor<'c>,
+}
+
+impl<'c> TaskScope<'c> {
+    pub fn new() -> Self {
+        Self {
+            executor: LocalExecutor::new(),
+        }
+    }
+    /// Spawn a task on this scope.
+    ///
+    /// The task is cancelled if the returned [Task] is dropped.
+    /// Call [detach][Task::detach] to keep it running until the scope is dropped.
+    pub fn spawn<T: 'c>(&self, future: impl Future<Output = T> + 'c) -> Task<T> {
+        self.executor.spawn(future)
+    }
+    /// Run the tasks of this scope. This future never completes.
+    pub async fn run(&self) {
+        Detached {
+            future: self.executor.run(pending::<()>()),
+        }
+        .await
+    }
+}
+
+impl<'c> Default for TaskScope<'c> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'c> Drop for TaskScope<'c> {
+    fn drop(&mut self) {
+        // The tasks are cancelled when the executor is dropped.
+        // Give them the same context they were polled in.
+        let executor = std::mem::replace(&mut self.executor, LocalExecutor::new());
+        RENDER_CONTEXT.set(&RenderContext::Detached, || drop(executor));
+    }
+}
+
+/// Polls the inner future outside of the UI tree.
+#[pin_project]
+struct Detached<F> {
+    #[pin]
+    future: F,
+}
+
+impl<F: Future> Future for Detached<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.project().future;
+        RENDER_CONTEXT.set(&RenderContext::Detached, || future.poll(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use futures_lite::future::{block_on, poll_once, yield_now};
+
+    use super::*;
+    use crate::{
+        combinators::join,
+        test_backend::{leaf, names, node, Names},
+        ContainerNodeFuture,
+    };
+
+    struct SetOnDrop<'a>(&'a Cell<bool>);
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn scoped_tasks() {
+        let root = node("root");
+        let count = Cell::new(0);
+        let cancelled = Cell::new(false);
+        let component = async {
+            let scope = TaskScope::new();
+            scope
+                .spawn(async {
+                    let _guard = SetOnDrop(&cancelled);
+                    loop {
+                        count.set(count.get() + 1);
+                        yield_now().await;
+                    }
+                })
+                .detach();
+            scope.spawn(leaf("from task")).detach();
+            join((scope.run(), leaf("component"))).await;
+        };
+        let mut fut = Box::pin(ContainerNodeFuture::<Names, _>::new_root(
+            component,
+            root.clone(),
+        ));
+        block_on(poll_once(fut.as_mut()));
+        block_on(poll_once(fut.as_mut()));
+        assert!(count.get() >= 2);
+        assert_eq!(names(&root), ["component"]);
+        assert!(!cancelled.get());
+        drop(fut);
+        assert!(cancelled.get());
+        assert_eq!(names(&root), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,47 @@
+//! A backend whose nodes are just names with children, for testing.
+
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+use crate::{backend::Backend, ContainerNodeFuture};
+
+pub(crate) struct Names;
+
+#[derive(Debug)]
+pub(crate) struct NameNode {
+    name: &'static str,
+    children: RefCell<Vec<Rc<NameNode>>>,
+}
+
+impl Backend for Names {
+    type Node = Rc<NameNode>;
+    fn insert_before(container: &Self::Node, node: &Self::Node, reference: Option<&Self::Node>) {
+        let mut children = container.children.borrow_mut();
+        let index = reference
+            .and_then(|r| children.iter().position(|c| Rc::ptr_eq(c, r)))
+            .unwrap_or(children.len());
+        children.insert(index, node.clone());
+    }
+    fn remove_child(container: &Self::Node, node: &Self::Node) {
+        container
+            .children
+            .borrow_mut()
+            .retain(|c| !Rc::ptr_eq(c, node));
+    }
+    fn create_marker() -> Self::Node {
+        node("")
+    }
+}
+
+pub(crate) fn node(name: &'static str) -> Rc<NameNode> {
+    Rc::new(NameNode {
+        name,
+        children: Default::default(),
+    })
+}
+pub(crate) fn names(node: &NameNode) -> Vec<&'static str> {
+    node.children.borrow().iter().map(|c| c.name).collect()
+}
+/// A node that stays rendered forever.
+pub(crate) fn leaf(name: &'static str) -> ContainerNodeFuture<Names, impl Future<Output = ()>> {
+    ContainerNodeFuture::new(std::future::pending(), node(name))
+}
@@ -0,0 +1,20 @@
+use async_ui::Backend;
+
+use crate::node::Node;
+
+/// The terminal UI tree as an Async UI [Backend].
+pub struct Terminal;
+
+impl Backend for Terminal {
+    type Node = Node;
+
+    fn insert_before(container: &Node, node: &Node, reference: Option<&Node>) {
+        container.insert_before(node, reference);
+    }
+    fn remove_child(container: &Node, node: &Node) {
+        container.remove_child(node);
+    }
+    fn create_marker() -> Node {
+        Node::new_marker()
+    }
+}
@@ -0,0 +1,74 @@
+//! In-memory screen contents.
+
+use crossterm::style::Color;
+
+/// One character cell of the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    /// Foreground color. `None` is the terminal's default color.
+    pub fg: Option<Color>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            fg: None,
+        }
+    }
+}
+
+/// A grid of [Cell]s, row by row.
+///
+/// The UI is drawn into a buffer, which is then written out to the terminal.
+/// In tests, inspect the buffer directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    /// Create a buffer of empty cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+    /// The cell at column `x` of row `y`. Panics if out of bounds.
+    pub fn get(&self, x: u16, y: u16) -> &Cell {
+        assert!(x < self.width && y < self.height, "cell out of bounds");
+        &self.cells[self.index(x, y)]
+    }
+    /// Set the cell at column `x` of row `y`. Does nothing if out of bounds.
+    pub fn set(&mut self, x: u16, y: u16, cell: Cell) {
+        if x < self.width && y < self.height {
+            let index = self.index(x, y);
+            self.cells[index] = cell;
+        }
+    }
+    /// The text of each row, without trailing spaces.
+    pub fn lines(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.width.max(1) as usize)
+            .take(self.height as usize)
+            .map(|row| {
+                let line: String = row.iter().map(|cell| cell.symbol).collect();
+                line.trim_end().to_owned()
+            })
+            .collect()
+    }
+    fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
@@ -0,0 +1,128 @@
+//! The building blocks of terminal UIs.
+
+use std::future::{pending, Future, Pending};
+
+use async_ui::ContainerNodeFuture;
+use crossterm::style::Color;
+
+use crate::{
+    backend::Terminal,
+    node::{mark_changed, BlockStyle, Node, NodeKind},
+};
+
+/// A box that lays out the things rendered inside it.
+/// See [BlockStyle] for the options.
+///
+/// ```
+/// # use async_ui_terminal::components::{Block, Text};
+/// # let _ = async {
+/// let block = Block::new();
+/// block.update_style(|style| {
+///     style.border = true;
+///     style.title = Some("Greeting".into());
+/// });
+/// block.render(Text::new("Hello World").render()).await;
+/// # };
+/// ```
+pub struct Block {
+    pub node: Node,
+}
+
+impl Block {
+    /// Create a new block with the default style
+    /// (children top to bottom, no border, no padding).
+    ///
+    /// This creates the node, but doesn't put it on the screen yet.
+    /// Use the `.render(_)` method to do that.
+    pub fn new() -> Self {
+        Self::new_with_style(BlockStyle::default())
+    }
+    pub fn new_with_style(style: BlockStyle) -> Self {
+        Self {
+            node: Node::new_block(style),
+        }
+    }
+    pub fn style(&self) -> BlockStyle {
+        self.style_cell().borrow().clone()
+    }
+    pub fn set_style(&self, style: BlockStyle) {
+        *self.style_cell().borrow_mut() = style;
+        mark_changed();
+    }
+    /// Modify the style in place.
+    pub fn update_style(&self, f: impl FnOnce(&mut BlockStyle)) {
+        f(&mut self.style_cell().borrow_mut());
+        mark_changed();
+    }
+    fn style_cell(&self) -> &std::cell::RefCell<BlockStyle> {
+        match self.node.kind() {
+            NodeKind::Block(style) => style,
+            _ => unreachable!(),
+        }
+    }
+    /// Put this block on the screen.
+    ///
+    /// The return Future completes when the given argument Future finishes.
+    /// Anything the argument Future renders will be inside this block.
+    ///
+    /// When the returned Future is dropped, the block will be removed.
+    ///
+    /// This method should only be called once. It may misbehave otherwise.
+    pub fn render<F: Future>(&self, c: F) -> ContainerNodeFuture<Terminal, F> {
+        ContainerNodeFuture::new(c, self.node.clone())
+    }
+}
+
+impl Default for Block {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A piece of text. Text containing newlines spans multiple lines.
+pub struct Text {
+    pub node: Node,
+}
+
+impl Text {
+    /// Create a new text node.
+    ///
+    /// This creates the node, but doesn't put it on the screen yet.
+    /// Use the `.render()` method to do that.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            node: Node::new_text(text.into()),
+        }
+    }
+    pub fn text(&self) -> String {
+        self.with_content(|text, _| text.clone())
+    }
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.with_content(|old, _| *old = text.into());
+        mark_changed();
+    }
+    /// Set the foreground color. `None` uses the terminal's default color.
+    pub fn set_color(&self, color: Option<Color>) {
+        self.with_content(|_, old| *old = color);
+        mark_changed();
+    }
+    fn with_content<R>(&self, f: impl FnOnce(&mut String, &mut Option<Color>) -> R) -> R {
+        match self.node.kind() {
+            NodeKind::Text(content) => {
+                let content = &mut *content.borrow_mut();
+                f(&mut content.text, &mut content.color)
+            }
+            _ => unreachable!(),
+        }
+    }
+    /// Put this text on the screen.
+    ///
+    /// This method returns a Future that never finishes.
+    ///
+    /// When the returned Future is dropped, the text will be removed.
+    ///
+    /// This method should only be called once. It may misbehave otherwise.
+    pub fn render(&self) -> ContainerNodeFuture<Terminal, Pending<()>> {
+        ContainerNodeFuture::new(pending(), self.node.clone())
+    }
+}
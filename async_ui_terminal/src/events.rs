@@ -0,0 +1,111 @@
+//! Keyboard input.
+//!
+//! The terminal has no focus or element targets, so every key press goes to
+//! every listener.
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll, Waker},
+};
+
+use async_ui_internal_utils::dummy_waker::dummy_waker;
+pub use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_core::Stream;
+
+type Shared = RefCell<(Option<KeyEvent>, Waker)>;
+
+thread_local! {
+    static LISTENERS: RefCell<Vec<Weak<Shared>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Send a key event to every [KeyEventStream] that is listening.
+///
+/// [run][crate::run] and [TerminalRoot::send_key][crate::TerminalRoot::send_key]
+/// call this for you.
+pub fn dispatch_key(event: KeyEvent) {
+    let listeners = LISTENERS.with(|l| {
+        let mut l = l.borrow_mut();
+        l.retain(|weak| weak.strong_count() > 0);
+        l.clone()
+    });
+    for listener in listeners.iter().filter_map(Weak::upgrade) {
+        let inner = &mut *listener.borrow_mut();
+        inner.0 = Some(event);
+        inner.1.wake_by_ref();
+    }
+}
+
+/// Wait until a key is pressed.
+/// The return type is both a [Future] and a [Stream] that yields the [KeyEvent].
+///
+/// ```
+/// # use async_ui_terminal::events::{until_key, KeyCode};
+/// # let _ = async {
+/// loop {
+///     if until_key().await.code == KeyCode::Char('q') {
+///         break;
+///     }
+/// }
+/// # };
+/// ```
+pub fn until_key() -> KeyEventStream {
+    KeyEventStream {
+        shared: Rc::new(RefCell::new((None, dummy_waker()))),
+        registered: false,
+    }
+}
+
+/// A struct implementing both [Future] and [Stream].
+/// Yields [KeyEvent]s.
+///
+/// Use [until_key] to get this struct.
+///
+/// ### Notes for the Stream API
+///
+/// *   The returned Stream is never exhausted.
+/// *   The implementation only keeps the last event it receives.
+///     This means if you use some custom manually-implemented wrapper futures and
+///     fail to poll the Stream upon `wake`, you might miss some
+///     in-between events.
+pub struct KeyEventStream {
+    shared: Rc<Shared>,
+    registered: bool,
+}
+
+impl Future for KeyEventStream {
+    type Output = KeyEvent;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.poll_next(cx) {
+            Poll::Ready(Some(ev)) => Poll::Ready(ev),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+impl Stream for KeyEventStream {
+    type Item = KeyEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        {
+            let shared = &mut *this.shared.borrow_mut();
+            let waker = cx.waker();
+            if !shared.1.will_wake(waker) {
+                shared.1 = waker.to_owned();
+            }
+        }
+        if !this.registered {
+            this.registered = true;
+            LISTENERS.with(|l| l.borrow_mut().push(Rc::downgrade(&this.shared)));
+            Poll::Pending
+        } else if let Some(ev) = this.shared.borrow_mut().0.take() {
+            Poll::Ready(Some(ev))
+        } else {
+            Poll::Pending
+        }
+    }
+}
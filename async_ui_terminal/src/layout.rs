@@ -0,0 +1,172 @@
+//! Sizing and drawing the tree.
+//!
+//! Blocks are as small as their content allows, except that children of a
+//! [Column][Direction::Column] block (and the root) are stretched to its full width.
+//! Anything that doesn't fit is cut off.
+
+use crate::{
+    buffer::{Buffer, Cell},
+    node::{Direction, Node, NodeKind},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    fn shrink(self, by: u16) -> Self {
+        Self {
+            x: self.x.saturating_add(by),
+            y: self.y.saturating_add(by),
+            width: self.width.saturating_sub(by.saturating_mul(2)),
+            height: self.height.saturating_sub(by.saturating_mul(2)),
+        }
+    }
+}
+
+/// The width and height `node` wants.
+pub(crate) fn measure(node: &Node) -> (u16, u16) {
+    match node.kind() {
+        NodeKind::Text(content) => {
+            let content = content.borrow();
+            let width = content.text.lines().map(|l| l.chars().count()).max();
+            (
+                clamp(width.unwrap_or(0)),
+                clamp(content.text.lines().count()),
+            )
+        }
+        NodeKind::Block(style) => {
+            let style = style.borrow();
+            let sizes = node.with_children(|children| {
+                children
+                    .iter()
+                    .map(measure)
+                    .filter(|&(w, h)| w > 0 || h > 0)
+                    .collect::<Vec<_>>()
+            });
+            let gaps = style
+                .gap
+                .saturating_mul(clamp(sizes.len().saturating_sub(1)));
+            let (width, height) = match style.direction {
+                Direction::Column => (
+                    sizes.iter().map(|s| s.0).max().unwrap_or(0),
+                    sizes.iter().fold(gaps, |a, s| a.saturating_add(s.1)),
+                ),
+                Direction::Row => (
+                    sizes.iter().fold(gaps, |a, s| a.saturating_add(s.0)),
+                    sizes.iter().map(|s| s.1).max().unwrap_or(0),
+                ),
+            };
+            let edge = (style.border as u16 + style.padding).saturating_mul(2);
+            (width.saturating_add(edge), height.saturating_add(edge))
+        }
+        NodeKind::Marker => (0, 0),
+    }
+}
+
+/// Draw `node` into `area` of the buffer.
+pub(crate) fn draw(node: &Node, area: Rect, buffer: &mut Buffer) {
+    match node.kind() {
+        NodeKind::Text(content) => {
+            let content = content.borrow();
+            for (y, line) in (area.y..area.y + area.height).zip(content.text.lines()) {
+                for (x, symbol) in (area.x..area.x + area.width).zip(line.chars()) {
+                    let fg = content.color;
+                    buffer.set(x, y, Cell { symbol, fg });
+                }
+            }
+        }
+        NodeKind::Block(style) => {
+            let style = style.borrow().clone();
+            let mut inner = area;
+            if style.border {
+                draw_border(area, style.title.as_deref(), buffer);
+                inner = inner.shrink(1);
+            }
+            draw_children(
+                node,
+                inner.shrink(style.padding),
+                style.direction,
+                style.gap,
+                buffer,
+            );
+        }
+        NodeKind::Marker => {}
+    }
+}
+
+/// Draw the children of `node` one after another in `area`.
+pub(crate) fn draw_children(
+    node: &Node,
+    area: Rect,
+    direction: Direction,
+    gap: u16,
+    buffer: &mut Buffer,
+) {
+    let (mut x, mut y) = (area.x, area.y);
+    let (right, bottom) = (area.x + area.width, area.y + area.height);
+    node.with_children(|children| {
+        for child in children {
+            let (width, height) = measure(child);
+            if width == 0 && height == 0 {
+                continue;
+            }
+            match direction {
+                Direction::Column => {
+                    let height = height.min(bottom.saturating_sub(y));
+                    let rect = Rect {
+                        x,
+                        y,
+                        width: area.width,
+                        height,
+                    };
+                    draw(child, rect, buffer);
+                    y = y.saturating_add(height).saturating_add(gap);
+                }
+                Direction::Row => {
+                    let width = width.min(right.saturating_sub(x));
+                    let rect = Rect {
+                        x,
+                        y,
+                        width,
+                        height: height.min(area.height),
+                    };
+                    draw(child, rect, buffer);
+                    x = x.saturating_add(width).saturating_add(gap);
+                }
+            }
+        }
+    });
+}
+
+fn draw_border(area: Rect, title: Option<&str>, buffer: &mut Buffer) {
+    if area.width < 2 || area.height < 2 {
+        return;
+    }
+    let (left, top) = (area.x, area.y);
+    let (right, bottom) = (area.x + area.width - 1, area.y + area.height - 1);
+    let mut set = |x, y, symbol| buffer.set(x, y, Cell { symbol, fg: None });
+    for x in left + 1..right {
+        set(x, top, '─');
+        set(x, bottom, '─');
+    }
+    for y in top + 1..bottom {
+        set(left, y, '│');
+        set(right, y, '│');
+    }
+    set(left, top, '┌');
+    set(right, top, '┐');
+    set(left, bottom, '└');
+    set(right, bottom, '┘');
+    for (x, symbol) in (left + 1..right).zip(title.unwrap_or_default().chars()) {
+        set(x, top, symbol);
+    }
+}
+
+fn clamp(value: usize) -> u16 {
+    value.try_into().unwrap_or(u16::MAX)
+}
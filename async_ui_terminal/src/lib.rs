@@ -0,0 +1,63 @@
+/*!
+# Async UI for the terminal
+
+Render [Async UI](async_ui) components as text in the terminal.
+
+Components are [Block]s, which lay out their children in a column or row
+(optionally with a border), and [Text]s.
+Like on the web, things appear in the order of the futures that render them,
+and disappear when those futures are dropped.
+The screen is laid out and drawn again whenever the tree changes.
+
+```no_run
+use async_ui_terminal::{
+    combinators::race,
+    components::{Block, Text},
+    events::{until_key, KeyCode},
+};
+
+async fn app() {
+    let block = Block::new();
+    block.update_style(|style| {
+        style.border = true;
+        style.title = Some("Hello".into());
+    });
+    let count = Text::new("Pressed 0 keys. Press Esc to quit.");
+    race((block.render(count.render()), async {
+        let mut pressed = 0;
+        while until_key().await.code != KeyCode::Esc {
+            pressed += 1;
+            count.set_text(format!("Pressed {pressed} keys. Press Esc to quit."));
+        }
+    }))
+    .await;
+}
+
+async_ui_terminal::run(app()).unwrap();
+```
+
+# Testing
+
+[TerminalRoot] renders into an in-memory [Buffer] instead of the terminal.
+Send key presses with [send_key][TerminalRoot::send_key]
+and check the result with [buffer][TerminalRoot::buffer].
+*/
+
+mod backend;
+pub mod buffer;
+pub mod components;
+pub mod events;
+mod layout;
+mod node;
+mod root;
+mod run;
+
+pub use async_ui::{combinators, provide, use_context, with_context, TaskScope};
+pub use backend::Terminal;
+#[doc(inline)]
+pub use buffer::Buffer;
+pub use components::{Block, Text};
+pub use crossterm::style::Color;
+pub use node::{BlockStyle, Direction, Node};
+pub use root::TerminalRoot;
+pub use run::run;
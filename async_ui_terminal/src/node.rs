@@ -0,0 +1,165 @@
+//! The tree that terminal components render into.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+
+use crossterm::style::Color;
+
+thread_local! {
+    /// Set whenever the tree changes, so that we know to lay it out and draw it again.
+    static TREE_CHANGED: Cell<bool> = const { Cell::new(true) };
+}
+
+pub(crate) fn mark_changed() {
+    TREE_CHANGED.with(|c| c.set(true));
+}
+pub(crate) fn take_changed() -> bool {
+    TREE_CHANGED.with(|c| c.replace(false))
+}
+
+/// A handle to a node in the terminal UI tree.
+/// Cloning the handle does not clone the node.
+#[derive(Clone)]
+pub struct Node {
+    data: Rc<NodeData>,
+}
+
+struct NodeData {
+    kind: NodeKind,
+    parent: RefCell<Weak<NodeData>>,
+    children: RefCell<Vec<Node>>,
+}
+
+pub(crate) enum NodeKind {
+    Block(RefCell<BlockStyle>),
+    Text(RefCell<TextContent>),
+    Marker,
+}
+
+pub(crate) struct TextContent {
+    pub text: String,
+    pub color: Option<Color>,
+}
+
+/// How a [Block][crate::components::Block] lays out and decorates its children.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockStyle {
+    /// Whether children are placed top to bottom or left to right.
+    pub direction: Direction,
+    /// Draw a border around the block.
+    pub border: bool,
+    /// Text shown on the top border. Only shown if `border` is set.
+    pub title: Option<String>,
+    /// Empty cells between the border (or edge) and the children.
+    pub padding: u16,
+    /// Empty cells between consecutive children.
+    pub gap: u16,
+}
+
+/// See [BlockStyle].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Top to bottom. Each child gets the full width of the block.
+    #[default]
+    Column,
+    /// Left to right. Each child gets the width of its content.
+    Row,
+}
+
+impl Node {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            data: Rc::new(NodeData {
+                kind,
+                parent: RefCell::new(Weak::new()),
+                children: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+    pub(crate) fn new_block(style: BlockStyle) -> Self {
+        Self::new(NodeKind::Block(RefCell::new(style)))
+    }
+    pub(crate) fn new_text(text: String) -> Self {
+        Self::new(NodeKind::Text(RefCell::new(TextContent {
+            text,
+            color: None,
+        })))
+    }
+    pub(crate) fn new_marker() -> Self {
+        Self::new(NodeKind::Marker)
+    }
+    pub(crate) fn kind(&self) -> &NodeKind {
+        &self.data.kind
+    }
+    pub(crate) fn with_children<R>(&self, f: impl FnOnce(&[Node]) -> R) -> R {
+        f(&self.data.children.borrow())
+    }
+    pub fn is_same_node(&self, other: &Node) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+    pub fn parent(&self) -> Option<Node> {
+        self.data
+            .parent
+            .borrow()
+            .upgrade()
+            .map(|data| Node { data })
+    }
+    pub fn children(&self) -> Vec<Node> {
+        self.data.children.borrow().clone()
+    }
+
+    /// Insert `node` as a child of this node, before `reference`
+    /// (or at the end, if `reference` is `None`).
+    /// If `node` already has a parent, it is moved.
+    ///
+    /// Panics if `reference` is not a child of this node.
+    pub fn insert_before(&self, node: &Node, reference: Option<&Node>) {
+        node.detach();
+        let mut children = self.data.children.borrow_mut();
+        let index = match reference {
+            Some(reference) => children
+                .iter()
+                .position(|c| c.is_same_node(reference))
+                .expect("reference node is not a child of this node"),
+            None => children.len(),
+        };
+        children.insert(index, node.clone());
+        *node.data.parent.borrow_mut() = Rc::downgrade(&self.data);
+        mark_changed();
+    }
+    /// Remove `node` from the children of this node.
+    ///
+    /// Panics if `node` is not a child of this node.
+    pub fn remove_child(&self, node: &Node) {
+        let mut children = self.data.children.borrow_mut();
+        let index = children
+            .iter()
+            .position(|c| c.is_same_node(node))
+            .expect("node to remove is not a child of this node");
+        children.remove(index);
+        *node.data.parent.borrow_mut() = Weak::new();
+        mark_changed();
+    }
+    fn detach(&self) {
+        if let Some(parent) = self.parent() {
+            parent.remove_child(self);
+        }
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind() {
+            NodeKind::Block(style) => f
+                .debug_struct("Block")
+                .field("style", &*style.borrow())
+                .field("children", &*self.data.children.borrow())
+                .finish(),
+            NodeKind::Text(content) => f.debug_tuple("Text").field(&content.borrow().text).finish(),
+            NodeKind::Marker => f.write_str("Marker"),
+        }
+    }
+}
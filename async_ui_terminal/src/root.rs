@@ -0,0 +1,235 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use async_ui::ContainerNodeFuture;
+
+use crate::{
+    backend::Terminal,
+    buffer::Buffer,
+    events::{dispatch_key, KeyEvent},
+    layout::{draw_children, Rect},
+    node::{take_changed, BlockStyle, Direction, Node},
+};
+
+/// A future being rendered into an in-memory [Buffer].
+///
+/// [run][crate::run] uses this to drive a real terminal.
+/// Use it directly to test components without one.
+///
+/// ```
+/// # use async_ui_terminal::{components::Text, TerminalRoot};
+/// let mut root = std::pin::pin!(TerminalRoot::new(Text::new("Hi").render(), 10, 1));
+/// root.as_mut().run_until_stalled();
+/// assert_eq!(root.buffer().lines(), ["Hi"]);
+/// ```
+#[pin_project::pin_project]
+pub struct TerminalRoot<F: Future> {
+    #[pin]
+    future: ContainerNodeFuture<Terminal, F>,
+    node: Node,
+    woken: Arc<WakeFlag>,
+    buffer: Buffer,
+    resized: bool,
+    output: Option<F::Output>,
+}
+
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<F: Future> TerminalRoot<F> {
+    /// Create a root for rendering the given future into a screen of the given size.
+    /// The future is not polled until [run_until_stalled][Self::run_until_stalled].
+    pub fn new(future: F, width: u16, height: u16) -> Self {
+        let node = Node::new_block(BlockStyle::default());
+        Self {
+            future: ContainerNodeFuture::new_root(future, node.clone()),
+            node,
+            // The first poll needs to happen regardless of wakes.
+            woken: Arc::new(WakeFlag(AtomicBool::new(true))),
+            buffer: Buffer::new(width, height),
+            resized: true,
+            output: None,
+        }
+    }
+
+    /// Poll the future until it stops waking itself or completes,
+    /// then redraw the buffer if anything changed.
+    ///
+    /// Returns `true` if the future has completed.
+    pub fn run_until_stalled(self: Pin<&mut Self>) -> bool {
+        let mut this = self.project();
+        let waker = Waker::from(this.woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        while this.output.is_none() && this.woken.0.swap(false, Ordering::Relaxed) {
+            if let Poll::Ready(output) = this.future.as_mut().poll(&mut cx) {
+                *this.output = Some(output);
+            }
+        }
+        if take_changed() | std::mem::take(this.resized) {
+            let mut buffer = Buffer::new(this.buffer.width(), this.buffer.height());
+            let area = Rect {
+                x: 0,
+                y: 0,
+                width: buffer.width(),
+                height: buffer.height(),
+            };
+            draw_children(this.node, area, Direction::Column, 0, &mut buffer);
+            *this.buffer = buffer;
+        }
+        this.output.is_some()
+    }
+
+    /// Send a key press to the UI, then [run_until_stalled][Self::run_until_stalled].
+    pub fn send_key(self: Pin<&mut Self>, event: KeyEvent) -> bool {
+        dispatch_key(event);
+        self.run_until_stalled()
+    }
+
+    /// Change the size of the screen.
+    /// The buffer is redrawn on the next [run_until_stalled][Self::run_until_stalled].
+    pub fn resize(self: Pin<&mut Self>, width: u16, height: u16) {
+        let this = self.project();
+        *this.buffer = Buffer::new(width, height);
+        *this.resized = true;
+    }
+
+    /// What has been drawn so far.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The output of the future, if it has completed.
+    pub fn take_output(self: Pin<&mut Self>) -> Option<F::Output> {
+        self.project().output.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{pending, ready},
+        pin::pin,
+    };
+
+    use async_ui::combinators::{join, race};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::{
+        components::{Block, Text},
+        events::until_key,
+    };
+
+    fn lines<F: Future>(future: F, width: u16, height: u16) -> Vec<String> {
+        let mut root = pin!(TerminalRoot::new(future, width, height));
+        root.as_mut().run_until_stalled();
+        root.buffer().lines()
+    }
+    fn row<F: Future>(gap: u16, children: F) -> impl Future<Output = F::Output> {
+        let block = Block::new();
+        block.update_style(|style| {
+            style.direction = Direction::Row;
+            style.gap = gap;
+        });
+        async move { block.render(children).await }
+    }
+
+    #[test]
+    fn layout() {
+        let screen = lines(
+            join((
+                Text::new("top").render(),
+                row(
+                    1,
+                    join((Text::new("a\nb").render(), Text::new("c").render())),
+                ),
+                race((Text::new("1").render(), Text::new("2").render())),
+            )),
+            10,
+            5,
+        );
+        assert_eq!(screen, ["top", "a c", "b", "1", "2"]);
+    }
+
+    #[test]
+    fn border_and_clipping() {
+        let block = Block::new();
+        block.update_style(|style| {
+            style.border = true;
+            style.title = Some("Title".into());
+            style.padding = 1;
+        });
+        let screen = lines(block.render(Text::new("a very long line").render()), 8, 6);
+        assert_eq!(
+            screen,
+            [
+                "┌Title─┐",
+                "│      │",
+                "│ a ve │",
+                "│      │",
+                "└──────┘",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn key_events() {
+        let text = Text::new("waiting");
+        let mut root = pin!(TerminalRoot::new(
+            race((text.render(), async {
+                loop {
+                    let key = until_key().await;
+                    if key.code == KeyCode::Esc {
+                        break;
+                    }
+                    if let KeyCode::Char(c) = key.code {
+                        text.set_text(format!("pressed {c}"));
+                    }
+                }
+            })),
+            20,
+            1,
+        ));
+        assert!(!root.as_mut().run_until_stalled());
+        assert_eq!(root.buffer().lines(), ["waiting"]);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(!root.as_mut().send_key(key(KeyCode::Char('x'))));
+        assert_eq!(root.buffer().lines(), ["pressed x"]);
+        assert!(root.as_mut().send_key(key(KeyCode::Esc)));
+        assert_eq!(root.take_output(), Some(()));
+    }
+
+    #[test]
+    fn removal() {
+        let screen = lines(
+            join((
+                Text::new("first").render(),
+                async {
+                    race((Text::new("gone").render(), ready(()))).await;
+                    Text::new("replaced").render().await;
+                },
+                pending::<()>(),
+            )),
+            10,
+            3,
+        );
+        assert_eq!(screen, ["first", "replaced", ""]);
+    }
+}
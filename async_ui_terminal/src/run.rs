@@ -0,0 +1,89 @@
+use std::{
+    future::Future,
+    io::{self, Write},
+    pin::pin,
+    time::Duration,
+};
+
+use crossterm::{
+    cursor, event,
+    style::{Color, Print, SetForegroundColor},
+    terminal, QueueableCommand,
+};
+
+use crate::{buffer::Buffer, root::TerminalRoot};
+
+/// Run the given future in the terminal, until it completes.
+///
+/// The terminal is put in raw mode and switched to the alternate screen,
+/// and restored when this function returns (or panics).
+///
+/// Raw mode means Ctrl+C no longer stops the program.
+/// Listen for it with [until_key][crate::events::until_key] and finish the future instead.
+pub fn run<F: Future>(future: F) -> io::Result<F::Output> {
+    let (width, height) = terminal::size()?;
+    let _guard = TerminalGuard::enter()?;
+    let mut out = io::stdout();
+    let mut root = pin!(TerminalRoot::new(future, width, height));
+    let mut shown = Buffer::new(0, 0);
+    loop {
+        let done = root.as_mut().run_until_stalled();
+        write_changes(&mut out, &shown, root.buffer())?;
+        shown = root.buffer().clone();
+        if done {
+            return Ok(root.take_output().unwrap());
+        }
+        // Wake up now and then so that futures woken from elsewhere get polled.
+        if event::poll(Duration::from_millis(16))? {
+            match event::read()? {
+                event::Event::Key(key) => crate::events::dispatch_key(key),
+                event::Event::Resize(width, height) => root.as_mut().resize(width, height),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Write out the cells that differ between what's on the terminal and the new buffer.
+fn write_changes(out: &mut impl Write, shown: &Buffer, new: &Buffer) -> io::Result<()> {
+    let full = (shown.width(), shown.height()) != (new.width(), new.height());
+    if full {
+        out.queue(terminal::Clear(terminal::ClearType::All))?;
+    }
+    for y in 0..new.height() {
+        for x in 0..new.width() {
+            let cell = new.get(x, y);
+            if full || shown.get(x, y) != cell {
+                out.queue(cursor::MoveTo(x, y))?
+                    .queue(SetForegroundColor(cell.fg.unwrap_or(Color::Reset)))?
+                    .queue(Print(cell.symbol))?;
+            }
+        }
+    }
+    out.flush()
+}
+
+/// Puts the terminal back the way it was on drop.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        io::stdout()
+            .queue(terminal::EnterAlternateScreen)?
+            .queue(cursor::Hide)?
+            .flush()?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = io::stdout()
+            .queue(cursor::Show)
+            .and_then(|out| out.queue(terminal::LeaveAlternateScreen))
+            .and_then(|out| out.flush());
+        let _ = terminal::disable_raw_mode();
+    }
+}
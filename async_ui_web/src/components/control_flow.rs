@@ -0,0 +1,381 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::Poll,
+};
+
+use async_ui_internal_utils::reactive_cell::ReactiveCell;
+use futures_lite::Stream;
+
+/// A value that can be read and watched for changes.
+///
+/// Implemented for [ReactiveCell].
+/// With the `x-bow` feature, x-bow paths can be used through [PathSource].
+pub trait Reactive {
+    type Value: ?Sized;
+    /// Call `f` with a reference to the current value.
+    fn with_value<O>(&self, f: impl FnOnce(&Self::Value) -> O) -> O;
+    /// Get a [Stream] that fires every time the value changes.
+    ///
+    /// Like [ReactiveCell::until_change], the first poll doesn't fire.
+    fn changes(&self) -> impl Stream<Item = ()> + '_;
+}
+
+impl<T> Reactive for ReactiveCell<T> {
+    type Value = T;
+    fn with_value<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        f(&self.borrow())
+    }
+    fn changes(&self) -> impl Stream<Item = ()> + '_ {
+        self.until_change()
+    }
+}
+
+/// Use an [x-bow](x_bow) path as a [Reactive] source.
+///
+/// Takes a path builder, as returned by `store.build_path()` and its field methods.
+///
+/// ```
+/// # use async_ui_web::{components::{PathSource, Show}, prelude_traits::*};
+/// # use x_bow::{Store, Trackable};
+/// #[derive(Trackable)]
+/// struct State {
+///     logged_in: bool,
+/// }
+/// # let _ = async {
+/// let store = Store::new(State { logged_in: false });
+/// let source = PathSource(store.build_path().logged_in());
+/// Show::new(&source, |&logged_in| logged_in, || "Welcome back!".render())
+///     .render()
+///     .await;
+/// # };
+/// ```
+#[cfg(feature = "x-bow")]
+pub struct PathSource<P>(pub P);
+
+#[cfg(feature = "x-bow")]
+impl<P> Reactive for PathSource<P>
+where
+    P: std::ops::Deref,
+    P::Target: x_bow::PathExtGuaranteed,
+{
+    type Value = <P::Target as x_bow::Path>::Out;
+    fn with_value<O>(&self, f: impl FnOnce(&Self::Value) -> O) -> O {
+        f(&x_bow::PathExtGuaranteed::borrow(&*self.0))
+    }
+    fn changes(&self) -> impl Stream<Item = ()> + '_ {
+        x_bow::PathExt::until_change(&*self.0)
+    }
+}
+
+/**
+Render a future while a condition on a [Reactive] source holds.
+
+```
+# use async_ui_web::{components::Show, html::Button, join, prelude_traits::*, ReactiveCell};
+# let _ = async {
+let open = ReactiveCell::new(false);
+let toggle = Button::new();
+join((
+    toggle.render("Details".render()),
+    Show::new(&open, |&open| open, || "Some details...".render()).render(),
+    async {
+        loop {
+            toggle.until_click().await;
+            let mut open = open.borrow_mut();
+            *open = !*open;
+        }
+    },
+))
+.await;
+# };
+```
+
+The child future is created (by calling `child`) when the condition becomes true,
+and dropped when it becomes false.
+Changes to the source that don't flip the condition don't touch the child.
+*/
+pub struct Show<'s, R: ?Sized, P, C> {
+    source: &'s R,
+    condition: P,
+    child: C,
+}
+
+impl<'s, R, P, C, F> Show<'s, R, P, C>
+where
+    R: Reactive + ?Sized,
+    P: FnMut(&R::Value) -> bool,
+    C: FnMut() -> F,
+    F: Future,
+{
+    pub fn new(source: &'s R, condition: P, child: C) -> Self {
+        Self {
+            source,
+            condition,
+            child,
+        }
+    }
+    /// Render the child here while the condition holds.
+    ///
+    /// This async method never completes.
+    pub async fn render(self) {
+        let Self {
+            source,
+            condition,
+            mut child,
+        } = self;
+        switch(source, condition, |&shown, _| shown.then(&mut child)).await
+    }
+}
+
+/**
+Render a different future depending on a value computed from a [Reactive] source.
+
+```
+# use async_ui_web::{components::Match, prelude_traits::*, ReactiveCell};
+#[derive(Clone, Copy, PartialEq)]
+enum Tab {
+    Home,
+    About,
+}
+# let _ = async {
+let tab = ReactiveCell::new(Tab::Home);
+Match::new(&tab, |tab| *tab, |tab| {
+    match tab {
+        Tab::Home => "Home page",
+        Tab::About => "About us",
+    }
+    .render()
+})
+.render()
+.await;
+# };
+```
+
+`render` is called whenever the selected value changes,
+and the future it returns replaces the previous one.
+Changes to the source that select the same value don't touch the rendered future.
+
+All branches must return the same type of future. If they don't, box them
+(into `Pin<Box<dyn Future<Output = ()>>>`).
+*/
+pub struct Match<'s, R: ?Sized, S, C> {
+    source: &'s R,
+    select: S,
+    render: C,
+}
+
+impl<'s, R, S, C, K, F> Match<'s, R, S, C>
+where
+    R: Reactive + ?Sized,
+    S: FnMut(&R::Value) -> K,
+    C: FnMut(&K) -> F,
+    K: PartialEq,
+    F: Future,
+{
+    pub fn new(source: &'s R, select: S, render: C) -> Self {
+        Self {
+            source,
+            select,
+            render,
+        }
+    }
+    /// Render the future for the selected value here.
+    ///
+    /// This async method never completes.
+    pub async fn render(self) {
+        let Self {
+            source,
+            select,
+            mut render,
+        } = self;
+        switch(source, select, |key, _| Some(render(key))).await
+    }
+}
+
+/**
+Render a future made from a [Reactive] source, remounting it whenever a key changes.
+
+```
+# use async_ui_web::{components::Keyed, prelude_traits::*, ReactiveCell};
+# struct User { id: u64, name: String }
+# async fn user_profile(_id: u64, _name: String) {}
+# let _ = async {
+let user = ReactiveCell::new(User { id: 1, name: "Alice".into() });
+Keyed::new(&user, |user| user.id, |user| user_profile(user.id, user.name.clone()))
+    .render()
+    .await;
+# };
+```
+
+When the key changes, the old child future is dropped (along with all its state)
+and a new one is made by calling `child` with the current value.
+Changes to the source that keep the same key don't touch the child.
+*/
+pub struct Keyed<'s, R: ?Sized, K, C> {
+    source: &'s R,
+    key: K,
+    child: C,
+}
+
+impl<'s, R, KF, C, K, F> Keyed<'s, R, KF, C>
+where
+    R: Reactive + ?Sized,
+    KF: FnMut(&R::Value) -> K,
+    C: FnMut(&R::Value) -> F,
+    K: PartialEq,
+    F: Future,
+{
+    pub fn new(source: &'s R, key: KF, child: C) -> Self {
+        Self { source, key, child }
+    }
+    /// Render the child here.
+    ///
+    /// This async method never completes.
+    pub async fn render(self) {
+        let Self {
+            source,
+            key,
+            mut child,
+        } = self;
+        switch(source, key, |_, value| Some(child(value))).await
+    }
+}
+
+/// Render the future made by `make` for the current key,
+/// replacing it only when the key changes.
+async fn switch<R, K, F>(
+    source: &R,
+    mut key: impl FnMut(&R::Value) -> K,
+    mut make: impl FnMut(&K, &R::Value) -> Option<F>,
+) where
+    R: Reactive + ?Sized,
+    K: PartialEq,
+    F: Future,
+{
+    let mut changes = pin!(source.changes());
+    let mut current: Option<K> = None;
+    // this is where the child future lives
+    let mut slot: Pin<&mut Option<F>> = pin!(None);
+    let mut done = false;
+    poll_fn(|cx| {
+        // The first poll only subscribes, so compute the key ourselves then.
+        let mut changed = current.is_none();
+        while let Poll::Ready(Some(())) = changes.as_mut().poll_next(cx) {
+            changed = true;
+        }
+        if changed {
+            let new_key = source.with_value(&mut key);
+            if current.as_ref() != Some(&new_key) {
+                // Drop the old child (removing its nodes) before making the new one.
+                slot.set(None);
+                slot.set(source.with_value(|value| make(&new_key, value)));
+                current = Some(new_key);
+                done = false;
+            }
+        }
+        if !done {
+            if let Some(fut) = slot.as_mut().as_pin_mut() {
+                done = fut.poll(cx).is_ready();
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::ssr::{html::Span, prelude_traits::*, SsrRoot};
+
+    #[test]
+    fn show() {
+        let source = ReactiveCell::new(0);
+        let made = Cell::new(0);
+        let mut root = pin!(SsrRoot::new(
+            Show::new(
+                &source,
+                |&value| value > 0,
+                || {
+                    made.set(made.get() + 1);
+                    Span::new().render("shown".render())
+                },
+            )
+            .render()
+        ));
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "");
+        *source.borrow_mut() = 1;
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "<span>shown</span>");
+        // the condition still holds, so the child stays
+        *source.borrow_mut() = 2;
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "<span>shown</span>");
+        assert_eq!(made.get(), 1);
+        *source.borrow_mut() = 0;
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "");
+        *source.borrow_mut() = 3;
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "<span>shown</span>");
+        assert_eq!(made.get(), 2);
+    }
+
+    #[test]
+    fn match_() {
+        let source = ReactiveCell::new(1);
+        let made = Cell::new(0);
+        let mut root = pin!(SsrRoot::new(
+            Match::new(
+                &source,
+                |&value| value % 2 == 0,
+                |&even| {
+                    made.set(made.get() + 1);
+                    if even { "even" } else { "odd" }.render()
+                },
+            )
+            .render()
+        ));
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "odd");
+        *source.borrow_mut() = 3;
+        root.as_mut().run_until_stalled();
+        assert_eq!(made.get(), 1);
+        *source.borrow_mut() = 4;
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "even");
+        assert_eq!(made.get(), 2);
+    }
+
+    #[test]
+    fn keyed() {
+        let source = ReactiveCell::new((1, "a"));
+        let made = Cell::new(0);
+        let mut root = pin!(SsrRoot::new(
+            Keyed::new(
+                &source,
+                |&(id, _)| id,
+                |&(id, name)| {
+                    made.set(made.get() + 1);
+                    format!("{id}:{name}").render()
+                },
+            )
+            .render()
+        ));
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "1:a");
+        // same key: the child is kept as it was made
+        *source.borrow_mut() = (1, "b");
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "1:a");
+        assert_eq!(made.get(), 1);
+        *source.borrow_mut() = (2, "c");
+        root.as_mut().run_until_stalled();
+        assert_eq!(root.to_html(), "2:c");
+        assert_eq!(made.get(), 2);
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::{pending, poll_fn, Future},
+    hash::Hash,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use async_ui_internal_utils::dummy_waker::dummy_waker;
+use async_ui_web_core::{combinators::race, DetachmentBlocker};
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::{
+    super::platform::{
+        dom::{Comment, Node},
+        ContainerNodeFuture,
+    },
+    offscreen::Offscreen,
+};
+
+/**
+Switch between futures without dropping the ones that aren't shown.
+
+Switching with [race][crate::race] or [DynamicSlot][super::DynamicSlot] drops
+the branch that is switched away from, losing its state
+(form input, scroll position, in-flight requests, ...).
+`KeepAlive` keeps every inserted branch alive, and only moves the nodes of
+hidden branches out of the page.
+
+```
+# use async_ui_web::{components::KeepAlive, html::{Button, Input}, join, prelude_traits::*};
+# let _ = async {
+let tabs = KeepAlive::new();
+let (tab_a, tab_b) = (Button::new(), Button::new());
+tabs.insert("a", Input::new().render());
+tabs.insert("b", Input::new().render());
+tabs.show("a");
+join((
+    tab_a.render("A".render()),
+    tab_b.render("B".render()),
+    tabs.render(),
+    async {
+        loop {
+            tab_a.until_click().await;
+            tabs.show("a");
+        }
+    },
+    async {
+        loop {
+            tab_b.until_click().await;
+            tabs.show("b");
+        }
+    },
+))
+.await;
+# };
+```
+
+By default, hidden branches keep being polled, so they keep running
+(and keep rendering, out of sight).
+Call [set_pause_hidden][Self::set_pause_hidden] to stop polling them
+until they are shown again.
+
+All branches are polled whenever any of them is woken,
+so this is meant for a handful of branches, like the pages of a tab view.
+*/
+pub struct KeepAlive<K: Eq + Hash, F: Future> {
+    commands: RefCell<Vec<Command<K, F>>>,
+    branches: RefCell<HashMap<K, Branch<F>>>,
+    shown: RefCell<Option<K>>,
+    pause_hidden: Cell<bool>,
+    waker: RefCell<Waker>,
+    detachment_blocker: DetachmentBlocker,
+}
+
+/// Changes are queued and applied in `render`, so that they can be made from
+/// inside the branches themselves.
+enum Command<K, F> {
+    Insert(K, F),
+    Remove(K),
+    Show(Option<K>),
+}
+
+struct Branch<F: Future> {
+    future: Pin<Box<Offscreen<F>>>,
+    done: bool,
+}
+
+impl<K: Eq + Hash, F: Future> Default for KeepAlive<K, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, F: Future> KeepAlive<K, F> {
+    /// Create a new `KeepAlive` without any branch.
+    pub fn new() -> Self {
+        Self {
+            commands: RefCell::new(Vec::new()),
+            branches: RefCell::new(HashMap::new()),
+            shown: RefCell::new(None),
+            pause_hidden: Cell::new(false),
+            waker: RefCell::new(dummy_waker()),
+            detachment_blocker: DetachmentBlocker,
+        }
+    }
+    /// Add a branch with the given key.
+    /// If a branch with that key already exists, it is dropped and replaced.
+    ///
+    /// The branch is hidden unless its key is the one [shown][Self::show].
+    pub fn insert(&self, key: K, future: F) {
+        self.send(Command::Insert(key, future));
+    }
+    /// Drop the branch with the given key.
+    pub fn remove(&self, key: K) {
+        self.send(Command::Remove(key));
+    }
+    /// Show the branch with the given key, hiding the one currently shown.
+    ///
+    /// If there is no such branch yet, nothing is shown until one is inserted.
+    pub fn show(&self, key: K) {
+        self.send(Command::Show(Some(key)));
+    }
+    /// Hide the branch currently shown.
+    pub fn hide(&self) {
+        self.send(Command::Show(None));
+    }
+    /// Whether hidden branches should stop being polled.
+    ///
+    /// Default is `false`.
+    pub fn set_pause_hidden(&self, pause: bool) {
+        self.pause_hidden.set(pause);
+        self.waker.borrow().wake_by_ref();
+    }
+    fn send(&self, command: Command<K, F>) {
+        self.commands.borrow_mut().push(command);
+        self.waker.borrow().wake_by_ref();
+    }
+
+    /// Render the shown branch here.
+    ///
+    /// This async method never completes.
+    /// Only render each `KeepAlive` in one place at a time.
+    pub async fn render(&self) {
+        let marker: Node = Comment::new().unwrap_throw().into();
+        let mut attached = false;
+        let _guard = scopeguard::guard((), |_| {
+            // Take the shown branch out of the page with us.
+            if let Some(key) = &*self.shown.borrow() {
+                if let Some(branch) = self.branches.borrow().get(key) {
+                    branch.future.hide();
+                }
+            }
+        });
+        // The marker is polled (and so added) first; the shown branch goes right after it.
+        race((
+            ContainerNodeFuture::new(pending::<()>(), marker.clone()),
+            poll_fn(|cx| self.poll_branches(&marker, &mut attached, cx)),
+        ))
+        .await
+    }
+
+    fn poll_branches(&self, marker: &Node, attached: &mut bool, cx: &mut Context<'_>) -> Poll<()> {
+        {
+            let mut waker = self.waker.borrow_mut();
+            if !waker.will_wake(cx.waker()) {
+                *waker = cx.waker().clone();
+            }
+        }
+        let mut branches = self.branches.borrow_mut();
+        let mut shown = self.shown.borrow_mut();
+        if !std::mem::replace(attached, true) {
+            // We might be rendered again after being dropped.
+            if let Some(branch) = shown.as_ref().and_then(|k| branches.get(k)) {
+                branch.future.show_after(marker);
+            }
+        }
+        let commands = std::mem::take(&mut *self.commands.borrow_mut());
+        for command in commands {
+            match command {
+                Command::Insert(key, future) => {
+                    // dropping the old branch removes its nodes
+                    branches.remove(&key);
+                    let branch = Branch {
+                        future: Box::pin(Offscreen::new(future)),
+                        done: false,
+                    };
+                    if shown.as_ref() == Some(&key) {
+                        branch.future.show_after(marker);
+                    }
+                    branches.insert(key, branch);
+                }
+                Command::Remove(key) => {
+                    branches.remove(&key);
+                }
+                Command::Show(key) => {
+                    if *shown == key {
+                        continue;
+                    }
+                    if let Some(old) = shown.as_ref().and_then(|k| branches.get(k)) {
+                        old.future.hide();
+                    }
+                    if let Some(new) = key.as_ref().and_then(|k| branches.get(k)) {
+                        new.future.show_after(marker);
+                    }
+                    *shown = key;
+                }
+            }
+        }
+        let pause_hidden = self.pause_hidden.get();
+        for branch in branches.values_mut() {
+            if branch.done || (pause_hidden && !branch.future.is_shown()) {
+                continue;
+            }
+            // hidden branches render out of sight
+            branch.done = branch.future.as_mut().poll(cx).is_ready();
+        }
+        Poll::Pending
+    }
+}
+
+impl<K: Eq + Hash, F: Future> Drop for KeepAlive<K, F> {
+    fn drop(&mut self) {
+        self.detachment_blocker.block_until_drop();
+    }
+}
@@ -2,8 +2,24 @@
 //!
 //! Components in this module are provided for convenience.
 
+mod control_flow;
 mod dropdown;
 mod dynamic_slot;
+mod keep_alive;
+mod offscreen;
+mod portal;
+mod suspense;
+mod transition;
 
+// Only with `panic = "unwind"`: in the browser, panics abort and can't be caught.
+#[cfg(panic = "unwind")]
+pub use async_ui_web_core::{ErrorBoundary, PanicPayload};
+#[cfg(feature = "x-bow")]
+pub use control_flow::PathSource;
+pub use control_flow::{Keyed, Match, Reactive, Show};
 pub use dropdown::Dropdown;
 pub use dynamic_slot::DynamicSlot;
+pub use keep_alive::KeepAlive;
+pub use portal::Portal;
+pub use suspense::{loading, Loading, Suspense};
+pub use transition::{Transition, TransitionFuture};
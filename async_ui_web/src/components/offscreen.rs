@@ -0,0 +1,120 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_ui_web_core::DetachmentBlocker;
+use pin_project::{pin_project, pinned_drop};
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::super::platform::{
+    dom::{Comment, DocumentFragment, Node},
+    OffscreenRoot, SiblingNodeFuture,
+};
+
+/// A future whose nodes can be moved in and out of the page without dropping it.
+///
+/// Everything it renders is kept between two markers.
+/// While hidden, the markers and everything between them are in a fragment,
+/// and the future is polled in an [OffscreenRoot] over that fragment.
+/// While shown, they are in the page, and the future is polled in place.
+///
+/// Used by [Suspense][super::Suspense] and [KeepAlive][super::KeepAlive].
+#[pin_project(PinnedDrop)]
+pub(crate) struct Offscreen<F> {
+    #[pin]
+    future: SiblingNodeFuture<F>,
+    root: OffscreenRoot,
+    start_marker: Node,
+    end_marker: Node,
+    shown: Cell<bool>,
+    drop: DetachmentBlocker,
+}
+
+impl<F: Future> Offscreen<F> {
+    /// Start out hidden.
+    pub fn new(future: F) -> Self {
+        let fragment = DocumentFragment::new().unwrap_throw();
+        let start_marker: Node = Comment::new().unwrap_throw().into();
+        let end_marker: Node = Comment::new().unwrap_throw().into();
+        fragment.append_child(&start_marker).unwrap_throw();
+        fragment.append_child(&end_marker).unwrap_throw();
+        Self {
+            future: SiblingNodeFuture::new(future, end_marker.clone()),
+            root: OffscreenRoot::new(fragment.into()),
+            start_marker,
+            end_marker,
+            shown: Cell::new(false),
+            drop: DetachmentBlocker,
+        }
+    }
+    pub fn is_shown(&self) -> bool {
+        self.shown.get()
+    }
+    /// Move everything into the page, right after `marker`.
+    pub fn show_after(&self, marker: &Node) {
+        if self.shown.replace(true) {
+            return;
+        }
+        let parent = marker.parent_node().unwrap_throw();
+        parent
+            .insert_before(self.root.container(), marker.next_sibling().as_ref())
+            .unwrap_throw();
+    }
+    /// Move the markers and everything between them back out of the page.
+    pub fn hide(&self) {
+        if !self.shown.replace(false) {
+            return;
+        }
+        let fragment = self.root.container();
+        let mut node = self.start_marker.clone();
+        loop {
+            let next = node.next_sibling();
+            fragment.append_child(&node).unwrap_throw();
+            if node.is_same_node(Some(&self.end_marker)) {
+                break;
+            }
+            node = next.unwrap_throw();
+        }
+    }
+}
+
+impl<F: Future> Future for Offscreen<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if this.shown.get() {
+            this.future.poll(cx)
+        } else {
+            this.root.poll(this.future, cx)
+        }
+    }
+}
+
+#[pinned_drop]
+impl<F> PinnedDrop for Offscreen<F> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        // Our nodes are all between the markers, so remove them in one go
+        // and let the future go without detaching anything.
+        // The markers aren't in any group, so even if an ancestor has removed
+        // its nodes, ours might still be in the page.
+        this.drop.block_until_drop();
+        if this.shown.get() {
+            if let Some(parent) = this.start_marker.parent_node() {
+                let mut node = this.start_marker.clone();
+                loop {
+                    let next = node.next_sibling();
+                    parent.remove_child(&node).unwrap_throw();
+                    if node.is_same_node(Some(this.end_marker)) {
+                        break;
+                    }
+                    node = next.unwrap_throw();
+                }
+            }
+        }
+    }
+}
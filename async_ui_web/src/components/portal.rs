@@ -0,0 +1,40 @@
+use std::future::Future;
+
+use super::super::platform::{dom, PortalFuture};
+
+/// Render things somewhere else in the document.
+///
+/// Use this for modals, tooltips, and other overlays that need to escape
+/// their ancestors' `overflow: hidden` or stacking context.
+///
+/// ```
+/// # use async_ui_web::{components::Portal, html::Div, prelude_traits::*};
+/// # let _ = async {
+/// let body = web_sys::window().unwrap().document().unwrap().body().unwrap();
+/// let portal = Portal::new(body.into());
+/// let modal = Div::new();
+/// portal.render(modal.render("I'm at the end of the body!".render())).await;
+/// # };
+/// ```
+///
+/// What the portal renders is still owned by the future that renders the portal.
+/// It is removed when that future is dropped, just like everything else.
+///
+/// Multiple portals into the same node keep the order in which they were
+/// first rendered, after whatever was already in the node.
+pub struct Portal {
+    target: dom::Node,
+}
+
+impl Portal {
+    /// Create a portal into the given node.
+    pub fn new(target: dom::Node) -> Self {
+        Self { target }
+    }
+    /// Render the given future into the target node.
+    ///
+    /// The returned Future completes when the given argument Future finishes.
+    pub fn render<F: Future>(&self, c: F) -> PortalFuture<F> {
+        PortalFuture::new(c, self.target.clone())
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use async_ui_web_core::{combinators::race, provide, use_context, Provide};
+use pin_project::{pin_project, pinned_drop};
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::{
+    super::platform::{
+        dom::{Comment, Node},
+        sleep, ContainerNodeFuture,
+    },
+    offscreen::Offscreen,
+};
+
+/**
+Show a fallback until everything inside has loaded, then show everything at once.
+
+The child future is rendered into a detached container, where it can build up
+its content out of sight.
+Futures inside the child that load data should be wrapped with [loading].
+Once the child has been polled and none of those futures are still pending,
+the content is moved into the page in one step, replacing the fallback.
+
+```
+# use async_ui_web::{components::{loading, Suspense}, join, prelude_traits::*};
+# async fn fetch_name() -> String { String::new() }
+# async fn fetch_bio() -> String { String::new() }
+# let _ = async {
+let suspense = Suspense::new();
+suspense
+    .render(
+        join((
+            async { loading(fetch_name()).await.render().await },
+            async { loading(fetch_bio()).await.render().await },
+        )),
+        "Loading profile...".render(),
+    )
+    .await;
+# };
+```
+
+Use [with_timeout][Self::with_timeout] to show whatever has loaded so far
+if loading takes too long.
+
+After the content is shown, the boundary does nothing more:
+things that start loading later are shown as they load.
+*/
+pub struct Suspense {
+    timeout: Option<Duration>,
+}
+
+impl Default for Suspense {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Suspense {
+    /// Create a boundary without a timeout.
+    pub fn new() -> Self {
+        Self { timeout: None }
+    }
+    /// Show the content after the given time even if it hasn't finished loading.
+    ///
+    /// In server-side rendering, there are no timers: the time is only checked
+    /// when the boundary is polled, and nothing wakes it when the time is up.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Render the child future here, showing `fallback` until it has loaded.
+    ///
+    /// The returned future completes when `child_future` completes.
+    pub async fn render<C: Future, F: Future>(&self, child_future: C, fallback: F) -> C::Output {
+        let state = Rc::new(State {
+            pending: Cell::new(0),
+            waker: RefCell::new(None),
+        });
+        let marker: Node = Comment::new().unwrap_throw().into();
+        let driver = Driver {
+            child: Offscreen::new(provide(state.clone(), child_future)),
+            fallback: Some(fallback),
+            fallback_done: false,
+            timeout: self.timeout.map(sleep),
+            state,
+            marker: marker.clone(),
+        };
+        // The marker is polled (and so added) first; the content goes right after it.
+        race((
+            ContainerNodeFuture::new(std::future::pending(), marker),
+            driver,
+        ))
+        .await
+    }
+}
+
+/// Mark a future as loading something for the nearest [Suspense].
+///
+/// The `Suspense` keeps showing its fallback until this future completes
+/// (or is dropped). Outside of a `Suspense`, this does nothing.
+pub fn loading<F: Future>(future: F) -> Loading<F> {
+    Loading {
+        future,
+        state: None,
+        started: false,
+    }
+}
+
+/// Future returned by [loading].
+#[pin_project(PinnedDrop)]
+pub struct Loading<F> {
+    #[pin]
+    future: F,
+    state: Option<Rc<State>>,
+    started: bool,
+}
+
+impl<F: Future> Future for Loading<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if !std::mem::replace(this.started, true) {
+            *this.state = use_context::<Rc<State>>();
+            if let Some(state) = this.state {
+                state.pending.set(state.pending.get() + 1);
+            }
+        }
+        let res = this.future.poll(cx);
+        if res.is_ready() {
+            State::release(this.state);
+        }
+        res
+    }
+}
+
+#[pinned_drop]
+impl<F> PinnedDrop for Loading<F> {
+    fn drop(self: Pin<&mut Self>) {
+        State::release(self.project().state);
+    }
+}
+
+/// Shared between a [Suspense] and the [Loading] futures inside it.
+struct State {
+    pending: Cell<usize>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl State {
+    fn release(state: &mut Option<Rc<State>>) {
+        if let Some(state) = state.take() {
+            state.pending.set(state.pending.get() - 1);
+            if state.pending.get() == 0 {
+                if let Some(waker) = state.waker.borrow_mut().take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+#[pin_project]
+struct Driver<C: Future, F> {
+    #[pin]
+    child: Offscreen<Provide<Rc<State>, C>>,
+    #[pin]
+    fallback: Option<F>,
+    fallback_done: bool,
+    timeout: Option<Pin<Box<dyn Future<Output = ()>>>>,
+    state: Rc<State>,
+    /// The content is shown right after this.
+    marker: Node,
+}
+
+impl<C: Future, F: Future> Future for Driver<C, F> {
+    type Output = C::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if !this.child.is_shown() {
+            *this.state.waker.borrow_mut() = Some(cx.waker().clone());
+            if let Some(fallback) = this.fallback.as_mut().as_pin_mut() {
+                if !*this.fallback_done && fallback.poll(cx).is_ready() {
+                    *this.fallback_done = true;
+                }
+            }
+            // The child renders out of sight for now.
+            let res = this.child.as_mut().poll(cx);
+            let timed_out = this
+                .timeout
+                .as_mut()
+                .is_some_and(|timeout| timeout.as_mut().poll(cx).is_ready());
+            if res.is_pending() && this.state.pending.get() > 0 && !timed_out {
+                return res;
+            }
+            // Swap the fallback for the content.
+            this.fallback.set(None);
+            this.child.show_after(this.marker);
+            *this.timeout = None;
+            if res.is_ready() {
+                return res;
+            }
+        }
+        this.child.poll(cx)
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+use wasm_bindgen::JsValue;
+
+use super::super::platform::{
+    add_classes, animate, del_classes,
+    dom::Element,
+    leaving::{remove_leave_hook, set_leave_hook},
+    running_animations,
+};
+use crate::executor::{get_executor, WeakExecutor};
+
+/// Animate an element as it appears and disappears.
+///
+/// ```
+/// # use async_ui_web::{components::Transition, html::Div, prelude_traits::*};
+/// # let _ = async {
+/// let toast = Div::new();
+/// Transition::new(&toast)
+///     .with_enter_class("fade-in")
+///     .with_leave_class("fade-out")
+///     .render(toast.render("Saved!".render()))
+///     .await;
+/// # };
+/// ```
+///
+/// When the element is first rendered, the enter class is added, and removed
+/// again once the CSS animations or transitions it starts have finished.
+///
+/// When the future rendering the element is dropped, the element is not removed
+/// right away. Instead, the leave class is added, and the element is removed
+/// once the animations or transitions it starts have finished.
+/// In the meantime, the element stays where it was, but is no longer part of
+/// the UI: things rendered later don't take its position into account.
+///
+/// Instead of classes, you can use the
+/// [Web Animations API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Animations_API)
+/// with [with_enter_animation][Self::with_enter_animation] and
+/// [with_leave_animation][Self::with_leave_animation].
+///
+/// The element must be rendered directly by the future given to
+/// [render][Self::render] (not inside some other element), and on its first poll.
+/// Leave animations don't play if an ancestor element is removed: the element goes
+/// with its ancestor.
+///
+/// In server-side rendering, nothing is animated, so the element is removed
+/// right away (still with the leave class added).
+pub struct Transition {
+    element: Element,
+    enter: Option<Effect>,
+    leave: Option<Effect>,
+}
+
+#[derive(Clone)]
+enum Effect {
+    Class(String),
+    Animation {
+        keyframes: JsValue,
+        options: JsValue,
+    },
+}
+
+impl Transition {
+    pub fn new(element: &Element) -> Self {
+        Self {
+            element: element.clone(),
+            enter: None,
+            leave: None,
+        }
+    }
+    /// Add this class when the element appears.
+    pub fn with_enter_class(mut self, class: &str) -> Self {
+        self.enter = Some(Effect::Class(class.to_owned()));
+        self
+    }
+    /// Add this class when the element is about to be removed.
+    pub fn with_leave_class(mut self, class: &str) -> Self {
+        self.leave = Some(Effect::Class(class.to_owned()));
+        self
+    }
+    /// Run this animation when the element appears.
+    ///
+    /// The arguments are those of
+    /// [`Element.animate()`](https://developer.mozilla.org/en-US/docs/Web/API/Element/animate):
+    /// an array or object of keyframes, and a duration in milliseconds or an options object.
+    pub fn with_enter_animation(mut self, keyframes: JsValue, options: JsValue) -> Self {
+        self.enter = Some(Effect::Animation { keyframes, options });
+        self
+    }
+    /// Run this animation when the element is about to be removed.
+    /// See [with_enter_animation][Self::with_enter_animation] for the arguments.
+    pub fn with_leave_animation(mut self, keyframes: JsValue, options: JsValue) -> Self {
+        self.leave = Some(Effect::Animation { keyframes, options });
+        self
+    }
+    /// Render the given future, which should render the element.
+    ///
+    /// The returned Future completes when the given argument Future finishes.
+    pub fn render<F: Future>(&self, c: F) -> TransitionFuture<F> {
+        TransitionFuture {
+            child_future: c,
+            element: self.element.clone(),
+            enter: self.enter.clone(),
+            leave: self.leave.clone(),
+            hook: None,
+        }
+    }
+}
+
+/// Get this by calling [Transition::render].
+#[pin_project]
+pub struct TransitionFuture<F> {
+    // dropped first, so the element's removal goes through our leave hook
+    #[pin]
+    child_future: F,
+    element: Element,
+    enter: Option<Effect>,
+    leave: Option<Effect>,
+    hook: Option<LeaveHookGuard>,
+}
+
+impl<F: Future> Future for TransitionFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = this.child_future.poll(cx);
+        if this.hook.is_none() {
+            // The hooks may run outside of this app (the leave hook runs wherever
+            // the element is dropped), so they spawn on this app's executor.
+            let executor = get_executor().downgrade();
+            if let Some(enter) = this.enter.take() {
+                apply(this.element, &enter);
+                if let Effect::Class(class) = enter {
+                    // take the class off so it doesn't get in the way of the leave class
+                    let element = this.element.clone();
+                    after_animations(this.element, &executor, move || {
+                        del_classes(&element.class_list(), std::iter::once(&*class));
+                    });
+                }
+            }
+            let element = this.element.clone();
+            let leave = this.leave.take();
+            set_leave_hook(&element.clone().into(), move || {
+                if let Some(leave) = leave {
+                    apply(&element, &leave);
+                }
+                let removed = element.clone();
+                after_animations(&element, &executor, move || {
+                    if let Some(parent) = removed.parent_node() {
+                        let _ = parent.remove_child(&removed);
+                    }
+                });
+            });
+            *this.hook = Some(LeaveHookGuard(this.element.clone()));
+        }
+        res
+    }
+}
+
+/// Removes the leave hook if it didn't get used.
+struct LeaveHookGuard(Element);
+
+impl Drop for LeaveHookGuard {
+    fn drop(&mut self) {
+        remove_leave_hook(&self.0.clone().into());
+    }
+}
+
+fn apply(element: &Element, effect: &Effect) {
+    match effect {
+        Effect::Class(class) => add_classes(&element.class_list(), std::iter::once(&**class)),
+        Effect::Animation { keyframes, options } => animate(element, keyframes, options),
+    }
+}
+
+/// Call `f` once all animations currently running on `element` have finished
+/// (or been cancelled). Waiting happens in a task on `executor`; if its app is
+/// gone, `f` is called right away.
+fn after_animations(element: &Element, executor: &WeakExecutor, f: impl FnOnce() + 'static) {
+    let animations = running_animations(element);
+    let executor = match executor.upgrade() {
+        Some(executor) if !animations.is_empty() => executor,
+        _ => return f(),
+    };
+    // Spawning wakes the app's root, so the task runs even if we're called
+    // from outside of the app.
+    executor
+        .spawn(async move {
+            for animation in animations {
+                animation.await;
+            }
+            f();
+        })
+        .detach();
+}
@@ -1,18 +1,156 @@
 //! Exposes a [LocalExecutor] for general use.
 //!
-//! Async UI Web creates an app-wide executor
+//! Each mounted [App][crate::App] has its own executors,
+//! which are dropped (along with their tasks) when the app is unmounted.
 //! Use the [get_executor] function to access the executor.
+//!
+//! For tasks that belong to a component (and should be cancelled along with it),
+//! use a [TaskScope][crate::TaskScope] instead.
+//!
+//! To choose when the executor runs after being woken, call [set_scheduling]
+//! before mounting your app (or [App::set_scheduling][crate::App::set_scheduling] after).
+//!
+//! ```
+//! # use async_ui_web::{executor::{set_scheduling, Scheduling}, mount};
+//! # let my_app = || std::future::pending::<()>();
+//! # let _ = || {
+//! // line up updates with frames, for smooth animations
+//! set_scheduling(Scheduling::AnimationFrame);
+//! mount(my_app());
+//! # };
+//! ```
+//!
+//! To keep the page responsive during large renders, give the executor a
+//! time budget with [set_time_budget]. Long-running work can check
+//! [should_yield] and call [yield_now] to continue in a later run.
+//!
+//! # Priorities
+//!
+//! Tasks can be spawned with a [Priority] using [spawn_with_priority].
+//! Whenever a task is ready to run, it runs before all ready tasks of lower priority.
+//! Tasks spawned on the executor from [get_executor] have [Priority::Normal].
+//!
+//! Tasks spawned with [spawn_idle] only run when the browser is idle
+//! (see [`requestIdleCallback`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback)).
+//! Use this for things like indexing and prefetching.
+//! Like the other executors, each app has its own idle executor.
 
-use std::{cell::OnceCell, future::pending};
+use std::{
+    cell::RefCell,
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll},
+};
 
-use async_executor::LocalExecutor;
-use async_ui_web_core::executor::set_executor_future;
+use async_executor::{LocalExecutor, Task};
+use async_ui_web_core::executor::{schedule, set_executor_future};
+pub use async_ui_web_core::executor::{
+    set_scheduling, set_time_budget, should_yield, yield_now, RootExecutor, Scheduling, YieldNow,
+};
 
 thread_local! {
-    static EXECUTOR: OnceCell<&'static LocalExecutor<'static>> = OnceCell::new();
+    /// Executors for tasks spawned outside of any mounted app.
+    static DEFAULT_EXECUTORS: Rc<Executors> = {
+        let exes = Executors::new();
+        set_executor_future(Box::new(exes.run()));
+        exes
+    };
+    /// The executors of the apps that are running right now, innermost last.
+    static CURRENT_EXECUTORS: RefCell<Vec<Rc<Executors>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The executors of one app.
+pub(crate) struct Executors {
+    user_blocking: LocalExecutor<'static>,
+    normal: LocalExecutor<'static>,
+    background: LocalExecutor<'static>,
+    idle: idle::Idle,
+}
+
+impl Executors {
+    pub(crate) fn new() -> Rc<Self> {
+        let exes = Rc::new(Self {
+            user_blocking: LocalExecutor::new(),
+            normal: LocalExecutor::new(),
+            background: LocalExecutor::new(),
+            idle: idle::Idle::new(),
+        });
+        idle::register(&exes);
+        exes
+    }
+    pub(crate) fn get(&self, priority: Priority) -> &LocalExecutor<'static> {
+        match priority {
+            Priority::UserBlocking => &self.user_blocking,
+            Priority::Normal => &self.normal,
+            Priority::Background => &self.background,
+        }
+    }
+    /// The future that drives these executors. Give it to a [RootExecutor].
+    pub(crate) fn run(self: &Rc<Self>) -> impl Future<Output = ()> {
+        PriorityRun {
+            ticks: PRIORITIES.map(|priority| tick(self, priority)),
+            executors: self.clone(),
+        }
+    }
 }
 
-/// Get the executor that is driving the framework.
+fn current_executors() -> Rc<Executors> {
+    CURRENT_EXECUTORS
+        .with(|current| current.borrow().last().cloned())
+        .unwrap_or_else(|| DEFAULT_EXECUTORS.with(Rc::clone))
+}
+
+/// A handle to one of the executors of an app.
+///
+/// Spawn tasks with it through [Deref] to [LocalExecutor].
+/// The app's tasks are kept alive for as long as any `Executor` of the app is,
+/// so avoid holding on to one inside the app's own tasks.
+#[derive(Clone)]
+pub struct Executor {
+    executors: Rc<Executors>,
+    priority: Priority,
+}
+
+impl Deref for Executor {
+    type Target = LocalExecutor<'static>;
+    fn deref(&self) -> &Self::Target {
+        self.executors.get(self.priority)
+    }
+}
+
+impl Executor {
+    /// A handle to the same executor that doesn't keep the app's tasks alive.
+    pub(crate) fn downgrade(&self) -> WeakExecutor {
+        WeakExecutor {
+            executors: Rc::downgrade(&self.executors),
+            priority: self.priority,
+        }
+    }
+}
+
+/// An [Executor] that doesn't keep its app alive.
+///
+/// For things made inside an app that may need to spawn tasks on it later,
+/// from wherever they happen to be called (like leave hooks).
+#[derive(Clone)]
+pub(crate) struct WeakExecutor {
+    executors: Weak<Executors>,
+    priority: Priority,
+}
+
+impl WeakExecutor {
+    /// The executor, unless the app is gone.
+    pub(crate) fn upgrade(&self) -> Option<Executor> {
+        Some(Executor {
+            executors: self.executors.upgrade()?,
+            priority: self.priority,
+        })
+    }
+}
+
+/// Get the executor that is driving the current app.
 /// Use this executor to spawn your own tasks if you want.
 ///
 /// ```
@@ -23,12 +161,436 @@ thread_local! {
 /// let task = exe.spawn(some_async_function());
 /// # }
 /// ```
-pub fn get_executor() -> &'static LocalExecutor<'static> {
-    EXECUTOR.with(|cell| {
-        *cell.get_or_init(|| {
-            let exe = Box::leak(Box::new(LocalExecutor::new()));
-            set_executor_future(Box::new(exe.run(pending())));
-            exe
-        })
-    })
+///
+/// When called from inside a mounted app, this is that app's executor, and
+/// its tasks are dropped when the app is [unmounted][crate::App::unmount].
+/// Otherwise, this is an executor shared by everything outside of apps.
+///
+/// This is the same as `get_executor_with_priority(Priority::Normal)`.
+///
+/// This used to return `&'static LocalExecutor<'static>`. The [Executor] derefs
+/// to one, so spawning works as before; code that stored the reference should
+/// store the [Executor] instead (see `CHANGELOG.md`).
+pub fn get_executor() -> Executor {
+    get_executor_with_priority(Priority::Normal)
+}
+
+/// How urgently a task should run. See the [module documentation][self].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Work the user is waiting on, like responding to input.
+    UserBlocking,
+    /// Rendering and everything else.
+    #[default]
+    Normal,
+    /// Work that can wait, but shouldn't wait for the browser to be idle.
+    Background,
+}
+
+/// Highest first.
+const PRIORITIES: [Priority; 3] = [
+    Priority::UserBlocking,
+    Priority::Normal,
+    Priority::Background,
+];
+
+/// Get the executor for tasks of the given priority. See [get_executor].
+pub fn get_executor_with_priority(priority: Priority) -> Executor {
+    Executor {
+        executors: current_executors(),
+        priority,
+    }
+}
+
+/// Spawn a task with the given priority.
+///
+/// ```
+/// # use async_ui_web::executor::{spawn_with_priority, Priority};
+/// # async fn sync_to_server() {}
+/// # fn example() {
+/// spawn_with_priority(sync_to_server(), Priority::Background).detach();
+/// # }
+/// ```
+///
+/// Like all spawned tasks, the task is cancelled if the returned [Task] is dropped.
+/// Call [detach][Task::detach] to let it run in the background.
+pub fn spawn_with_priority<T: 'static>(
+    future: impl Future<Output = T> + 'static,
+    priority: Priority,
+) -> Task<T> {
+    let task = get_executor_with_priority(priority).spawn(future);
+    schedule();
+    task
+}
+
+/// Drives the executors, always running a task from the highest-priority
+/// executor that has one ready.
+struct PriorityRun {
+    executors: Rc<Executors>,
+    ticks: [Pin<Box<dyn Future<Output = ()>>>; 3],
+}
+
+fn tick(executors: &Rc<Executors>, priority: Priority) -> Pin<Box<dyn Future<Output = ()>>> {
+    let executors = executors.clone();
+    Box::pin(async move { executors.get(priority).tick().await })
+}
+
+/// After running this many tasks, let the root executor check its time budget.
+const TICKS_PER_POLL: usize = 64;
+
+impl Future for PriorityRun {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        // so that `get_executor` in our tasks returns our executors
+        CURRENT_EXECUTORS.with(|current| current.borrow_mut().push(this.executors.clone()));
+        let _guard = scopeguard::guard((), |_| {
+            CURRENT_EXECUTORS.with(|current| current.borrow_mut().pop());
+        });
+        'run: for _ in 0..TICKS_PER_POLL {
+            // `tick` runs one task if there is one ready,
+            // and registers our waker otherwise.
+            for (priority, tick_fut) in PRIORITIES.into_iter().zip(this.ticks.iter_mut()) {
+                if tick_fut.as_mut().poll(cx).is_ready() {
+                    *tick_fut = tick(&this.executors, priority);
+                    continue 'run;
+                }
+            }
+            // nothing left to run
+            return Poll::Pending;
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Polls the future with the given executors as the current ones,
+/// so that [get_executor] in it returns them.
+/// For rendering outside of a mounted app, as in server-side rendering.
+#[cfg(feature = "ssr")]
+#[pin_project::pin_project]
+pub(crate) struct WithExecutors<F> {
+    #[pin]
+    future: F,
+    executors: Rc<Executors>,
+}
+
+#[cfg(feature = "ssr")]
+impl<F> WithExecutors<F> {
+    pub(crate) fn new(future: F, executors: Rc<Executors>) -> Self {
+        Self { future, executors }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl<F: Future> Future for WithExecutors<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        CURRENT_EXECUTORS.with(|current| current.borrow_mut().push(this.executors.clone()));
+        let _guard = scopeguard::guard((), |_| {
+            CURRENT_EXECUTORS.with(|current| current.borrow_mut().pop());
+        });
+        this.future.poll(cx)
+    }
+}
+
+pub use idle::{get_idle_executor, spawn_idle, IdleExecutor};
+
+mod idle {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::BTreeMap,
+        future::Future,
+        ops::Deref,
+        pin::Pin,
+        rc::{Rc, Weak},
+        task::{Context, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use async_executor::{LocalExecutor, Task};
+    use async_ui_web_core::window::WINDOW;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
+    use web_sys::IdleDeadline;
+
+    use super::{current_executors, Executors, CURRENT_EXECUTORS};
+
+    thread_local! {
+        /// The executors of every live idle executor,
+        /// so that wakers and callbacks can find them by id.
+        static IDLES: RefCell<BTreeMap<usize, Weak<Executors>>> = const { RefCell::new(BTreeMap::new()) };
+        static NEXT_IDLE_ID: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// The idle executor of one app. Owned by the app's [Executors].
+    /// Registered with [register] once its [Executors] are made.
+    pub(crate) struct Idle {
+        id: usize,
+        /// Shared with `tick`, which can't borrow it from us.
+        pub(super) executor: Rc<LocalExecutor<'static>>,
+        tick: RefCell<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+        pub(super) requested: Cell<bool>,
+    }
+
+    impl Idle {
+        pub(crate) fn new() -> Self {
+            Self {
+                id: NEXT_IDLE_ID.with(|next| next.replace(next.get() + 1)),
+                executor: Rc::new(LocalExecutor::new()),
+                tick: RefCell::new(None),
+                requested: Cell::new(false),
+            }
+        }
+        /// Run idle tasks until `time_remaining` runs out or there are no tasks ready.
+        /// Returns `true` if we stopped because we ran out of time.
+        pub(crate) fn run(&self, time_remaining: impl Fn() -> f64) -> bool {
+            let waker = idle_waker(self.id);
+            let mut cx = Context::from_waker(&waker);
+            let mut tick = self.tick.borrow_mut();
+            loop {
+                if time_remaining() <= 0.0 {
+                    return true;
+                }
+                let fut = tick.get_or_insert_with(|| {
+                    let executor = self.executor.clone();
+                    Box::pin(async move { executor.tick().await })
+                });
+                if fut.as_mut().poll(&mut cx).is_ready() {
+                    *tick = None;
+                } else {
+                    return false;
+                }
+            }
+        }
+        fn request(&self) {
+            if self.requested.replace(true) {
+                return;
+            }
+            // A fresh closure every time, so that pending callbacks
+            // don't keep an unmounted app's tasks alive.
+            let id = self.id;
+            let callback = Closure::once_into_js(move |deadline: JsValue| run_idle(id, deadline));
+            let callback = callback.unchecked_ref();
+            WINDOW.with(|window| {
+                // Safari doesn't have `requestIdleCallback` yet.
+                if js_sys::Reflect::has(window, &"requestIdleCallback".into()).unwrap_or(false) {
+                    window.request_idle_callback(callback).unwrap_throw();
+                } else {
+                    window
+                        .set_timeout_with_callback_and_timeout_and_arguments_0(callback, 50)
+                        .unwrap_throw();
+                }
+            });
+        }
+    }
+
+    impl Drop for Idle {
+        fn drop(&mut self) {
+            let _ = IDLES.try_with(|idles| idles.borrow_mut().remove(&self.id));
+        }
+    }
+
+    pub(crate) fn register(executors: &Rc<Executors>) {
+        IDLES.with(|idles| {
+            idles
+                .borrow_mut()
+                .insert(executors.idle.id, Rc::downgrade(executors))
+        });
+    }
+
+    fn find_executors(id: usize) -> Option<Rc<Executors>> {
+        IDLES.with(|idles| idles.borrow().get(&id).and_then(Weak::upgrade))
+    }
+
+    /// A handle to the idle executor of an app.
+    ///
+    /// Spawn tasks with it through [Deref] to [LocalExecutor].
+    /// Like [Executor][super::Executor], it keeps the app's idle tasks alive.
+    #[derive(Clone)]
+    pub struct IdleExecutor {
+        executors: Rc<Executors>,
+    }
+
+    impl Deref for IdleExecutor {
+        type Target = LocalExecutor<'static>;
+        fn deref(&self) -> &Self::Target {
+            &self.executors.idle.executor
+        }
+    }
+
+    /// Get the executor for tasks that only run when the browser is idle.
+    ///
+    /// Tasks on this executor run outside of the UI tree,
+    /// so they shouldn't render anything.
+    ///
+    /// Like [get_executor][super::get_executor], when called from inside a
+    /// mounted app, this is that app's idle executor, and its tasks are
+    /// dropped when the app is [unmounted][crate::App::unmount].
+    pub fn get_idle_executor() -> IdleExecutor {
+        let executors = current_executors();
+        // so that tasks spawned through the handle get to run
+        executors.idle.request();
+        IdleExecutor { executors }
+    }
+
+    /// Spawn a task that only runs when the browser is idle.
+    /// See [get_idle_executor].
+    ///
+    /// ```
+    /// # use async_ui_web::executor::spawn_idle;
+    /// # async fn build_search_index() {}
+    /// # fn example() {
+    /// spawn_idle(build_search_index()).detach();
+    /// # }
+    /// ```
+    pub fn spawn_idle<T: 'static>(future: impl Future<Output = T> + 'static) -> Task<T> {
+        get_idle_executor().spawn(future)
+    }
+
+    /// Run idle tasks until we run out of idle time or tasks.
+    fn run_idle(id: usize, deadline: JsValue) {
+        let Some(executors) = find_executors(id) else {
+            // the app has been unmounted
+            return;
+        };
+        let idle = &executors.idle;
+        idle.requested.set(false);
+        let time_remaining = match deadline.dyn_into::<IdleDeadline>() {
+            Ok(deadline) => Box::new(move || deadline.time_remaining()) as Box<dyn Fn() -> f64>,
+            Err(_) => {
+                // `setTimeout` fallback: give ourselves a few milliseconds
+                let end = js_sys::Date::now() + 5.0;
+                Box::new(move || end - js_sys::Date::now())
+            }
+        };
+        // so that `get_executor` in idle tasks returns the app's executors
+        CURRENT_EXECUTORS.with(|current| current.borrow_mut().push(executors.clone()));
+        let out_of_time = idle.run(time_remaining);
+        CURRENT_EXECUTORS.with(|current| current.borrow_mut().pop());
+        if out_of_time {
+            idle.request();
+        }
+    }
+
+    /// A waker that requests an idle callback when woken.
+    fn idle_waker(id: usize) -> Waker {
+        fn wake(data: *const ()) {
+            if let Some(executors) = find_executors(data as usize) {
+                executors.idle.request();
+            }
+        }
+        const VTABLE: RawWakerVTable =
+            RawWakerVTable::new(|data| RawWaker::new(data, &VTABLE), wake, wake, |_| {});
+        unsafe { Waker::from_raw(RawWaker::new(id as *const (), &VTABLE)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use futures_lite::future::{block_on, poll_once, yield_now};
+
+    use super::*;
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn log_task(log: &Log, name: &'static str) -> impl Future<Output = ()> + 'static {
+        let log = log.clone();
+        async move { log.borrow_mut().push(name) }
+    }
+
+    #[test]
+    fn priorities() {
+        let exes = Executors::new();
+        let log = Log::default();
+        exes.get(Priority::Background)
+            .spawn(log_task(&log, "background"))
+            .detach();
+        exes.get(Priority::Normal)
+            .spawn({
+                let log = log.clone();
+                async move {
+                    log.borrow_mut().push("normal");
+                    // spawned from inside the app, with a higher priority
+                    get_executor_with_priority(Priority::UserBlocking)
+                        .spawn(log_task(&log, "spawned"))
+                        .detach();
+                    yield_now().await;
+                    log.borrow_mut().push("normal again");
+                }
+            })
+            .detach();
+        exes.get(Priority::UserBlocking)
+            .spawn(log_task(&log, "user blocking"))
+            .detach();
+        let mut run = Box::pin(exes.run());
+        block_on(poll_once(run.as_mut()));
+        assert_eq!(
+            *log.borrow(),
+            [
+                "user blocking",
+                "normal",
+                "spawned",
+                "normal again",
+                "background"
+            ]
+        );
+    }
+
+    #[test]
+    fn tasks_dropped_with_app() {
+        let exes = Executors::new();
+        let dropped = Rc::new(RefCell::new(false));
+        let guard = scopeguard::guard(dropped.clone(), |dropped| *dropped.borrow_mut() = true);
+        exes.get(Priority::Background)
+            .spawn(async move {
+                let _guard = guard;
+                std::future::pending::<()>().await;
+            })
+            .detach();
+        let mut run = Box::pin(exes.run());
+        block_on(poll_once(run.as_mut()));
+        assert!(!*dropped.borrow());
+        drop(run);
+        drop(exes);
+        assert!(*dropped.borrow());
+    }
+
+    #[test]
+    fn idle() {
+        let exes = Executors::new();
+        // as if a callback was already requested, so that we don't ask the browser
+        exes.idle.requested.set(true);
+        let log = Log::default();
+        for name in ["a", "b", "c"] {
+            exes.idle.executor.spawn(log_task(&log, name)).detach();
+        }
+        // idle tasks don't run with the others
+        let mut run = Box::pin(exes.run());
+        block_on(poll_once(run.as_mut()));
+        assert!(log.borrow().is_empty());
+        // out of idle time after one task
+        let budget = std::cell::Cell::new(1.0);
+        assert!(exes.idle.run(|| budget.replace(0.0)));
+        assert_eq!(*log.borrow(), ["a"]);
+        assert!(!exes.idle.run(|| 1.0));
+        assert_eq!(*log.borrow(), ["a", "b", "c"]);
+
+        // unfinished idle tasks are dropped with the app
+        let dropped = Rc::new(RefCell::new(false));
+        let guard = scopeguard::guard(dropped.clone(), |dropped| *dropped.borrow_mut() = true);
+        exes.idle
+            .executor
+            .spawn(async move {
+                let _guard = guard;
+                std::future::pending::<()>().await;
+            })
+            .detach();
+        drop(run);
+        drop(exes);
+        assert!(*dropped.borrow());
+    }
 }
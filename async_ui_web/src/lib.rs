@@ -10,20 +10,148 @@ pub mod executor;
 pub mod lists;
 mod mount;
 mod no_child;
+mod platform;
+mod prop_value;
 mod shortcuts;
+#[cfg(feature = "ssr")]
+pub mod ssr;
 
 pub use async_ui_internal_utils::reactive_cell::ReactiveCell;
-pub use async_ui_web_core::combinators::{join, race, race_ok, try_join};
+pub use async_ui_web_core::combinators::{join, race, race_ok, try_join, JoinSet, JoinSetHandle};
+pub use async_ui_web_core::{provide, use_context, with_context, TaskScope};
 pub use async_ui_web_html::nodes as html;
+pub use async_ui_web_html::svg;
+/// # Examples
+///
+/// ```
+/// # use std::future::Future;
+/// # use async_ui_web::{component, html::Div, join, prelude_traits::*};
+/// #[component]
+/// async fn card<C: Future<Output = ()>>(
+///     title: &str,
+///     #[prop(default = 2)] level: u8,
+///     children: C,
+/// ) {
+///     let div = Div::new();
+///     div.with_attr("data-level", &level.to_string());
+///     div.render(join((title.render(), children))).await;
+/// }
+/// # let _ = async {
+/// // Setters can come in any order.
+/// Card::props().level(3).title("Hello").render("!".render()).await;
+/// # };
+/// ```
+///
+/// Rendering without setting every required prop doesn't compile.
+///
+/// ```compile_fail,E0599
+/// # use async_ui_web::component;
+/// #[component]
+/// async fn greeting(name: &str) {}
+/// # let _ = async {
+/// Greeting::props().render().await;
+/// # };
+/// ```
+pub use async_ui_web_macros::component;
 pub use async_ui_web_macros::css;
+/// # Examples
+///
+/// Branches whose pattern doesn't match are disabled, and `complete` runs once all are.
+///
+/// ```
+/// # use async_ui_web::select;
+/// # use futures_lite::{Stream, StreamExt};
+/// # async fn example(mut numbers: impl Stream<Item = i32> + Unpin) {
+/// loop {
+///     select! {
+///         Some(n @ 1..) = numbers.next() => println!("{n}"),
+///         complete => break,
+///     }
+/// }
+/// # }
+/// ```
+///
+/// `default` runs if no future is ready right away,
+/// and with `biased;`, the first ready branch wins.
 pub use async_ui_web_macros::select;
-pub use mount::{mount, mount_at};
+/// # Examples
+///
+/// ```
+/// # use async_ui_web::{prelude_traits::*, view};
+/// # let _ = async {
+/// let name = String::from("world");
+/// view! {
+///     div(class = "card primary", data-id = "1") {
+///         h1 { "Hello " { name.render() } }
+///         input(type = "text", disabled)
+///         span as label { "loading" }
+///         {
+///             // bound elements can be used in all the futures
+///             async {
+///                 label.with_class("ready");
+///             }
+///         }
+///     }
+/// }
+/// .await;
+/// # };
+/// ```
+///
+/// Tags without a type in [html] don't compile,
+///
+/// ```compile_fail
+/// # use async_ui_web::view;
+/// # let _ = async {
+/// view! { blink { "Hello" } }.await;
+/// # };
+/// ```
+///
+/// neither do children for elements that can't have them,
+///
+/// ```compile_fail,E0277
+/// # use async_ui_web::view;
+/// # let _ = async {
+/// view! { input { "Hello" } }.await;
+/// # };
+/// ```
+///
+/// and neither do attributes unknown to HTML.
+/// Only the name is checked: an attribute of some other element is accepted.
+///
+/// ```compile_fail
+/// # use async_ui_web::view;
+/// # let _ = async {
+/// view! { div(clas = "typo") }.await;
+/// # };
+/// ```
+pub use async_ui_web_macros::view;
+pub use mount::{hydrate, hydrate_at, mount, mount_at, App};
 pub use no_child::NoChild;
 
 #[doc(hidden)]
 pub mod __private_macro_only {
+    #[doc(hidden)]
+    pub use async_ui_web_core::combinators::race_biased;
+    #[doc(hidden)]
+    pub use async_ui_web_html::nodes::{tags, RenderView};
+    #[doc(hidden)]
+    pub use async_ui_web_macros::view;
     #[doc(hidden)]
     pub use wasm_bindgen;
+
+    /// A required prop of a `#[component]` that hasn't been set yet.
+    #[doc(hidden)]
+    pub struct MissingProp;
+
+    #[doc(hidden)]
+    pub fn set_attribute(
+        element: &impl AsRef<async_ui_web_core::dom::Element>,
+        name: &str,
+        value: &str,
+    ) {
+        use wasm_bindgen::UnwrapThrowExt;
+        element.as_ref().set_attribute(name, value).unwrap_throw();
+    }
 }
 
 pub mod event_handling {
@@ -38,14 +166,19 @@ pub mod event_traits {
     /*!
     Traits for event handling.
     */
-    pub use async_ui_web_html::events::{EmitElementEvent, EmitEvent, EmitHtmlElementEvent};
+    pub use async_ui_web_html::events::{
+        EmitElementEvent, EmitEvent, EmitHtmlElementEvent, EmitSvgElementEvent,
+    };
 }
 
 pub mod shortcut_traits {
     /*!
     Traits provided for convenience.
      */
-    pub use super::shortcuts::{ShortcutClassList, ShortcutClassListBuilder, ShortcutRenderStr};
+    pub use super::prop_value::PropValue;
+    pub use super::shortcuts::{
+        ShortcutAttributeBuilder, ShortcutClassList, ShortcutClassListBuilder, ShortcutRenderStr,
+    };
     pub use async_ui_web_core::combinators::UiFutureExt;
 }
 
@@ -58,10 +191,11 @@ pub mod prelude_traits {
     ```
      */
     pub use super::shortcuts::{
-        ShortcutClassList as _, ShortcutClassListBuilder as _, ShortcutRenderStr as _,
+        ShortcutAttributeBuilder as _, ShortcutClassList as _, ShortcutClassListBuilder as _,
+        ShortcutRenderStr as _,
     };
     pub use async_ui_web_core::combinators::UiFutureExt as _;
     pub use async_ui_web_html::events::{
-        EmitElementEvent as _, EmitEvent as _, EmitHtmlElementEvent as _,
+        EmitElementEvent as _, EmitEvent as _, EmitHtmlElementEvent as _, EmitSvgElementEvent as _,
     };
 }
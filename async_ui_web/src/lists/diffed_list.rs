@@ -82,6 +82,10 @@ where
     /// Update the list; reorder, insert, or delete futures as needed.
     ///
     /// Time complexity: O(n) in Rust code.
+    ///
+    /// The new futures are rendered by [render][Self::render], which yields to
+    /// the browser when the [time budget][crate::executor::set_time_budget]
+    /// runs out, so a large update is spread over several runs.
     pub fn update(&self, new_keys: Vec<K>) {
         let mut inner = self.inner.borrow_mut();
         let Inner {
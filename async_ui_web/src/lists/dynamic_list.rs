@@ -8,13 +8,20 @@ use std::{
 };
 
 use async_executor::{LocalExecutor, Task};
-use async_ui_web_core::{ContainerNodeFuture, DetachmentBlocker, SiblingNodeFuture};
+use async_ui_web_core::{
+    executor::{should_yield, yield_now},
+    DetachmentBlocker,
+};
 use wasm_bindgen::UnwrapThrowExt;
-use web_sys::{Comment, DocumentFragment};
+
+use super::super::platform::{
+    dom::{Comment, DocumentFragment, Node},
+    ContainerNodeFuture, SiblingNodeFuture,
+};
 
 #[derive(Clone, Debug)]
 enum ContainingNode {
-    Real(web_sys::Node),
+    Real(Node),
     Fake(DocumentFragment),
 }
 
@@ -53,8 +60,8 @@ join((
 pub struct DynamicList<'c, K: Eq + Hash, F: Future + 'c> {
     inner: RefCell<DynamicListInner<K, F>>,
     executor: LocalExecutor<'c>,
-    list_end_marker: web_sys::Node,
-    list_start_marker: web_sys::Node,
+    list_end_marker: Node,
+    list_start_marker: Node,
     detachment_blocker: DetachmentBlocker,
 }
 
@@ -65,12 +72,12 @@ struct DynamicListInner<K: Eq + Hash, F: Future> {
 
 struct Stored<F: Future> {
     task: Task<F::Output>,
-    start_marker: web_sys::Node,
-    end_marker: web_sys::Node,
+    start_marker: Node,
+    end_marker: Node,
 }
 
 impl ContainingNode {
-    fn get(&self) -> &web_sys::Node {
+    fn get(&self) -> &Node {
         match self {
             ContainingNode::Real(real) => real,
             ContainingNode::Fake(fake) => fake,
@@ -122,8 +129,8 @@ impl<'c, K: Eq + Hash, F: Future + 'c> DynamicList<'c, K, F> {
     pub fn insert(&self, key: K, future: F, before: Option<&K>) -> bool {
         let mut inner = self.inner.borrow_mut();
         let container = inner.containing_node.get();
-        let start_marker: web_sys::Node = Comment::new().unwrap_throw().into();
-        let end_marker: web_sys::Node = Comment::new().unwrap_throw().into();
+        let start_marker: Node = Comment::new().unwrap_throw().into();
+        let end_marker: Node = Comment::new().unwrap_throw().into();
         let after = before
             .map(|k| &inner.items.get(k).unwrap().start_marker)
             .unwrap_or(&self.list_end_marker);
@@ -290,6 +297,10 @@ impl<'c, K: Eq + Hash, F: Future + 'c> DynamicList<'c, K, F> {
 
     /// Render the list here.
     ///
+    /// Items are rendered (polled) here. If that takes longer than the
+    /// [time budget][crate::executor::set_time_budget], the rest continue in a later run,
+    /// so large updates don't block the page.
+    ///
     /// This async method never completes.
     pub async fn render(&self) {
         let real_containing_node;
@@ -337,7 +348,14 @@ impl<'c, K: Eq + Hash, F: Future + 'c> DynamicList<'c, K, F> {
             );
             inner.containing_node = ContainingNode::Fake(fragment);
         });
-        self.executor.run(pending()).await
+        // Run the items one at a time, so that rendering many new items
+        // doesn't go over the time budget.
+        loop {
+            self.executor.tick().await;
+            if should_yield() {
+                yield_now().await;
+            }
+        }
     }
 }
 
@@ -349,10 +367,10 @@ impl<'c, K: Eq + Hash, F: Future> Drop for DynamicList<'c, K, F> {
 /// Move `start_marker`, `end_marker`, and eveything between them
 /// into `container` at location before `after`.
 fn move_nodes_before(
-    container: &web_sys::Node,
-    start_marker: &web_sys::Node,
-    end_marker: &web_sys::Node,
-    after: Option<&web_sys::Node>,
+    container: &Node,
+    start_marker: &Node,
+    end_marker: &Node,
+    after: Option<&Node>,
 ) {
     let mut node = start_marker.clone();
     loop {
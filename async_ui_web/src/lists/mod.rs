@@ -23,6 +23,11 @@
 //! This approach is not very flexible: there is no way to insert into or remove
 //! from the vec once you've passed it to `join`.
 //!
+//! ### JoinSet
+//! [JoinSet][crate::JoinSet] is like `join` on a Vec, but you can keep adding
+//! futures to it while it runs. Each new future is rendered after the
+//! existing ones, and is removed when it completes.
+//!
 //! ### DiffedList
 //! [DiffedList] is more flexible. You provide it with a Vec of "keys", and
 //! a closure to convert each key into a future. You can update the keys Vec
@@ -47,6 +52,12 @@
 //! automatically gets updated, with DynamicList you have to deal with
 //! inserting/moving/removing futures manually.
 //!
+//! ### render_stream
+//! [render_stream] renders each item of a [Stream][futures_lite::Stream]
+//! after the previous ones, as the items arrive. It is made for feeds and logs,
+//! where new items are only ever appended (and old ones can be dropped from the
+//! front).
+//!
 //! ### VirtualizedList
 //! [VirtualizedList] is very different from all the other lists. It is made
 //! for very large collections. It only renders the items that are visible in
@@ -58,9 +69,11 @@
 mod diffed_list;
 mod dynamic_list;
 mod modeled_list;
+mod render_stream;
 mod virtualized_list;
 
 pub use diffed_list::DiffedList;
 pub use dynamic_list::DynamicList;
 pub use modeled_list::{ListModel, ModeledList};
+pub use render_stream::{render_stream, RenderStream};
 pub use virtualized_list::VirtualizedList;
@@ -250,6 +250,10 @@ impl<'c, K: Eq + Hash + Clone, F: Future, R: Fn(&K) -> F> ModeledList<'c, K, F,
     /// Time complexity: O(C)
     /// where C is the number of changes done to the model since last `update` call.
     ///
+    /// The new futures are rendered by [render][Self::render], which yields to
+    /// the browser when the [time budget][crate::executor::set_time_budget]
+    /// runs out, so a large update is spread over several runs.
+    ///
     /// Repeated calls of `update` should pass same instance of `ListModel`.
     /// If not, performance will suffer (although the list will still work fine).
     pub fn update(&self, model: &ListModel<K>) {
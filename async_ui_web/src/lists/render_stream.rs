@@ -0,0 +1,286 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    future::{pending, poll_fn, Future, Pending},
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use async_executor::{LocalExecutor, Task};
+use async_ui_web_core::DetachmentBlocker;
+use futures_lite::Stream;
+use pin_project::{pin_project, pinned_drop};
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::super::platform::{
+    dom::{Comment, Node},
+    ContainerNodeFuture, SiblingNodeFuture,
+};
+
+/**
+Render every item of a [Stream], one after another, in the order they arrive.
+
+For each item the stream yields, `render` is called to make a future,
+which is rendered after all the previous ones.
+This is good for chat logs, notification feeds, streaming search results...
+
+```
+# use async_ui_web::{lists::render_stream, prelude_traits::*};
+# use futures_lite::stream;
+# let messages = stream::iter(["hello", "world"]);
+# let _ = async {
+render_stream(messages, |message| message.render())
+    // only keep the latest 100 messages
+    .with_max_len(100)
+    .await;
+# };
+```
+
+When the future of an item completes, the item is removed from the list.
+The returned [RenderStream] can be awaited as a future, which never completes.
+It is also a [Stream] of the outputs of the completed futures:
+
+```
+# use std::pin::pin;
+# use async_ui_web::{html::Button, lists::render_stream, prelude_traits::*, race};
+# use futures_lite::{stream, StreamExt};
+# let notifications = stream::iter(["saved", "sent"]);
+# let _ = async {
+let mut dismissed = pin!(render_stream(notifications, |text| async move {
+    let button = Button::new();
+    race((button.render(text.render()), async {
+        button.until_click().await;
+    }))
+    .await;
+    text
+}));
+while let Some(text) = dismissed.next().await {
+    // `text` was dismissed
+}
+# };
+```
+
+The stream never ends, even after the input stream ends,
+because dropping it would remove everything it rendered.
+*/
+pub fn render_stream<'c, S, R, F>(stream: S, render: R) -> RenderStream<'c, S, R, F>
+where
+    S: Stream,
+    R: FnMut(S::Item) -> F,
+    F: Future + 'c,
+{
+    let executor = Rc::new(LocalExecutor::new());
+    let run = {
+        let executor = executor.clone();
+        Box::pin(async move { executor.run(pending()).await })
+    };
+    let anchor_marker: Node = Comment::new().unwrap_throw().into();
+    RenderStream {
+        stream,
+        render,
+        stream_done: false,
+        max_len: None,
+        anchor: Some(ContainerNodeFuture::new(pending(), anchor_marker.clone())),
+        anchor_marker,
+        start_marker: Comment::new().unwrap_throw().into(),
+        end_marker: Comment::new().unwrap_throw().into(),
+        next_id: 0,
+        items: BTreeMap::new(),
+        finished: Rc::new(RefCell::new(Vec::new())),
+        outputs: VecDeque::new(),
+        run,
+        executor,
+        drop: DetachmentBlocker,
+    }
+}
+
+/// Future and [Stream] returned by [render_stream].
+#[pin_project(PinnedDrop)]
+pub struct RenderStream<'c, S, R, F: Future> {
+    #[pin]
+    stream: S,
+    render: R,
+    stream_done: bool,
+    max_len: Option<usize>,
+    /// Puts `anchor_marker` in the right place in the parent.
+    #[pin]
+    anchor: Option<ContainerNodeFuture<Pending<()>>>,
+    anchor_marker: Node,
+    /// Everything we render goes between these two markers (inserted after the anchor).
+    /// Unlike the anchor, they aren't removed by the parent, so we remove them ourselves.
+    start_marker: Node,
+    end_marker: Node,
+    next_id: u64,
+    /// Keyed by arrival order.
+    items: BTreeMap<u64, Item<F>>,
+    /// Written to by the items' tasks.
+    finished: Finished<F::Output>,
+    outputs: VecDeque<F::Output>,
+    run: Pin<Box<dyn Future<Output = ()> + 'c>>,
+    executor: Rc<LocalExecutor<'c>>,
+    drop: DetachmentBlocker,
+}
+
+/// Outputs of completed items, with the ids of the items.
+type Finished<O> = Rc<RefCell<Vec<(u64, O)>>>;
+
+struct Item<F> {
+    /// Polls `future`, then pushes the output to `finished`.
+    _task: Task<()>,
+    /// Shared with the task, so that we can drop the future ourselves.
+    future: ItemFuture<F>,
+    /// Everything the item renders goes right before this.
+    end_marker: Node,
+}
+
+type ItemFuture<F> = Rc<RefCell<Option<Pin<Box<SiblingNodeFuture<F>>>>>>;
+
+impl<'c, S, R, F> RenderStream<'c, S, R, F>
+where
+    S: Stream,
+    R: FnMut(S::Item) -> F,
+    F: Future + 'c,
+{
+    /// Keep at most `max_len` items in the list.
+    /// When a new item arrives to a full list, the oldest item is dropped.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+    fn poll_render(self: Pin<&mut Self>, cx: &mut Context<'_>) {
+        let mut this = self.project();
+        if let Some(anchor) = this.anchor.as_mut().as_pin_mut() {
+            let _ = anchor.poll(cx);
+        }
+        let container = this.anchor_marker.parent_node().unwrap_throw();
+        if this.start_marker.parent_node().is_none() {
+            let after = this.anchor_marker.next_sibling();
+            container
+                .insert_before(this.start_marker, after.as_ref())
+                .unwrap_throw();
+            container
+                .insert_before(this.end_marker, after.as_ref())
+                .unwrap_throw();
+        }
+        while !*this.stream_done {
+            let item = match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => item,
+                Poll::Ready(None) => {
+                    *this.stream_done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            };
+            let end_marker: Node = Comment::new().unwrap_throw().into();
+            container
+                .insert_before(&end_marker, Some(this.end_marker))
+                .unwrap_throw();
+            let future: ItemFuture<F> = Rc::new(RefCell::new(Some(Box::pin(
+                SiblingNodeFuture::new((this.render)(item), end_marker.clone()),
+            ))));
+            let id = *this.next_id;
+            *this.next_id += 1;
+            let finished = this.finished.clone();
+            let task = this.executor.spawn({
+                let future = future.clone();
+                poll_fn(move |cx| {
+                    let mut slot = future.borrow_mut();
+                    let Some(fut) = slot.as_mut() else {
+                        // evicted
+                        return Poll::Ready(());
+                    };
+                    let Poll::Ready(output) = fut.as_mut().poll(cx) else {
+                        return Poll::Pending;
+                    };
+                    // Drop it (removing its nodes) where it was rendered.
+                    let fut = slot.take();
+                    drop(slot);
+                    drop(fut);
+                    finished.borrow_mut().push((id, output));
+                    Poll::Ready(())
+                })
+            });
+            this.items.insert(
+                id,
+                Item {
+                    _task: task,
+                    future,
+                    end_marker,
+                },
+            );
+            if this.max_len.is_some_and(|max| this.items.len() > max) {
+                let (_, evicted) = this.items.pop_first().unwrap_throw();
+                // Drop the evicted future right now, in the context it was
+                // rendered in (the executor polls it in ours), removing its nodes.
+                let fut = evicted.future.borrow_mut().take();
+                drop(fut);
+                container.remove_child(&evicted.end_marker).unwrap_throw();
+            }
+        }
+        let _ = this.run.as_mut().poll(cx);
+        for (id, output) in this.finished.borrow_mut().drain(..) {
+            // The future has been dropped along with its nodes.
+            if let Some(item) = this.items.remove(&id) {
+                container.remove_child(&item.end_marker).unwrap_throw();
+            }
+            this.outputs.push_back(output);
+        }
+    }
+}
+
+impl<'c, S, R, F> Future for RenderStream<'c, S, R, F>
+where
+    S: Stream,
+    R: FnMut(S::Item) -> F,
+    F: Future + 'c,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.as_mut().poll_render(cx);
+        self.project().outputs.clear();
+        Poll::Pending
+    }
+}
+
+impl<'c, S, R, F> Stream for RenderStream<'c, S, R, F>
+where
+    S: Stream,
+    R: FnMut(S::Item) -> F,
+    F: Future + 'c,
+{
+    type Item = F::Output;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.outputs.is_empty() {
+            self.as_mut().poll_render(cx);
+        }
+        match self.project().outputs.pop_front() {
+            Some(output) => Poll::Ready(Some(output)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[pinned_drop]
+impl<'c, S, R, F: Future> PinnedDrop for RenderStream<'c, S, R, F> {
+    fn drop(self: Pin<&mut Self>) {
+        let mut this = self.project();
+        this.anchor.set(None);
+        // Remove everything between (and including) our markers in one go.
+        if let Some(container) = this.start_marker.parent_node() {
+            let mut node = this.start_marker.clone();
+            loop {
+                let next = node.next_sibling();
+                container.remove_child(&node).unwrap_throw();
+                if node.is_same_node(Some(this.end_marker)) {
+                    break;
+                }
+                node = next.unwrap_throw();
+            }
+        }
+        // The items' nodes are gone already.
+        this.drop.block_until_drop();
+    }
+}
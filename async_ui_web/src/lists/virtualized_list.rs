@@ -7,7 +7,7 @@ use std::{
 };
 
 use async_ui_internal_utils::reactive_cell::ReactiveCell;
-use async_ui_web_core::{combinators::join, ContainerNodeFuture};
+use async_ui_web_core::{combinators::join, executor::RootExecutor, ContainerNodeFuture};
 use futures_lite::{Future, StreamExt};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 use web_sys::{Element, HtmlElement, IntersectionObserver, IntersectionObserverInit};
@@ -106,6 +106,7 @@ impl<'c, Fut: Future + 'c, Renderer: FnMut(usize) -> Fut> VirtualizedList<'c, Fu
         };
         let signal = Rc::new(ReactiveCell::new(()));
         let signal_cloned = signal.clone();
+        let executor_root = RootExecutor::current();
         Self {
             list: DynamicList::new(),
             spacers: (spacer_front, spacer_back),
@@ -115,7 +116,7 @@ impl<'c, Fut: Future + 'c, Renderer: FnMut(usize) -> Fut> VirtualizedList<'c, Fu
             signal,
             wake_closure: Closure::new(move |_: JsValue| {
                 signal_cloned.borrow_mut();
-                async_ui_web_core::executor::run_now();
+                executor_root.run_now();
             }),
         }
     }
@@ -1,10 +1,110 @@
-use std::future::Future;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use async_executor::Task;
-use async_ui_web_core::{executor::schedule, window::DOCUMENT};
+use async_ui_web_core::{
+    executor::{RootExecutor, Scheduling},
+    hydration::Hydration,
+    window::DOCUMENT,
+};
 use wasm_bindgen::UnwrapThrowExt;
 
-use crate::executor::get_executor;
+use crate::executor::{Executors, Priority};
+
+/// A mounted app. Get this from [mount_at] or [hydrate_at].
+///
+/// Each app has its own executors and scheduling,
+/// so several apps can run on one page without affecting each other.
+///
+/// When dropped (or [unmounted][Self::unmount]), the app's future is dropped,
+/// removing everything it rendered and the event listeners it added,
+/// and every task spawned on the app's [executor][crate::executor::get_executor]
+/// (or its idle executor) is dropped too.
+/// To keep the app running forever, call [detach][Self::detach].
+///
+/// The app is also a [Future] that completes with the output of its future.
+#[must_use = "When the returned `App` is dropped your app unmounts. Call `.detach()` to avoid this."]
+pub struct App<T> {
+    task: Option<Task<T>>,
+    /// `None` once detached.
+    root: Option<RootExecutor>,
+}
+
+impl<T: 'static> App<T> {
+    fn start(future: impl Future<Output = T> + 'static, hydration: Option<Hydration>) -> Self {
+        let root = RootExecutor::new();
+        let executors = Executors::new();
+        match hydration {
+            Some(hydration) => {
+                root.set_future(Box::new(hydration.wrap(executors.run())));
+                root.on_settled(move || hydration.finish());
+            }
+            None => root.set_future(Box::new(executors.run())),
+        }
+        let task = executors.get(Priority::Normal).spawn(future);
+        root.schedule();
+        Self {
+            task: Some(task),
+            root: Some(root),
+        }
+    }
+}
+
+impl<T> App<T> {
+    /// Choose when this app is polled after being woken.
+    /// See [set_scheduling][crate::executor::set_scheduling].
+    pub fn set_scheduling(&self, scheduling: Scheduling) {
+        if let Some(root) = &self.root {
+            root.set_scheduling(scheduling);
+        }
+    }
+    /// Limit how long this app keeps running at a time.
+    /// See [set_time_budget][crate::executor::set_time_budget].
+    pub fn set_time_budget(&self, budget: Option<Duration>) {
+        if let Some(root) = &self.root {
+            root.set_time_budget(budget);
+        }
+    }
+    /// Stop the app and remove everything it rendered.
+    ///
+    /// This is the same as dropping the `App`.
+    /// It can't be called from inside the app itself.
+    pub fn unmount(self) {}
+    /// Let the app keep running forever.
+    pub fn detach(mut self) {
+        if let Some(task) = self.task.take() {
+            task.detach();
+        }
+        // The root owns the executors, which own the app's task.
+        std::mem::forget(self.root.take());
+    }
+}
+
+impl<T> Drop for App<T> {
+    fn drop(&mut self) {
+        // Dropping the task only marks it as cancelled;
+        // it is dropped along with the executors, which are owned by the root's future.
+        if let Some(root) = self.root.take() {
+            drop(self.task.take());
+            drop(root.take_future());
+        }
+    }
+}
+
+impl<T> Future for App<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.task.as_mut() {
+            Some(task) => Pin::new(task).poll(cx),
+            None => Poll::Pending,
+        }
+    }
+}
 
 /// Start running the given future, letting it render into the given node.
 ///
@@ -12,19 +112,24 @@ use crate::executor::get_executor;
 /// # use async_ui_web::mount_at;
 /// # let my_app = || std::future::pending::<()>();
 /// # let _ = || {
-/// let mounted_task = mount_at(my_app(), web_sys::window().unwrap().document().unwrap().into());
-/// mounted_task.detach();
+/// let app = mount_at(my_app(), web_sys::window().unwrap().document().unwrap().into());
+/// app.detach();
 /// # };
 /// ```
 ///
-/// The return value is a [Task]. When dropped, it will unmount your app.
-/// To prevent unmounting, call [detach][Task::detach] first.
-#[must_use = "When the returned `Task` is dropped your app unmounts. Call `.detach()` to avoid this."]
-pub fn mount_at<F: Future + 'static>(child_future: F, node: web_sys::Node) -> Task<F::Output> {
-    let fut = async_ui_web_core::ContainerNodeFuture::new_root(child_future, node);
-    let task = get_executor().spawn(fut);
-    schedule();
-    task
+/// The return value is an [App]. When dropped, it will unmount your app.
+/// To prevent unmounting, call [detach][App::detach] first.
+///
+/// To choose when the app is polled after being woken (for example, once per
+/// animation frame), call [App::set_scheduling].
+/// The app starts with the settings given to
+/// [set_scheduling][crate::executor::set_scheduling] and
+/// [set_time_budget][crate::executor::set_time_budget].
+pub fn mount_at<F: Future + 'static>(child_future: F, node: web_sys::Node) -> App<F::Output> {
+    App::start(
+        async_ui_web_core::ContainerNodeFuture::new_root(child_future, node),
+        None,
+    )
 }
 /// Start running the given future, letting it render into the `<body>` of the document.
 ///
@@ -46,3 +151,43 @@ pub fn mount<F: Future + 'static>(child_future: F) {
     )
     .detach();
 }
+
+/// Like [mount_at], but take over the HTML already inside the given node
+/// (for example, HTML produced by server-side rendering) instead of
+/// rendering next to it.
+///
+/// ```
+/// # use async_ui_web::hydrate_at;
+/// # let my_app = || std::future::pending::<()>();
+/// # let _ = || {
+/// let body = web_sys::window().unwrap().document().unwrap().body().unwrap();
+/// hydrate_at(my_app(), body.into()).detach();
+/// # };
+/// ```
+///
+/// The existing nodes themselves are reused: each element your app creates
+/// *is* the next existing element with the same tag name (in document order),
+/// and each text node the next existing text. Nodes that are rendered where
+/// they already are stay put, so images don't reload and focus is kept.
+/// See [hydration][async_ui_web_core::hydration] for the details.
+///
+/// This lasts until the app first settles (has nothing left to do),
+/// so content rendered a few polls in still takes over existing nodes.
+/// Then existing nodes that weren't rendered are removed.
+/// In debug builds, they are reported in the browser console.
+pub fn hydrate_at<F: Future + 'static>(child_future: F, node: web_sys::Node) -> App<F::Output> {
+    let hydration = Hydration::new(&node);
+    App::start(
+        async_ui_web_core::ContainerNodeFuture::new_root(child_future, node),
+        Some(hydration),
+    )
+}
+/// Like [mount], but take over the HTML already in the `<body>` of the document.
+/// See [hydrate_at].
+pub fn hydrate<F: Future + 'static>(child_future: F) {
+    hydrate_at(
+        child_future,
+        DOCUMENT.with(|doc| doc.body().unwrap_throw().into()),
+    )
+    .detach();
+}
@@ -0,0 +1,95 @@
+//! What the components, lists and shortcuts need from the browser.
+//!
+//! The [ssr][crate::ssr] module compiles the same files against the
+//! in-memory DOM, with its own `platform` module in place of this one.
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+pub(crate) use async_ui_web_core::{
+    dom, leaving, ContainerNodeFuture, OffscreenRoot, PortalFuture, SiblingNodeFuture,
+};
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Animation;
+
+use crate::prop_value::sealed::Sealed;
+
+/// Convert an iterator of str to a JS array of strings.
+fn strs_to_js_array<'a>(values: impl Iterator<Item = &'a str>) -> Array {
+    values.into_iter().map(JsValue::from_str).collect()
+}
+
+pub(crate) fn add_classes<'a>(list: &dom::DomTokenList, classes: impl Iterator<Item = &'a str>) {
+    list.add(&strs_to_js_array(classes)).unwrap_throw();
+}
+
+pub(crate) fn del_classes<'a>(list: &dom::DomTokenList, classes: impl Iterator<Item = &'a str>) {
+    list.remove(&strs_to_js_array(classes)).unwrap_throw();
+}
+
+/// The inline style of any element (HTML, SVG, ...).
+pub(crate) fn element_style(element: &dom::Element) -> dom::CssStyleDeclaration {
+    // `HtmlElement::style` only works on HTML elements, so look the property up instead.
+    js_sys::Reflect::get(element, &JsValue::from_str("style"))
+        .unwrap_throw()
+        .unchecked_into()
+}
+
+pub(crate) fn set_property(element: &dom::Element, name: &str, value: impl Sealed) {
+    js_sys::Reflect::set(element, &JsValue::from_str(name), &value.into_js_value()).unwrap_throw();
+}
+
+pub(crate) fn sleep(duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+    use async_ui_web_core::window::WINDOW;
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        WINDOW.with(|window| {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    &resolve,
+                    duration.as_millis().try_into().unwrap_or(i32::MAX),
+                )
+                .unwrap_throw();
+        });
+    });
+    let future = JsFuture::from(promise);
+    Box::pin(async move {
+        let _ = future.await;
+    })
+}
+
+/// Start a Web Animation on `element`. See `Element.animate()`.
+pub(crate) fn animate(element: &dom::Element, keyframes: &JsValue, options: &JsValue) {
+    call_method(element, "animate", &Array::of2(keyframes, options));
+}
+
+/// Futures for the animations running on `element` right now,
+/// each completing when its animation finishes (or is cancelled).
+pub(crate) fn running_animations(element: &dom::Element) -> Vec<Pin<Box<dyn Future<Output = ()>>>> {
+    // `getAnimations` also makes the browser pick up the classes we just added.
+    let Ok(animations) = call_method(element, "getAnimations", &Array::new()).dyn_into::<Array>()
+    else {
+        return Vec::new();
+    };
+    animations
+        .iter()
+        .filter_map(|animation| animation.unchecked_into::<Animation>().finished().ok())
+        .map(|finished| {
+            let finished = JsFuture::from(finished);
+            Box::pin(async move {
+                // rejects if cancelled; we're done either way
+                let _ = finished.await;
+            }) as Pin<Box<dyn Future<Output = ()>>>
+        })
+        .collect()
+}
+
+/// `Element.animate` and `Element.getAnimations` are not in stable web-sys yet,
+/// so we call them dynamically.
+fn call_method(target: &dom::Element, name: &str, args: &Array) -> JsValue {
+    Reflect::get(target, &name.into())
+        .ok()
+        .and_then(|method| method.dyn_into::<Function>().ok())
+        .and_then(|method| method.apply(target, args).ok())
+        .unwrap_or(JsValue::UNDEFINED)
+}
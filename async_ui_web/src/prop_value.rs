@@ -0,0 +1,43 @@
+use wasm_bindgen::JsValue;
+
+/// Values that can be given to [with_prop][crate::shortcut_traits::ShortcutAttributeBuilder::with_prop].
+///
+/// This trait is sealed: it is implemented for `bool`, `&str`, `String`,
+/// `f64`, `i32`, and `u32`, and can't be implemented outside this crate.
+pub trait PropValue: sealed::Sealed {}
+impl<T: sealed::Sealed> PropValue for T {}
+
+pub(crate) mod sealed {
+    use wasm_bindgen::JsValue;
+
+    pub trait Sealed {
+        fn into_js_value(self) -> JsValue;
+        /// `None` means the attribute should be removed.
+        fn into_attribute_value(self) -> Option<String>;
+    }
+}
+
+impl sealed::Sealed for bool {
+    fn into_js_value(self) -> JsValue {
+        JsValue::from_bool(self)
+    }
+    fn into_attribute_value(self) -> Option<String> {
+        self.then(String::new)
+    }
+}
+
+macro_rules! impl_prop_value {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {
+                fn into_js_value(self) -> JsValue {
+                    JsValue::from(self)
+                }
+                fn into_attribute_value(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+impl_prop_value!(&str, String, f64, i32, u32);
@@ -1,9 +1,14 @@
 use std::future::Pending;
 
-use async_ui_web_core::ContainerNodeFuture;
-use async_ui_web_html::nodes::Text;
-use js_sys::Array;
-use wasm_bindgen::{JsValue, UnwrapThrowExt};
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::{
+    html::Text,
+    platform::{
+        add_classes, del_classes, dom::Element, element_style, set_property, ContainerNodeFuture,
+    },
+};
+use crate::prop_value::PropValue;
 
 pub trait ShortcutRenderStr {
     /// Render the [str] as an HTML text node with that content.
@@ -44,28 +49,19 @@ pub trait ShortcutClassList {
     fn set_class(&self, c: &str, included: bool);
 }
 
-/// Convert an iterator of str to a JS array of strings.
-fn strs_to_js_array<'a>(values: impl Iterator<Item = &'a str>) -> Array {
-    values.into_iter().map(JsValue::from_str).collect()
-}
-
-impl ShortcutClassList for web_sys::Element {
+impl ShortcutClassList for Element {
     fn add_class(&self, c: &str) {
         self.class_list().add_1(c).unwrap_throw();
     }
     fn add_classes<'a>(&self, c: impl IntoIterator<Item = &'a str>) {
-        self.class_list()
-            .add(&strs_to_js_array(c.into_iter()))
-            .unwrap_throw();
+        add_classes(&self.class_list(), c.into_iter());
     }
 
     fn del_class(&self, c: &str) {
         self.class_list().remove_1(c).unwrap();
     }
     fn del_classes<'a>(&self, c: impl IntoIterator<Item = &'a str>) {
-        self.class_list()
-            .remove(&strs_to_js_array(c.into_iter()))
-            .unwrap_throw();
+        del_classes(&self.class_list(), c.into_iter());
     }
 
     fn set_class(&self, c: &str, included: bool) {
@@ -75,7 +71,7 @@ impl ShortcutClassList for web_sys::Element {
     }
 }
 
-pub trait ShortcutClassListBuilder: AsRef<web_sys::Element> {
+pub trait ShortcutClassListBuilder: AsRef<Element> {
     /// Add a classname to the element and return reference to the input.
     ///
     /// This is for writing the UI "declaratively".
@@ -105,4 +101,126 @@ pub trait ShortcutClassListBuilder: AsRef<web_sys::Element> {
         self
     }
 }
-impl<T: AsRef<web_sys::Element>> ShortcutClassListBuilder for T {}
+impl<T: AsRef<Element>> ShortcutClassListBuilder for T {}
+
+pub trait ShortcutAttributeBuilder: AsRef<Element> {
+    /// Set an attribute of the element and return reference to the input.
+    ///
+    /// This is for writing the UI "declaratively".
+    /// ```
+    /// # let _ = async {
+    /// # use async_ui_web::{html::Anchor, prelude_traits::*};
+    /// # let children = std::future::pending::<()>();
+    /// Anchor::new()
+    ///     .with_attr("href", "/about")
+    ///     .with_attr("target", "_blank")
+    ///     .render(children)
+    ///     .await;
+    /// # };
+    /// ```
+    fn with_attr(&self, name: &str, value: &str) -> &Self {
+        self.as_ref().set_attribute(name, value).unwrap_throw();
+        self
+    }
+    /// Add or remove a boolean attribute (like `disabled` or `hidden`)
+    /// and return reference to the input.
+    fn with_bool_attr(&self, name: &str, present: bool) -> &Self {
+        self.as_ref()
+            .toggle_attribute_with_force(name, present)
+            .unwrap_throw();
+        self
+    }
+    /// Set a `data-*` attribute and return reference to the input.
+    ///
+    /// `elem.with_data("user-id", "42")` sets `data-user-id="42"`.
+    fn with_data(&self, key: &str, value: &str) -> &Self {
+        self.with_attr(&format!("data-{key}"), value)
+    }
+    /// Set an `aria-*` attribute and return reference to the input.
+    ///
+    /// `elem.with_aria("label", "Close")` sets `aria-label="Close"`.
+    fn with_aria(&self, key: &str, value: &str) -> &Self {
+        self.with_attr(&format!("aria-{key}"), value)
+    }
+    /// Set an inline style property and return reference to the input.
+    ///
+    /// ```
+    /// # let _ = async {
+    /// # use async_ui_web::{html::Div, prelude_traits::*};
+    /// # let children = std::future::pending::<()>();
+    /// Div::new()
+    ///     .with_style("display", "flex")
+    ///     .with_style("--gap", "4px")
+    ///     .render(children)
+    ///     .await;
+    /// # };
+    /// ```
+    fn with_style(&self, property: &str, value: &str) -> &Self {
+        element_style(self.as_ref())
+            .set_property(property, value)
+            .unwrap_throw();
+        self
+    }
+    /// Set a JavaScript property of the element (not an attribute)
+    /// and return reference to the input.
+    ///
+    /// ```
+    /// # let _ = async {
+    /// # use async_ui_web::{html::Input, prelude_traits::*};
+    /// Input::new()
+    ///     .with_prop("value", "initial text")
+    ///     .with_prop("indeterminate", true)
+    ///     .render()
+    ///     .await;
+    /// # };
+    /// ```
+    ///
+    /// In server-side rendering (the `ssr` module), there are no JavaScript properties.
+    /// Properties that reflect an attribute (`id`, `className`, `hidden`,
+    /// `tabIndex`, ...) set that attribute instead (or add/remove it, for
+    /// `bool` values). Other properties (like `value` and `indeterminate`
+    /// above) do nothing, since the HTML can't show them.
+    ///
+    /// ```
+    /// # #[cfg(feature = "ssr")]
+    /// # {
+    /// # use async_ui_web::ssr::{html::Input, prelude_traits::*};
+    /// let input = Input::new();
+    /// input
+    ///     .with_prop("value", "initial text")
+    ///     .with_prop("className", "wide")
+    ///     .with_prop("disabled", true);
+    /// assert_eq!(input.to_html(), r#"<input class="wide" disabled>"#);
+    /// # }
+    /// ```
+    fn with_prop(&self, name: &str, value: impl PropValue) -> &Self {
+        set_property(self.as_ref(), name, value);
+        self
+    }
+
+    /// Set the `id` attribute and return reference to the input.
+    fn with_id(&self, id: &str) -> &Self {
+        self.with_attr("id", id)
+    }
+    /// Set the `title` attribute (the tooltip) and return reference to the input.
+    fn with_title(&self, title: &str) -> &Self {
+        self.with_attr("title", title)
+    }
+    /// Set the `role` attribute and return reference to the input.
+    fn with_role(&self, role: &str) -> &Self {
+        self.with_attr("role", role)
+    }
+    /// Set the `tabindex` attribute and return reference to the input.
+    fn with_tab_index(&self, index: i32) -> &Self {
+        self.with_attr("tabindex", &index.to_string())
+    }
+    /// Add or remove the `hidden` attribute and return reference to the input.
+    fn with_hidden(&self, hidden: bool) -> &Self {
+        self.with_bool_attr("hidden", hidden)
+    }
+    /// Add or remove the `disabled` attribute and return reference to the input.
+    fn with_disabled(&self, disabled: bool) -> &Self {
+        self.with_bool_attr("disabled", disabled)
+    }
+}
+impl<T: AsRef<Element>> ShortcutAttributeBuilder for T {}
@@ -0,0 +1,31 @@
+//! The components of [components][crate::components], for the in-memory DOM.
+//!
+//! Those that don't touch the DOM themselves are the same types.
+//! `Dropdown` needs a real browser, so it isn't here.
+
+// Shared with the crate root, compiled again for the in-memory DOM.
+#[allow(clippy::duplicate_mod)]
+#[path = "../components/keep_alive.rs"]
+mod keep_alive;
+#[allow(clippy::duplicate_mod)]
+#[path = "../components/offscreen.rs"]
+mod offscreen;
+#[allow(clippy::duplicate_mod)]
+#[path = "../components/portal.rs"]
+mod portal;
+#[allow(clippy::duplicate_mod)]
+#[path = "../components/suspense.rs"]
+mod suspense;
+#[allow(clippy::duplicate_mod)]
+#[path = "../components/transition.rs"]
+mod transition;
+
+#[cfg(feature = "x-bow")]
+pub use crate::components::PathSource;
+pub use crate::components::{DynamicSlot, Keyed, Match, Reactive, Show};
+#[cfg(panic = "unwind")]
+pub use crate::components::{ErrorBoundary, PanicPayload};
+pub use keep_alive::KeepAlive;
+pub use portal::Portal;
+pub use suspense::{loading, Loading, Suspense};
+pub use transition::{Transition, TransitionFuture};
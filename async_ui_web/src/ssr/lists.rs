@@ -0,0 +1,22 @@
+//! The lists of [lists][crate::lists], for the in-memory DOM.
+//!
+//! `VirtualizedList` needs a real browser, so it isn't here.
+
+// Shared with the crate root, compiled again for the in-memory DOM.
+#[allow(clippy::duplicate_mod)]
+#[path = "../lists/diffed_list.rs"]
+mod diffed_list;
+#[allow(clippy::duplicate_mod)]
+#[path = "../lists/dynamic_list.rs"]
+mod dynamic_list;
+#[allow(clippy::duplicate_mod)]
+#[path = "../lists/modeled_list.rs"]
+mod modeled_list;
+#[allow(clippy::duplicate_mod)]
+#[path = "../lists/render_stream.rs"]
+mod render_stream;
+
+pub use diffed_list::DiffedList;
+pub use dynamic_list::DynamicList;
+pub use modeled_list::{ListModel, ModeledList};
+pub use render_stream::{render_stream, RenderStream};
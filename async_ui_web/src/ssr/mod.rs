@@ -0,0 +1,139 @@
+/*!
+Server-side rendering.
+
+This module has the same components, lists, elements and traits as the crate
+root, but they render into an in-memory DOM instead of the browser's.
+Use [render_to_string] to turn your app into HTML.
+
+```
+# use async_ui_web::ssr::{html::Div, prelude_traits::*, render_to_string};
+async fn my_app() {
+    Div::new()
+        .with_class("greeting")
+        .render("Hello World".render())
+        .await;
+}
+let html = render_to_string(my_app());
+assert_eq!(html, r#"<div class="greeting">Hello World</div>"#);
+```
+
+The same in-memory DOM lets you test components natively with `cargo test`.
+Use [SsrRoot] to render step by step, and fire events at the nodes it rendered.
+
+```
+# use std::pin::pin;
+# use async_ui_web::{join, ssr::{dom::Event, html::{Button, Text}, prelude_traits::*, SsrRoot}};
+async fn counter() {
+    let button = Button::new();
+    let text = Text::new();
+    join((button.render(text.render()), async {
+        for count in 0.. {
+            text.set_data(&count.to_string());
+            button.until_click().await;
+        }
+    }))
+    .await;
+}
+let mut root = pin!(SsrRoot::new(counter()));
+root.as_mut().run_until_stalled();
+assert_eq!(root.to_html(), "<button>0</button>");
+
+let button = root.container().query_selector("button").unwrap().unwrap();
+button.dispatch_event(&Event::new("click").unwrap()).unwrap();
+root.as_mut().run_until_stalled();
+assert_eq!(root.to_html(), "<button>1</button>");
+```
+
+To render the same app in the browser and on the server, write it against
+one set of paths and switch between them, for example with a `use` behind a
+feature of your own.
+
+Only what exists in the in-memory DOM is available: element-specific
+APIs (such as those of [HtmlInputElement][web_sys::HtmlInputElement]) are
+replaced by the generic [HtmlElement][dom::HtmlElement], and components
+that need a real browser (`Dropdown`, `Transition`, `VirtualizedList`)
+aren't here.
+*/
+
+mod platform;
+mod root;
+// Shared with the crate root, compiled again for the in-memory DOM.
+#[allow(clippy::duplicate_mod)]
+#[path = "../shortcuts.rs"]
+mod shortcuts;
+
+pub mod components;
+pub mod lists;
+
+#[doc(inline)]
+pub use crate::__ssr_view as view;
+pub use async_ui_web_core::ssr::{dom, Ssr};
+pub use async_ui_web_html::ssr::nodes as html;
+pub use async_ui_web_html::ssr::svg;
+pub use root::{render_to_string, SsrRoot};
+
+/// Like [view!][crate::view], but with the elements of [html].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ssr_view {
+    ($($tokens:tt)*) => {
+        $crate::__private_macro_only::view! { in $crate::ssr; $($tokens)* }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private_macro_only {
+    #[doc(hidden)]
+    pub use async_ui_web_html::ssr::nodes::{tags, RenderView};
+
+    #[doc(hidden)]
+    pub fn set_attribute(element: &impl AsRef<super::dom::Element>, name: &str, value: &str) {
+        element.as_ref().set_attribute(name, value).unwrap();
+    }
+}
+
+pub mod event_handling {
+    /*!
+    Types used in event handling mechanism.
+    You shouldn't need to interact with this module directly often.
+    */
+    pub use async_ui_web_html::ssr::events::EventFutureStream;
+}
+
+pub mod event_traits {
+    /*!
+    Traits for event handling.
+    */
+    pub use async_ui_web_html::ssr::events::{
+        EmitElementEvent, EmitEvent, EmitHtmlElementEvent, EmitSvgElementEvent,
+    };
+}
+
+pub mod shortcut_traits {
+    /*!
+    Traits provided for convenience.
+     */
+    pub use super::shortcuts::{
+        ShortcutAttributeBuilder, ShortcutClassList, ShortcutClassListBuilder, ShortcutRenderStr,
+    };
+    pub use crate::prop_value::PropValue;
+    pub use async_ui_web_core::combinators::UiFutureExt;
+}
+
+pub mod prelude_traits {
+    /*!
+    Includes all traits from [event_traits][super::event_traits]
+    and [shortcut_traits][super::shortcut_traits].
+    ```
+    use async_ui_web::ssr::prelude_traits::*;
+    ```
+     */
+    pub use super::shortcuts::{
+        ShortcutAttributeBuilder as _, ShortcutClassList as _, ShortcutClassListBuilder as _,
+        ShortcutRenderStr as _,
+    };
+    pub use async_ui_web_core::combinators::UiFutureExt as _;
+    pub use async_ui_web_html::ssr::events::{
+        EmitElementEvent as _, EmitEvent as _, EmitHtmlElementEvent as _, EmitSvgElementEvent as _,
+    };
+}
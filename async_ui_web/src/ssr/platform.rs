@@ -0,0 +1,106 @@
+//! What the components, lists and shortcuts need from the in-memory DOM.
+//! The browser versions are in the crate's own `platform` module.
+
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+pub(crate) use async_ui_web_core::ssr::{
+    dom, leaving, ContainerNodeFuture, OffscreenRoot, PortalFuture, SiblingNodeFuture,
+};
+use wasm_bindgen::JsValue;
+
+use crate::prop_value::sealed::Sealed;
+
+pub(crate) fn add_classes<'a>(list: &dom::DomTokenList, classes: impl Iterator<Item = &'a str>) {
+    classes.for_each(|c| list.add_1(c).unwrap());
+}
+
+pub(crate) fn del_classes<'a>(list: &dom::DomTokenList, classes: impl Iterator<Item = &'a str>) {
+    classes.for_each(|c| list.remove_1(c).unwrap());
+}
+
+/// The inline style of any element (HTML, SVG, ...).
+pub(crate) fn element_style(element: &dom::Element) -> dom::CssStyleDeclaration {
+    element.clone().unchecked_into_html_element().style()
+}
+
+/// There are no JavaScript properties,
+/// so only set the attribute if the property reflects one.
+pub(crate) fn set_property(element: &dom::Element, name: &str, value: impl Sealed) {
+    let Some(attribute) = reflected_attribute(name) else {
+        return;
+    };
+    match value.into_attribute_value() {
+        Some(value) => element.set_attribute(attribute, &value).unwrap(),
+        None => {
+            element
+                .toggle_attribute_with_force(attribute, false)
+                .unwrap();
+        }
+    }
+}
+
+/// The attribute that a property reflects, for properties whose value is
+/// always the same as the attribute's.
+fn reflected_attribute(property: &str) -> Option<&'static str> {
+    Some(match property {
+        "id" => "id",
+        "className" => "class",
+        "title" => "title",
+        "lang" => "lang",
+        "dir" => "dir",
+        "hidden" => "hidden",
+        "tabIndex" => "tabindex",
+        "accessKey" => "accesskey",
+        "name" => "name",
+        "type" => "type",
+        "href" => "href",
+        "src" => "src",
+        "alt" => "alt",
+        "rel" => "rel",
+        "target" => "target",
+        "htmlFor" => "for",
+        "placeholder" => "placeholder",
+        "disabled" => "disabled",
+        "required" => "required",
+        "readOnly" => "readonly",
+        "multiple" => "multiple",
+        "autofocus" => "autofocus",
+        "open" => "open",
+        "min" => "min",
+        "max" => "max",
+        "step" => "step",
+        "pattern" => "pattern",
+        "width" => "width",
+        "height" => "height",
+        "colSpan" => "colspan",
+        "rowSpan" => "rowspan",
+        _ => return None,
+    })
+}
+
+pub(crate) fn sleep(duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+    // There are no timers; just check the time whenever we're polled.
+    let deadline = Instant::now() + duration;
+    Box::pin(poll_fn(move |_cx| {
+        if Instant::now() >= deadline {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }))
+}
+
+/// Nothing is animated in the in-memory DOM.
+pub(crate) fn animate(_element: &dom::Element, _keyframes: &JsValue, _options: &JsValue) {}
+
+/// Nothing is animated in the in-memory DOM, so there are never any running.
+pub(crate) fn running_animations(
+    _element: &dom::Element,
+) -> Vec<Pin<Box<dyn Future<Output = ()>>>> {
+    Vec::new()
+}
@@ -0,0 +1,77 @@
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+};
+
+use async_ui_web_core::ssr::dom;
+use pin_project::pin_project;
+
+use crate::executor::{Executors, WithExecutors};
+
+type CoreRoot<F> = async_ui_web_core::ssr::SsrRoot<WithExecutors<F>>;
+
+/// Render the given future to HTML.
+///
+/// The future is polled until it completes or nothing is left to do,
+/// then dropped. Returns the HTML of everything it rendered.
+///
+/// This is a shorthand for [SsrRoot::new], [SsrRoot::run_until_stalled],
+/// and [SsrRoot::to_html].
+pub fn render_to_string<F: Future>(future: F) -> String {
+    let mut root = pin!(SsrRoot::new(future));
+    root.as_mut().run_until_stalled();
+    root.to_html()
+}
+
+/// A future being rendered into the in-memory DOM.
+///
+/// Like a mounted [App][crate::App], each root has its own
+/// [executors][crate::executor], which run along with the future
+/// and are dropped with the root.
+///
+/// Use this instead of [render_to_string] to render in multiple steps,
+/// for example to wait for data between steps, or to fire events in tests.
+/// See [async_ui_web_core::ssr::SsrRoot] for the details.
+#[pin_project]
+pub struct SsrRoot<F: Future> {
+    #[pin]
+    inner: CoreRoot<F>,
+}
+
+impl<F: Future> SsrRoot<F> {
+    /// Create a root for rendering the given future.
+    /// The future is not polled until [run_until_stalled][Self::run_until_stalled].
+    pub fn new(future: F) -> Self {
+        Self::start(future, CoreRoot::new)
+    }
+    /// Like [new][Self::new], but take over the nodes already in `container`
+    /// instead of rendering after them. See [hydrate_at][crate::hydrate_at].
+    pub fn hydrate(future: F, container: dom::DocumentFragment) -> Self {
+        Self::start(future, |future| CoreRoot::hydrate(future, container))
+    }
+    fn start(future: F, make: impl FnOnce(WithExecutors<F>) -> CoreRoot<F>) -> Self {
+        let executors = Executors::new();
+        let inner = make(WithExecutors::new(future, executors.clone()));
+        inner.executor().set_future(Box::new(executors.run()));
+        Self { inner }
+    }
+    /// Poll the future (and tasks spawned on its executors) until nothing
+    /// is left to do or the future completes.
+    ///
+    /// Returns `true` if the future has completed.
+    pub fn run_until_stalled(self: Pin<&mut Self>) -> bool {
+        self.project().inner.run_until_stalled()
+    }
+    /// The output of the future, if it has completed.
+    pub fn take_output(self: Pin<&mut Self>) -> Option<F::Output> {
+        self.project().inner.take_output()
+    }
+    /// The node everything is rendered into.
+    pub fn container(&self) -> &dom::DocumentFragment {
+        self.inner.container()
+    }
+    /// Serialize what has been rendered so far to HTML.
+    pub fn to_html(&self) -> String {
+        self.inner.to_html()
+    }
+}
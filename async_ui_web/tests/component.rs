@@ -0,0 +1,46 @@
+#![cfg(feature = "ssr")]
+
+use std::{fmt::Display, future::Future};
+
+use async_ui_web::{
+    component, join,
+    ssr::{html::Div, prelude_traits::*, render_to_string},
+};
+
+#[component]
+async fn card<T: Display, C: Future<Output = ()>>(
+    title: T,
+    subtitle: Option<&str>,
+    #[prop(default = 2)] level: u8,
+    children: C,
+) {
+    let div = Div::new();
+    div.with_attr("data-level", &level.to_string());
+    div.render(join((
+        title.to_string().render(),
+        async {
+            if let Some(subtitle) = subtitle {
+                subtitle.render().await;
+            }
+        },
+        children,
+    )))
+    .await;
+}
+
+#[test]
+fn props_and_children() {
+    let html = render_to_string(
+        Card::props()
+            .title("Hello")
+            .subtitle("world")
+            .render("!".render()),
+    );
+    assert_eq!(html, r#"<div data-level="2">Helloworld!</div>"#);
+}
+
+#[test]
+fn setters_in_any_order() {
+    let html = render_to_string(Card::props().level(3).title(7).render(async {}));
+    assert_eq!(html, r#"<div data-level="3">7</div>"#);
+}
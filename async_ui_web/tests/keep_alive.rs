@@ -0,0 +1,80 @@
+#![cfg(feature = "ssr")]
+
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+};
+
+use async_ui_web::{
+    join,
+    ssr::{components::KeepAlive, html::Text, prelude_traits::*, SsrRoot},
+    ReactiveCell,
+};
+
+/// Count clicks (changes to `clicks`) into a text node, so that the
+/// count shows whether the branch kept its state.
+async fn counter(clicks: &ReactiveCell<u32>) {
+    let text = Text::new();
+    join((text.render(), async {
+        let mut count = 0;
+        loop {
+            text.set_data(&count.to_string());
+            clicks.until_change().await;
+            count += 1;
+        }
+    }))
+    .await;
+}
+
+#[test]
+fn hidden_state_survives() {
+    let clicks = ReactiveCell::new(0);
+    let tabs = KeepAlive::new();
+    tabs.insert(
+        "a",
+        Box::pin(counter(&clicks)) as Pin<Box<dyn Future<Output = ()>>>,
+    );
+    tabs.insert("b", Box::pin("b".render()));
+    tabs.show("a");
+    let mut root = pin!(SsrRoot::new(tabs.render()));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!----><!---->0<!---->");
+    tabs.show("b");
+    *clicks.borrow_mut() += 1;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!----><!---->b<!---->");
+    // the hidden branch kept running, and kept its state
+    tabs.show("a");
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!----><!---->1<!---->");
+
+    tabs.set_pause_hidden(true);
+    tabs.show("b");
+    *clicks.borrow_mut() += 1;
+    root.as_mut().run_until_stalled();
+    tabs.show("a");
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!----><!---->2<!---->");
+}
+
+#[test]
+fn drop_removes_all_branches() {
+    let tabs = KeepAlive::new();
+    tabs.insert("a", "a".render());
+    tabs.insert("b", "b".render());
+    tabs.show("a");
+    let mut root = Box::pin(SsrRoot::new(join((
+        "before".render(),
+        async {
+            // drop the KeepAlive along with its render future
+            let tabs = tabs;
+            tabs.render().await;
+        },
+        "after".render(),
+    ))));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "before<!----><!---->a<!---->after");
+    let container = root.container().clone();
+    drop(root);
+    assert_eq!(container.to_html(), "");
+}
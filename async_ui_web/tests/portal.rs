@@ -0,0 +1,91 @@
+#![cfg(feature = "ssr")]
+
+use std::pin::pin;
+
+use async_ui_web::{
+    join, race,
+    ssr::{
+        components::Portal,
+        dom::Node,
+        html::{Div, Span},
+        prelude_traits::*,
+        SsrRoot,
+    },
+    ReactiveCell,
+};
+
+/// A target for portals, outside of what the root renders.
+fn target() -> (Div, Portal) {
+    let target = Div::new();
+    let portal = Portal::new(AsRef::<Node>::as_ref(&target).clone());
+    (target, portal)
+}
+
+#[test]
+fn removed_with_parent_future() {
+    let gone = ReactiveCell::new(false);
+    let (target, portal) = target();
+    let parent = Div::new();
+    let mut root = pin!(SsrRoot::new(async {
+        race((
+            parent.render(portal.render("modal".render())),
+            gone.until_change(),
+        ))
+        .await
+    }));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<div></div>");
+    assert_eq!(target.to_html(), "<div>modal<!----></div>");
+
+    // the portal's content isn't inside `parent`, so it goes even though `parent` went first
+    *gone.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "");
+    assert_eq!(target.to_html(), "<div></div>");
+}
+
+#[test]
+fn nested_content_stays_while_blocked() {
+    let (target, portal) = target();
+    let (outer, inner) = (Div::new(), Span::new());
+    let mut root = Box::pin(SsrRoot::new(
+        portal.render(outer.render(inner.render("text".render()))),
+    ));
+    root.as_mut().run_until_stalled();
+    assert_eq!(
+        target.to_html(),
+        "<div><div><span>text</span></div><!----></div>"
+    );
+    drop(root);
+    // only the portal's own nodes are removed;
+    // what's inside them stays, like with any removed element
+    assert_eq!(target.to_html(), "<div></div>");
+    assert!(inner
+        .parent_node()
+        .is_some_and(|parent| parent.is_same_node(Some(&outer))));
+    assert_eq!(outer.to_html(), "<div><span>text</span></div>");
+}
+
+#[test]
+fn portals_keep_their_order() {
+    let more = ReactiveCell::new(false);
+    let (target, portal) = target();
+    target.append_child(&Span::new()).unwrap();
+    let mut root = pin!(SsrRoot::new(join((
+        portal.render(async {
+            race(("a".render(), more.until_change())).await;
+            join(("a".render(), "b".render())).await;
+        }),
+        portal.render("c".render()),
+    ))));
+    root.as_mut().run_until_stalled();
+    assert_eq!(target.to_html(), "<div><span></span>a<!---->c<!----></div>");
+
+    // what the first portal renders later still comes before the second portal
+    *more.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(
+        target.to_html(),
+        "<div><span></span>ab<!---->c<!----></div>"
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "ssr")]
+
+use std::{cell::Cell, future::Future, pin::pin};
+
+use async_ui_web::{
+    ssr::{html::Span, lists::render_stream, prelude_traits::*, render_to_string, SsrRoot},
+    ReactiveCell,
+};
+use futures_lite::{stream, StreamExt};
+
+/// Render `n` in a span, and note down when the future is dropped.
+fn item(n: u32, dropped: &Cell<u32>) -> impl Future<Output = ()> + '_ {
+    let guard = scopeguard::guard((), |_| dropped.set(dropped.get() + 1));
+    async move {
+        let _guard = guard;
+        Span::new().render(n.to_string().render()).await;
+    }
+}
+
+#[test]
+fn ordering() {
+    let dropped = Cell::new(0);
+    let html = render_to_string(render_stream(stream::iter(1..=3), |n| item(n, &dropped)));
+    assert_eq!(
+        html,
+        "<!----><!----><span>1</span><!----><span>2</span><!----><span>3</span><!----><!---->"
+    );
+}
+
+#[test]
+fn eviction() {
+    let dropped = Cell::new(0);
+    let more = ReactiveCell::new(false);
+    let items = stream::iter(1..=2).chain(
+        stream::once_future(async {
+            while !*more.borrow() {
+                more.until_change().await;
+            }
+        })
+        .flat_map(|()| stream::iter(3..=5)),
+    );
+    let mut root = pin!(SsrRoot::new(
+        render_stream(items, |n| item(n, &dropped)).with_max_len(2)
+    ));
+    root.as_mut().run_until_stalled();
+    assert_eq!(
+        root.to_html(),
+        "<!----><!----><span>1</span><!----><span>2</span><!----><!---->"
+    );
+    *more.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(
+        root.to_html(),
+        "<!----><!----><span>4</span><!----><span>5</span><!----><!---->"
+    );
+    assert_eq!(dropped.get(), 3);
+}
+
+#[test]
+fn item_outputs() {
+    let mut root = pin!(SsrRoot::new(async {
+        let mut doubled = pin!(render_stream(stream::iter(1..=3), |n| async move { n * 2 }));
+        let mut outputs = Vec::new();
+        while outputs.len() < 3 {
+            outputs.push(doubled.next().await.unwrap());
+        }
+        outputs
+    }));
+    assert!(root.as_mut().run_until_stalled());
+    assert_eq!(root.as_mut().take_output(), Some(vec![2, 4, 6]));
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "ssr")]
+
+use async_ui_web::{select, ssr::render_to_string};
+use futures_lite::{stream, StreamExt};
+
+#[test]
+fn unmatched_branches_are_disabled() {
+    enum Command {
+        Say(String),
+        Stop,
+    }
+    let mut commands = stream::iter([Command::Say("hi".into()), Command::Stop]);
+    let mut numbers = stream::iter([1, 0, 2]);
+    let mut log = Vec::new();
+    render_to_string(async {
+        loop {
+            select! {
+                Some(Command::Say(mut word)) = commands.next() => {
+                    word.push('!');
+                    log.push(word);
+                }
+                Some(n @ 1..) = numbers.next() => log.push(n.to_string()),
+                complete => break,
+            }
+        }
+    });
+    // `Command::Stop` and `0` didn't match, so those branches were disabled early.
+    log.sort();
+    assert_eq!(log, ["1", "hi!"]);
+}
+
+#[test]
+fn default_when_nothing_is_ready() {
+    let mut value = 0;
+    render_to_string(async {
+        value = select! {
+            x = std::future::pending::<i32>() => x,
+            default => -1,
+        };
+    });
+    assert_eq!(value, -1);
+}
+
+#[test]
+fn biased_takes_the_first_ready_branch() {
+    for _ in 0..10 {
+        let mut first = "";
+        render_to_string(async {
+            first = select! {
+                biased;
+                a = async { "a" } => a,
+                b = async { "b" } => b,
+            };
+        });
+        assert_eq!(first, "a");
+    }
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "ssr")]
+
+use std::{pin::pin, time::Duration};
+
+use async_ui_web::{
+    join,
+    ssr::{
+        components::{loading, Show, Suspense},
+        html::Span,
+        prelude_traits::*,
+        render_to_string, SsrRoot,
+    },
+    ReactiveCell,
+};
+use futures_lite::future::pending;
+
+/// Completes once `loaded` is set.
+async fn wait(loaded: &ReactiveCell<bool>) {
+    while !*loaded.borrow() {
+        loaded.until_change().await;
+    }
+}
+
+#[test]
+fn reveal() {
+    let loaded = ReactiveCell::new(false);
+    let suspense = Suspense::new();
+    let mut root = pin!(SsrRoot::new(suspense.render(
+        join((Span::new().render("a".render()), async {
+            loading(wait(&loaded)).await;
+            "b".render().await
+        },)),
+        "loading".render(),
+    )));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!---->loading");
+    *loaded.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<!----><!----><span>a</span>b<!---->");
+}
+
+#[test]
+fn timeout() {
+    let html = |timeout| {
+        let suspense = match timeout {
+            Some(timeout) => Suspense::new().with_timeout(timeout),
+            None => Suspense::new(),
+        };
+        render_to_string(suspense.render(
+            join(("a".render(), loading(pending::<()>()))),
+            "loading".render(),
+        ))
+    };
+    assert_eq!(html(None), "<!---->loading");
+    assert_eq!(html(Some(Duration::from_secs(60))), "<!---->loading");
+    // show what has loaded so far
+    assert_eq!(html(Some(Duration::ZERO)), "<!----><!---->a<!---->");
+}
+
+#[test]
+fn drop_while_revealed() {
+    let shown = ReactiveCell::new(true);
+    let mut root = pin!(SsrRoot::new(async {
+        join((
+            "before".render(),
+            Show::new(
+                &shown,
+                |&shown| shown,
+                || async {
+                    Suspense::new()
+                        .render(Span::new().render("content".render()), "loading".render())
+                        .await
+                },
+            )
+            .render(),
+            "after".render(),
+        ))
+        .await
+    }));
+    root.as_mut().run_until_stalled();
+    assert_eq!(
+        root.to_html(),
+        "before<!----><!----><span>content</span><!---->after"
+    );
+    *shown.borrow_mut() = false;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "beforeafter");
+}
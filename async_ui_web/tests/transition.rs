@@ -0,0 +1,85 @@
+#![cfg(feature = "ssr")]
+
+use std::pin::pin;
+
+use async_ui_web::{
+    join, race,
+    ssr::{
+        components::Transition,
+        html::{Div, Span},
+        prelude_traits::*,
+        SsrRoot,
+    },
+    ReactiveCell,
+};
+
+#[test]
+fn enter_class_comes_off() {
+    let div = Div::new();
+    let mut root = pin!(SsrRoot::new(
+        Transition::new(&div)
+            .with_enter_class("fade-in")
+            .with_leave_class("fade-out")
+            .render(div.render("hi".render()))
+    ));
+    root.as_mut().run_until_stalled();
+    // nothing animates here, so the enter class is gone as soon as it was added
+    assert_eq!(div.class_name(), "");
+    assert!(div.has_attribute("class"));
+}
+
+#[test]
+fn leave_class_then_removed() {
+    let gone = ReactiveCell::new(false);
+    let div = Div::new();
+    let mut root = pin!(SsrRoot::new(join((
+        "before".render(),
+        async {
+            race((
+                Transition::new(&div)
+                    .with_leave_class("fade-out")
+                    .render(div.render("toast".render())),
+                gone.until_change(),
+            ))
+            .await
+        },
+        "after".render(),
+    ))));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "before<div>toast</div>after");
+
+    *gone.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "beforeafter");
+    // the leave hook removed it, after adding the leave class
+    assert!(div.parent_node().is_none());
+    assert_eq!(div.class_name(), "fade-out");
+}
+
+#[test]
+fn no_leave_when_ancestor_removed() {
+    let gone = ReactiveCell::new(false);
+    let (outer, inner) = (Div::new(), Span::new());
+    let mut root = pin!(SsrRoot::new(async {
+        race((
+            outer.render(
+                Transition::new(&inner)
+                    .with_leave_class("fade-out")
+                    .render(inner.render("toast".render())),
+            ),
+            gone.until_change(),
+        ))
+        .await
+    }));
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "<div><span>toast</span></div>");
+
+    *gone.borrow_mut() = true;
+    root.as_mut().run_until_stalled();
+    assert_eq!(root.to_html(), "");
+    // the element went with its ancestor, without its leave hook running
+    assert!(inner
+        .parent_node()
+        .is_some_and(|parent| parent.is_same_node(Some(&outer))));
+    assert_eq!(inner.class_name(), "");
+}
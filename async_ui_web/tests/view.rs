@@ -0,0 +1,40 @@
+#![cfg(feature = "ssr")]
+
+use async_ui_web::ssr::{prelude_traits::*, render_to_string, view};
+
+#[test]
+fn elements_attributes_and_children() {
+    let name = String::from("world");
+    let html = render_to_string(view! {
+        div(class = "card primary", data-id = "1") {
+            h1 { "Hello " { name.render() } }
+            input(type = "text", disabled)
+            my-widget(title = "custom") { p { "text" } }
+        }
+    });
+    assert_eq!(
+        html,
+        concat!(
+            r#"<div class="card primary" data-id="1">"#,
+            "<h1>Hello world</h1>",
+            r#"<input type="text" disabled>"#,
+            r#"<my-widget title="custom"><p>text</p></my-widget>"#,
+            "</div>",
+        )
+    );
+}
+
+#[test]
+fn bound_elements() {
+    // bound elements can be used in all the futures
+    let html = render_to_string(view! {
+        span as label { "loading" }
+        {
+            async {
+                label.with_class("ready");
+                "done".render().await;
+            }
+        }
+    });
+    assert_eq!(html, r#"<span class="ready">loading</span>done"#);
+}
@@ -0,0 +1,59 @@
+use async_ui::Backend;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::{
+    dom::{Comment, Element, Node, Text},
+    hydration,
+    leaving::take_leave_hook,
+};
+
+/// The browser DOM as an Async UI [Backend].
+pub struct Web;
+
+impl Backend for Web {
+    type Node = Node;
+
+    fn insert_before(container: &Node, node: &Node, reference: Option<&Node>) {
+        // adopted nodes that are already in place aren't moved
+        if hydration::before_insert(container, node, reference) {
+            return;
+        }
+        container.insert_before(node, reference).unwrap_throw();
+    }
+    fn remove_child(container: &Node, node: &Node) {
+        if let Some(hook) = take_leave_hook(node) {
+            hook();
+        } else {
+            container.remove_child(node).unwrap_throw();
+        }
+    }
+    fn create_marker() -> Node {
+        Comment::new().unwrap_throw().into()
+    }
+    fn debug_log(message: &str, node: &Node) {
+        web_sys::console::warn_2(&message.into(), node.as_ref());
+    }
+}
+
+// Helpers for the modules shared with `ssr`, which can't use `JsCast` or name the backend.
+
+pub(crate) fn unchecked_into_element(node: Node) -> Element {
+    node.unchecked_into()
+}
+pub(crate) fn unchecked_into_text(node: Node) -> Text {
+    node.unchecked_into()
+}
+pub(crate) fn containing_node() -> Option<Node> {
+    async_ui::containing_node::<Web>()
+}
+pub(crate) fn attribute_names(element: &Element) -> Vec<String> {
+    element
+        .get_attribute_names()
+        .iter()
+        .filter_map(|name| name.as_string())
+        .collect()
+}
+#[cfg(debug_assertions)]
+pub(crate) fn debug_log(message: &str, node: &Node) {
+    Web::debug_log(message, node)
+}
@@ -0,0 +1,10 @@
+//! The DOM types that Async UI renders into in the browser.
+//!
+//! These are just re-exports from [web_sys].
+//! Server-side rendering uses the in-memory implementations from
+//! `ssr::dom` instead (with the `ssr` feature).
+
+pub use web_sys::{
+    Comment, CssStyleDeclaration, Document, DocumentFragment, DomTokenList, Element, Event,
+    EventTarget, HtmlElement, Node, SvgElement, Text,
+};
@@ -1,12 +1,21 @@
 //! A mini executor responsible for running the root future.
 //! The `async_ui_web` uses this to drive the actual executor that runs the app.
 //! If you want to spawn tasks, please use the executor from `async_ui_web`.
+//!
+//! Each mounted app gets its own [RootExecutor]; the free functions here act
+//! on whichever root is running at the time.
+//!
+//! Roots with [Scheduling::Manual] don't need an event loop at all:
+//! they only run when [run_now] is called. Server-side rendering uses these.
 
 use std::{
     cell::{Cell, RefCell},
+    collections::BTreeMap,
     future::Future,
     pin::Pin,
-    task::{Context, RawWaker, RawWakerVTable, Waker},
+    rc::{Rc, Weak},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    time::Duration,
 };
 
 use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
@@ -14,32 +23,290 @@ use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
 use crate::window::WINDOW;
 
 thread_local! {
-    static EXECUTOR: ExecutorSingleton = ExecutorSingleton::new();
+    static DEFAULT_ROOT: RootExecutor = RootExecutor::new_with(Scheduling::default(), None);
+    /// The roots that are running right now, innermost last.
+    static CURRENT_ROOTS: RefCell<Vec<RootExecutor>> = const { RefCell::new(Vec::new()) };
+    /// All live roots, so that wakers can find them by id.
+    static ROOTS: RefCell<BTreeMap<usize, Weak<RootInner>>> = const { RefCell::new(BTreeMap::new()) };
+    static NEXT_ROOT_ID: Cell<usize> = const { Cell::new(0) };
 }
-struct ExecutorSingleton {
+
+/// A mini executor that runs one root future.
+///
+/// Each root has its own scheduling state, so several apps on one page
+/// don't affect each other.
+/// The free functions in this module ([schedule], [run_now], ...) act on the
+/// root that is running right now, or the thread's default root if none is.
+///
+/// Clones refer to the same root. The root's future is dropped when the
+/// last clone is dropped, or when [take_future][Self::take_future] is called.
+#[derive(Clone)]
+pub struct RootExecutor {
+    inner: Rc<RootInner>,
+}
+
+struct RootInner {
+    id: usize,
     waker: Waker,
     future: RefCell<Option<Pin<Box<dyn Future<Output = ()>>>>>,
     scheduled: Cell<bool>,
     active: Cell<bool>,
-    run_closure: Closure<dyn Fn()>,
+    scheduling: Cell<Scheduling>,
+    /// In milliseconds.
+    time_budget: Cell<Option<f64>>,
+    run_start: Cell<f64>,
+    yielded: RefCell<Vec<Waker>>,
+    settled: RefCell<Vec<Box<dyn FnOnce()>>>,
 }
 
-impl ExecutorSingleton {
-    fn new() -> Self {
-        Self {
-            waker: root_waker(),
+impl RootExecutor {
+    /// Create a new root without a future.
+    ///
+    /// The root starts out with the [scheduling][set_scheduling] and
+    /// [time budget][set_time_budget] of the current root.
+    pub fn new() -> Self {
+        let (scheduling, time_budget) =
+            current_root(|root| (root.scheduling.get(), root.time_budget.get()));
+        Self::new_with(scheduling, time_budget)
+    }
+    fn new_with(scheduling: Scheduling, time_budget: Option<f64>) -> Self {
+        let id = NEXT_ROOT_ID.with(|next| next.replace(next.get() + 1));
+        let inner = Rc::new(RootInner {
+            id,
+            waker: root_waker(id),
             future: RefCell::new(None),
             scheduled: Cell::new(false),
             active: Cell::new(false),
-            run_closure: Closure::new(run_now),
+            scheduling: Cell::new(scheduling),
+            time_budget: Cell::new(time_budget),
+            run_start: Cell::new(0.0),
+            yielded: RefCell::new(Vec::new()),
+            settled: RefCell::new(Vec::new()),
+        });
+        ROOTS.with(|roots| roots.borrow_mut().insert(id, Rc::downgrade(&inner)));
+        Self { inner }
+    }
+    /// Get the root that is running right now,
+    /// or the thread's default root if none is.
+    pub fn current() -> Self {
+        CURRENT_ROOTS
+            .with(|current| current.borrow().last().cloned())
+            .unwrap_or_else(|| DEFAULT_ROOT.with(Clone::clone))
+    }
+    /// Set the future for this root to execute, dropping the previous one.
+    pub fn set_future(&self, future: Box<dyn Future<Output = ()>>) {
+        let old = self.inner.future.replace(Some(future.into()));
+        drop(old);
+    }
+    /// Remove the future from this root, so that it can be dropped.
+    ///
+    /// This can't be called while the root is polling its future.
+    pub fn take_future(&self) -> Option<Pin<Box<dyn Future<Output = ()>>>> {
+        self.inner.future.take()
+    }
+    /// Choose when this root polls its future after being woken.
+    /// See [set_scheduling].
+    pub fn set_scheduling(&self, scheduling: Scheduling) {
+        self.inner.scheduling.set(scheduling)
+    }
+    /// Limit how long this root keeps polling once it has been scheduled.
+    /// See [set_time_budget].
+    pub fn set_time_budget(&self, budget: Option<Duration>) {
+        self.inner
+            .time_budget
+            .set(budget.map(|budget| budget.as_secs_f64() * 1000.0))
+    }
+    /// Schedule this root to poll its future. See [schedule].
+    pub fn schedule(&self) {
+        self.inner.schedule()
+    }
+    /// Run this root immediately. See [run_now].
+    pub fn run_now(&self) {
+        // Without an event loop, every run is a later run.
+        let manual = self.inner.scheduling.get() == Scheduling::Manual;
+        self.run(false, manual)
+    }
+    /// Call `f` as if this root were running, so that [schedule], [yield_now]
+    /// and the other free functions act on this root.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        CURRENT_ROOTS.with(|current| current.borrow_mut().push(self.clone()));
+        let result = f();
+        CURRENT_ROOTS.with(|current| current.borrow_mut().pop());
+        result
+    }
+    /// Check whether this root has been woken but not yet polled.
+    pub fn is_scheduled(&self) -> bool {
+        self.inner.scheduled.get()
+    }
+    /// Call `callback` the next time this root finishes a run with nothing
+    /// left to do (nothing woken or yielded since it last polled its future).
+    pub fn on_settled(&self, callback: impl FnOnce() + 'static) {
+        self.inner.settled.borrow_mut().push(Box::new(callback));
+    }
+    /// `continuation` is set for runs queued by [RootInner::schedule_continuation].
+    /// Only those let things that yielded continue, so that yielding always
+    /// gives the browser a chance to handle input and paint.
+    fn run(&self, budgeted: bool, continuation: bool) {
+        let exe = &*self.inner;
+        let was_active = exe.active.replace(true);
+        if !was_active {
+            CURRENT_ROOTS.with(|current| current.borrow_mut().push(self.clone()));
+            if exe.time_budget.get().is_some() {
+                exe.run_start.set(now());
+            }
+            if continuation {
+                // things that yielded in a previous run get to continue now
+                let yielded = exe.yielded.take();
+                yielded.into_iter().for_each(Waker::wake);
+            }
+            while exe.scheduled.replace(false) {
+                let mut cx = Context::from_waker(&exe.waker);
+                if let Some(fu) = exe.future.borrow_mut().as_mut() {
+                    let _ = fu.as_mut().poll(&mut cx);
+                }
+                if budgeted && exe.should_yield() {
+                    break;
+                }
+            }
+            CURRENT_ROOTS.with(|current| current.borrow_mut().pop());
+            exe.active.set(false);
+            // continue later if we ran out of time or something yielded
+            if exe.scheduled.replace(false) || !exe.yielded.borrow().is_empty() {
+                exe.schedule_continuation();
+            } else {
+                let settled = exe.settled.take();
+                settled.into_iter().for_each(|callback| callback());
+            }
         }
     }
 }
 
-/// Set the root future to execute.
+impl Default for RootExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RootInner {
+    fn drop(&mut self) {
+        // The default root is dropped when the thread exits,
+        // possibly after the registry.
+        let _ = ROOTS.try_with(|roots| roots.borrow_mut().remove(&self.id));
+    }
+}
+
+fn current_root<R>(f: impl FnOnce(&RootInner) -> R) -> R {
+    f(&RootExecutor::current().inner)
+}
+
+/// When the executor polls its future after being woken.
+///
+/// Set this with [set_scheduling].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scheduling {
+    /// Run in a microtask (`queueMicrotask`), right after the current JavaScript
+    /// finishes. Updates are applied as soon as possible,
+    /// but an app that keeps waking itself up can block the page.
+    ///
+    /// Work continued after the [time budget][set_time_budget] runs out or
+    /// after [yield_now] still runs in a new task (`setTimeout`), since the
+    /// browser can't handle input or paint between microtasks.
+    Microtask,
+    /// Run right before the next repaint (`requestAnimationFrame`).
+    /// Updates from many wakes are batched and line up with frames,
+    /// but don't happen at all while the page is in the background.
+    AnimationFrame,
+    /// Run in a new task (`setTimeout`).
+    /// Browsers may add a delay of a few milliseconds.
+    #[default]
+    Timeout,
+    /// Never run on its own; only when [run_now] is called.
+    /// This doesn't need a browser, so it is what server-side rendering uses.
+    Manual,
+}
+
+/// Choose when the executor polls its future after being woken.
+/// Call this before mounting your app.
+///
+/// The default is [Scheduling::Timeout].
+pub fn set_scheduling(scheduling: Scheduling) {
+    RootExecutor::current().set_scheduling(scheduling)
+}
+
+/// Set the future for the thread's default root to execute.
 /// We usually call this with future returned from `async_executor::LocalExecutor::run(...)`.
 pub fn set_executor_future(future: Box<dyn Future<Output = ()>>) {
-    EXECUTOR.with(|exe| *exe.future.borrow_mut() = Some(future.into()))
+    DEFAULT_ROOT.with(|root| root.set_future(future))
+}
+
+/// Limit how long the executor keeps polling once it has been scheduled.
+///
+/// When the budget runs out, the executor stops and schedules itself to
+/// continue later, giving the browser a chance to handle input and paint.
+/// It continues in a new task (`setTimeout`), or in the next animation frame
+/// with [Scheduling::AnimationFrame]; never in a microtask.
+/// Work is only interrupted between polls of the root future; a single
+/// long-running poll can't be cut short. Use [should_yield] and [yield_now]
+/// to split up long-running work.
+///
+/// [run_now] is not limited by the budget.
+///
+/// The default is `None` (no limit).
+/// Something around 8 milliseconds keeps animations smooth.
+pub fn set_time_budget(budget: Option<Duration>) {
+    RootExecutor::current().set_time_budget(budget)
+}
+
+/// Whether the current run of the executor has used up its [time budget][set_time_budget].
+/// If so, long-running work should [yield_now].
+pub fn should_yield() -> bool {
+    current_root(RootInner::should_yield)
+}
+
+/// Let the browser handle input and paint before continuing.
+///
+/// The returned future completes in a later run of the executor that is
+/// queued as a new task (`setTimeout`), or as an animation frame with
+/// [Scheduling::AnimationFrame]; never in the current run or a microtask.
+///
+/// ```
+/// # use async_ui_web_core::executor::{should_yield, yield_now};
+/// # fn process(_item: u32) {}
+/// # let _ = async {
+/// for item in 0..10_000 {
+///     process(item);
+///     if should_yield() {
+///         yield_now().await;
+///     }
+/// }
+/// # };
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Future returned by [yield_now].
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        current_root(|exe| {
+            exe.yielded.borrow_mut().push(cx.waker().clone());
+            // if we're in the middle of a run, the run will schedule the next one
+            if !exe.active.get() {
+                exe.schedule_continuation();
+            }
+        });
+        Poll::Pending
+    }
 }
 
 /// Run the root executor immediately.
@@ -48,42 +315,93 @@ pub fn set_executor_future(future: Box<dyn Future<Output = ()>>) {
 /// But by then `.preventDefault()` no longer works.
 /// So our event handlers call `run_now` to let the Rust app handle events immediately.
 pub fn run_now() {
-    EXECUTOR.with(|exe| {
-        let was_active = exe.active.replace(true);
-        if !was_active {
-            while exe.scheduled.replace(false) {
-                let mut cx = Context::from_waker(&exe.waker);
-                if let Some(fu) = exe.future.borrow_mut().as_mut() {
-                    let _ = fu.as_mut().poll(&mut cx);
-                }
-            }
-            exe.active.set(false);
-        }
-    })
+    RootExecutor::current().run_now()
+}
+
+fn now() -> f64 {
+    js_sys::Date::now()
 }
 
 /// Schedule the executor to poll its future.
 /// Does nothing if already scheduled.
-/// If not already scheduled, the executor will queue itself to run in the next microtask.
+/// If not already scheduled, the executor will queue itself to run
+/// according to the [Scheduling] set.
 pub fn schedule() {
-    EXECUTOR.with(|exe| {
-        if !exe.scheduled.replace(true) && !exe.active.get() {
-            WINDOW.with(|window| {
+    current_root(RootInner::schedule)
+}
+
+/// Check whether the executor has been woken but not yet polled.
+pub fn is_scheduled() -> bool {
+    current_root(|exe| exe.scheduled.get())
+}
+
+impl RootInner {
+    fn schedule(&self) {
+        self.schedule_as(self.scheduling.get(), false)
+    }
+    /// Schedule a run that continues work cut short by the time budget or
+    /// by [yield_now]. This is never a microtask.
+    fn schedule_continuation(&self) {
+        let scheduling = match self.scheduling.get() {
+            Scheduling::Microtask => Scheduling::Timeout,
+            scheduling => scheduling,
+        };
+        self.schedule_as(scheduling, true)
+    }
+    fn schedule_as(&self, scheduling: Scheduling, continuation: bool) {
+        if self.scheduled.replace(true) || self.active.get() || scheduling == Scheduling::Manual {
+            return;
+        }
+        // A fresh closure every time, so that pending callbacks
+        // don't keep an unmounted root alive.
+        let id = self.id;
+        // Any run outside a microtask comes after the browser had its turn.
+        let continuation = continuation || scheduling != Scheduling::Microtask;
+        let callback = Closure::once_into_js(move || {
+            if let Some(inner) = find_root(id) {
+                RootExecutor { inner }.run(true, continuation);
+            }
+        });
+        let callback = callback.unchecked_ref();
+        WINDOW.with(|window| match scheduling {
+            Scheduling::Microtask => window.queue_microtask(callback),
+            Scheduling::AnimationFrame => {
                 window
-                    .set_timeout_with_callback(exe.run_closure.as_ref().unchecked_ref())
+                    .request_animation_frame(callback)
                     .expect_throw("failed to schedule task");
-            })
-        }
-    })
+            }
+            Scheduling::Timeout => {
+                window
+                    .set_timeout_with_callback(callback)
+                    .expect_throw("failed to schedule task");
+            }
+            Scheduling::Manual => unreachable!(),
+        })
+    }
+    fn should_yield(&self) -> bool {
+        self.active.get()
+            && self
+                .time_budget
+                .get()
+                .is_some_and(|budget| now() - self.run_start.get() > budget)
+    }
 }
 
-/// A waker for the root future. Calls `schedule()` when woke.
-pub(crate) fn root_waker() -> Waker {
-    fn new_raw_waker() -> RawWaker {
-        RawWaker::new(
-            core::ptr::null::<()>(),
-            &RawWakerVTable::new(|_| new_raw_waker(), |_| schedule(), |_| schedule(), |_| {}),
-        )
+fn find_root(id: usize) -> Option<Rc<RootInner>> {
+    ROOTS.with(|roots| roots.borrow().get(&id).and_then(Weak::upgrade))
+}
+
+/// A waker for a root future. Schedules the root when woken.
+///
+/// It holds the root's id rather than the root itself,
+/// so that it can be sent around like any other waker.
+fn root_waker(id: usize) -> Waker {
+    fn wake(data: *const ()) {
+        if let Some(root) = find_root(data as usize) {
+            root.schedule();
+        }
     }
-    unsafe { Waker::from_raw(new_raw_waker()) }
+    const VTABLE: RawWakerVTable =
+        RawWakerVTable::new(|data| RawWaker::new(data, &VTABLE), wake, wake, |_| {});
+    unsafe { Waker::from_raw(RawWaker::new(id as *const (), &VTABLE)) }
 }
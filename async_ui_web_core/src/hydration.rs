@@ -0,0 +1,611 @@
+//! Taking over existing nodes (for example, HTML from server-side rendering)
+//! instead of creating new ones.
+//!
+//! Components create their nodes before rendering them, and keep handles to
+//! them. So hydration happens when nodes are created: while a [Hydration] is
+//! [entered][Hydration::enter], [create_element], [create_element_ns] and
+//! [create_text_node] return existing nodes from under the hydration root
+//! instead of new ones. The component then holds the very node that is
+//! already on the page, so images and iframes don't reload, and focus and
+//! input state are kept.
+//!
+//! Existing nodes are handed out per container. Each container (the node that
+//! the code creating the node renders into, or the hydration root outside of
+//! rendering) walks through its existing descendants in document order, which
+//! is the order its children are rendered in. A node created there takes the
+//! next existing node with the same tag name, so a missing or extra node only
+//! affects its own container. Whitespace-only text, comments, and the text
+//! inside `<script>` and `<style>` are never handed out.
+//!
+//! An adopted element starts without the attributes it had, so that it ends up
+//! with exactly the attributes (and classes) the app sets. An adopted text node
+//! keeps its text unless the app creates it with different text.
+//!
+//! An adopted node that is rendered where it already is stays where it is.
+//! Otherwise (for example, if nodes were created in a different order than
+//! they appear, or inside lists, which place their items between their own
+//! markers) it is moved like a new node would be.
+//!
+//! When the hydration [finishes][Hydration::finish], existing nodes that
+//! weren't rendered, and don't contain anything that was, are removed.
+//! In debug builds, mismatches between the existing nodes and what the app
+//! rendered are reported: new nodes rendered where an existing node was,
+//! text and attributes that the app changed, and removed elements and text.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+use wasm_bindgen::UnwrapThrowExt;
+
+use super::{
+    backend::{attribute_names, containing_node, unchecked_into_element, unchecked_into_text},
+    dom::{Element, Node, Text},
+    node_map::NodeMap,
+    window::DOCUMENT,
+};
+
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+const TEXT_NODE: u16 = 3;
+/// How far to look for the reference node when checking whether an adopted
+/// node is already in place. Beyond this, the node is just moved.
+const LOOKAHEAD: usize = 32;
+
+thread_local! {
+    /// The hydration that nodes created right now take existing nodes from.
+    static CURRENT: RefCell<Option<Hydration>> = const { RefCell::new(None) };
+    /// Hydrations that haven't finished.
+    static ACTIVE: RefCell<Vec<Weak<Inner>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Existing nodes for an app to take over.
+/// See the [module documentation][self].
+///
+/// Clones refer to the same hydration.
+#[derive(Clone)]
+pub struct Hydration {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    root: Node,
+    /// The existing nodes, in document order.
+    nodes: RefCell<Vec<Node>>,
+    existing: RefCell<NodeMap<Existing>>,
+    /// Positions in `nodes` of the existing nodes not adopted yet, by node name.
+    available: RefCell<HashMap<String, BTreeSet<usize>>>,
+    /// For each container, the position in `nodes` to look for its next node from.
+    cursors: RefCell<NodeMap<usize>>,
+    /// For each container, the first of the unrendered existing nodes at its
+    /// end. Lets nodes rendered in document order be checked without walking
+    /// over all the nodes after them.
+    trailing: RefCell<NodeMap<Node>>,
+    finished: Cell<bool>,
+}
+
+struct Existing {
+    /// Position in `nodes`.
+    index: usize,
+    /// Position in `nodes` right after the node's descendants.
+    end: usize,
+    rendered: bool,
+    /// What the node had before it was adopted. `None` if it wasn't.
+    server: Option<Server>,
+}
+
+/// The content of an adopted node before the app took it over.
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+enum Server {
+    Element { attributes: Vec<(String, String)> },
+    Text { data: String },
+}
+
+impl Hydration {
+    /// Prepare to take over the existing descendants of `root`.
+    pub fn new(root: &Node) -> Self {
+        let mut descendants = Vec::new();
+        collect_descendants(root, &mut descendants);
+        let mut nodes = Vec::with_capacity(descendants.len());
+        let mut existing = NodeMap::new();
+        let mut available = HashMap::<_, BTreeSet<_>>::new();
+        for (index, (node, end)) in descendants.into_iter().enumerate() {
+            existing.insert(
+                &node,
+                Existing {
+                    index,
+                    end,
+                    rendered: false,
+                    server: None,
+                },
+            );
+            if is_adoptable(&node) {
+                available.entry(node.node_name()).or_default().insert(index);
+            }
+            nodes.push(node);
+        }
+        let inner = Rc::new(Inner {
+            root: root.clone(),
+            nodes: RefCell::new(nodes),
+            existing: RefCell::new(existing),
+            available: RefCell::new(available),
+            cursors: RefCell::new(NodeMap::new()),
+            trailing: RefCell::new(NodeMap::new()),
+            finished: Cell::new(false),
+        });
+        ACTIVE.with(|active| active.borrow_mut().push(Rc::downgrade(&inner)));
+        Self { inner }
+    }
+    /// Run `f`, letting the nodes it creates take over existing nodes.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Hydration>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                let _ = CURRENT.try_with(|current| *current.borrow_mut() = previous);
+            }
+        }
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+    /// Wrap `future` so that it is always polled [entered][Self::enter].
+    pub fn wrap<F: Future>(&self, future: F) -> Hydrating<F> {
+        Hydrating {
+            hydration: self.clone(),
+            future,
+        }
+    }
+    /// Stop handing out existing nodes, and remove the ones that weren't
+    /// rendered (unless something rendered is inside them).
+    ///
+    /// Call this once the app has rendered. Calling it again does nothing.
+    pub fn finish(&self) {
+        let inner = &*self.inner;
+        if inner.finished.replace(true) {
+            return;
+        }
+        ACTIVE.with(|active| {
+            active
+                .borrow_mut()
+                .retain(|other| !Weak::ptr_eq(other, &Rc::downgrade(&self.inner)))
+        });
+        inner.available.take();
+        inner.cursors.replace(NodeMap::new());
+        inner.trailing.replace(NodeMap::new());
+        let existing = inner.existing.replace(NodeMap::new());
+        let nodes = inner.nodes.take();
+
+        // Children come after their parent, so go backward to decide children first.
+        let mut keep = vec![false; nodes.len()];
+        for (index, node) in nodes.iter().enumerate().rev() {
+            keep[index] = existing.get(node).is_some_and(|e| e.rendered) || {
+                let mut child = node.first_child();
+                let mut has_kept_child = false;
+                while let Some(current) = child {
+                    // Nodes we don't know about were added by the app.
+                    if existing.get(&current).is_none_or(|e| keep[e.index]) {
+                        has_kept_child = true;
+                        break;
+                    }
+                    child = current.next_sibling();
+                }
+                has_kept_child
+            };
+        }
+        // Go forward so that only the outermost removed nodes are removed and reported.
+        for (node, keep) in nodes.iter().zip(keep) {
+            if keep {
+                if let Some(server) = existing.get(node).and_then(|e| e.server.as_ref()) {
+                    report_changed(node, server);
+                }
+                continue;
+            }
+            if !inner.root.contains(Some(node)) {
+                continue;
+            }
+            if let Some(parent) = node.parent_node() {
+                report_removed(node);
+                parent.remove_child(node).unwrap_throw();
+            }
+        }
+    }
+}
+
+impl Inner {
+    /// Take the next available existing node with the given node name
+    /// that is inside `container`.
+    fn adopt(&self, container: &Node, node_name: &str) -> Option<Node> {
+        let mut existing = self.existing.borrow_mut();
+        let (start, end) = if container.is_same_node(Some(&self.root)) {
+            (0, self.nodes.borrow().len())
+        } else {
+            // new containers don't have anything to take over
+            let container = existing.get(container)?;
+            (container.index + 1, container.end)
+        };
+        let mut cursors = self.cursors.borrow_mut();
+        let cursor = cursors.get(container).copied().unwrap_or(start);
+        let mut available = self.available.borrow_mut();
+        let indices = available.get_mut(node_name)?;
+        // Nodes created out of order can still take over the nodes before the cursor.
+        let index = indices
+            .range(cursor..end)
+            .next()
+            .or_else(|| indices.range(start..cursor).next())
+            .copied()?;
+        indices.remove(&index);
+        cursors.insert(container, index + 1);
+        let node = self.nodes.borrow()[index].clone();
+        existing.get_mut(&node).unwrap().server = Some(Server::take(&node));
+        Some(node)
+    }
+    fn is_unrendered(&self, node: &Node) -> bool {
+        self.existing
+            .borrow()
+            .get(node)
+            .is_some_and(|existing| !existing.rendered)
+    }
+    /// `node` (one of ours) is being rendered in `container` before `reference`.
+    /// Returns whether it is already there.
+    fn render(&self, container: &Node, node: &Node, reference: Option<&Node>) -> bool {
+        if !self.existing.borrow().contains(node) {
+            // a new node in one of our containers
+            #[cfg(debug_assertions)]
+            if is_adoptable(node) {
+                if let Some(replaced) = self.available_before(container, reference) {
+                    report_replaced(node, &replaced);
+                }
+            }
+            return false;
+        }
+        let is_child = node
+            .parent_node()
+            .is_some_and(|parent| parent.is_same_node(Some(container)));
+        let in_place = self.is_unrendered(node)
+            && is_child
+            && match reference {
+                None => self.is_trailing(container, node),
+                Some(reference) => self.is_followed_by(node, reference),
+            };
+        if let Some(existing) = self.existing.borrow_mut().get_mut(node) {
+            existing.rendered = true;
+        }
+        let mut trailing = self.trailing.borrow_mut();
+        if in_place {
+            // everything after `node` is still unrendered
+            match node.next_sibling() {
+                Some(next) if reference.is_none() => trailing.insert(container, next),
+                None => trailing.remove(container),
+                _ => None,
+            };
+        } else if let Some(parent) = node.parent_node() {
+            // `node` is about to be moved out of the run it may have started
+            let starts_run = trailing
+                .get(&parent)
+                .is_some_and(|start| start.is_same_node(Some(node)));
+            if starts_run {
+                match node.next_sibling() {
+                    Some(next) => trailing.insert(&parent, next),
+                    None => trailing.remove(&parent),
+                };
+            }
+        }
+        in_place
+    }
+    /// Whether everything after `node` (a child of `container`) is unrendered.
+    fn is_trailing(&self, container: &Node, node: &Node) -> bool {
+        let Some(last) = container.last_child() else {
+            return false;
+        };
+        if !self.is_unrendered(&last) {
+            return false;
+        }
+        let cached = self
+            .trailing
+            .borrow()
+            .get(container)
+            .filter(|start| {
+                start
+                    .parent_node()
+                    .is_some_and(|parent| parent.is_same_node(Some(container)))
+            })
+            .cloned();
+        let start = cached.unwrap_or_else(|| {
+            let mut start = last;
+            while let Some(previous) = start.previous_sibling() {
+                if !self.is_unrendered(&previous) {
+                    break;
+                }
+                start = previous;
+            }
+            self.trailing.borrow_mut().insert(container, start.clone());
+            start
+        });
+        // `node` is in the run if we get to its start without leaving the run
+        let mut current = node.clone();
+        loop {
+            if current.is_same_node(Some(&start)) {
+                return true;
+            }
+            match current.previous_sibling() {
+                Some(previous) if self.is_unrendered(&previous) => current = previous,
+                _ => return false,
+            }
+        }
+    }
+    /// The first node that could still be adopted in the unrendered nodes right
+    /// before `reference` in `container`: the node that a new node rendered
+    /// there probably should have been.
+    #[cfg(debug_assertions)]
+    fn available_before(&self, container: &Node, reference: Option<&Node>) -> Option<Node> {
+        let mut run = Vec::new();
+        let mut previous = match reference {
+            Some(reference) => reference.previous_sibling(),
+            None => container.last_child(),
+        };
+        while let Some(current) = previous {
+            if run.len() == LOOKAHEAD || !self.is_unrendered(&current) {
+                break;
+            }
+            previous = current.previous_sibling();
+            run.push(current);
+        }
+        let existing = self.existing.borrow();
+        run.into_iter().rev().find(|node| {
+            is_adoptable(node) && existing.get(node).is_some_and(|e| e.server.is_none())
+        })
+    }
+    /// Whether `reference` comes after `node`, with only unrendered nodes in between.
+    fn is_followed_by(&self, node: &Node, reference: &Node) -> bool {
+        let mut next = node.next_sibling();
+        for _ in 0..LOOKAHEAD {
+            match next {
+                Some(current) if current.is_same_node(Some(reference)) => return true,
+                Some(current) if self.is_unrendered(&current) => next = current.next_sibling(),
+                _ => return false,
+            }
+        }
+        false
+    }
+}
+
+impl Server {
+    /// Record what `node` has, and clear its attributes for the app to set.
+    fn take(node: &Node) -> Self {
+        if node.node_type() == TEXT_NODE {
+            return Self::Text {
+                data: node.text_content().unwrap_or_default(),
+            };
+        }
+        let element = unchecked_into_element(node.clone());
+        let attributes = attribute_names(&element)
+            .into_iter()
+            .map(|name| {
+                let value = element.get_attribute(&name).unwrap_or_default();
+                element.remove_attribute(&name).unwrap_throw();
+                (name, value)
+            })
+            .collect();
+        Self::Element { attributes }
+    }
+}
+
+/// Future for [Hydration::wrap].
+#[pin_project]
+pub struct Hydrating<F> {
+    hydration: Hydration,
+    #[pin]
+    future: F,
+}
+
+impl<F: Future> Future for Hydrating<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.hydration.enter(|| this.future.poll(cx))
+    }
+}
+
+/// Take the next available existing node with the given node name
+/// in the container that the current code renders into.
+fn adopt(node_name: &str) -> Option<Node> {
+    CURRENT.with(|current| {
+        let current = current.borrow();
+        let inner = &current.as_ref()?.inner;
+        if inner.finished.get() {
+            return None;
+        }
+        let container = containing_node().unwrap_or_else(|| inner.root.clone());
+        inner.adopt(&container, node_name)
+    })
+}
+
+/// Create an HTML element, or adopt an existing one if hydrating.
+/// Like [create_element][web_sys::Document::create_element].
+pub fn create_element(local_name: &str) -> Element {
+    match adopt(&local_name.to_ascii_uppercase()) {
+        Some(node) => unchecked_into_element(node),
+        None => DOCUMENT.with(|doc| doc.create_element(local_name).unwrap_throw()),
+    }
+}
+
+/// Create an element in the given namespace, or adopt an existing one if hydrating.
+/// Like [create_element_ns][web_sys::Document::create_element_ns].
+pub fn create_element_ns(namespace: Option<&str>, qualified_name: &str) -> Element {
+    // Only HTML elements have their names uppercased.
+    let node_name = if namespace == Some(HTML_NAMESPACE) {
+        qualified_name.to_ascii_uppercase()
+    } else {
+        qualified_name.to_owned()
+    };
+    match adopt(&node_name) {
+        Some(node) => unchecked_into_element(node),
+        None => DOCUMENT.with(|doc| {
+            doc.create_element_ns(namespace, qualified_name)
+                .unwrap_throw()
+        }),
+    }
+}
+
+/// Create a text node, or adopt an existing one if hydrating.
+/// Like [create_text_node][web_sys::Document::create_text_node].
+///
+/// An adopted text node keeps its text if `data` is empty,
+/// since text nodes are usually created empty and filled in later.
+/// Otherwise, it gets `data` (reported as a mismatch in debug builds, if different).
+pub fn create_text_node(data: &str) -> Text {
+    match adopt("#text") {
+        Some(node) => {
+            let text = unchecked_into_text(node);
+            if !data.is_empty() && text.data() != data {
+                text.set_data(data);
+            }
+            text
+        }
+        None => DOCUMENT.with(|doc| doc.create_text_node(data)),
+    }
+}
+
+/// Called by the backend before inserting `node`.
+/// Returns whether `node` is an adopted node that is already in place,
+/// so that inserting it can be skipped.
+pub(crate) fn before_insert(container: &Node, node: &Node, reference: Option<&Node>) -> bool {
+    ACTIVE.with(|active| {
+        let mut active = active.borrow_mut();
+        if active.is_empty() {
+            return false;
+        }
+        active.retain(|inner| inner.strong_count() > 0);
+        active
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|inner| {
+                let existing = inner.existing.borrow();
+                existing.contains(node)
+                    || existing.contains(container)
+                    || inner.root.is_same_node(Some(container))
+            })
+            .is_some_and(|inner| inner.render(container, node, reference))
+    })
+}
+
+/// Add the descendants of `parent` to `nodes` in document order,
+/// each with the length of `nodes` after its own descendants.
+fn collect_descendants(parent: &Node, nodes: &mut Vec<(Node, usize)>) {
+    let mut child = parent.first_child();
+    while let Some(node) = child {
+        let index = nodes.len();
+        nodes.push((node.clone(), 0));
+        collect_descendants(&node, nodes);
+        nodes[index].1 = nodes.len();
+        child = node.next_sibling();
+    }
+}
+
+fn is_adoptable(node: &Node) -> bool {
+    match node.node_type() {
+        TEXT_NODE => {
+            !is_whitespace(node)
+                && !node
+                    .parent_node()
+                    .is_some_and(|parent| matches!(&*parent.node_name(), "SCRIPT" | "STYLE"))
+        }
+        _ => node.node_name() != "#comment",
+    }
+}
+
+fn is_whitespace(node: &Node) -> bool {
+    node.node_type() == TEXT_NODE
+        && node
+            .text_content()
+            .is_some_and(|text| text.trim().is_empty())
+}
+
+#[cfg(debug_assertions)]
+fn report_removed(node: &Node) {
+    if node.node_name() != "#comment" && !is_whitespace(node) {
+        super::backend::debug_log(
+            "Hydration mismatch. This existing node wasn't rendered by the app, so it was removed:",
+            node,
+        );
+    }
+}
+#[cfg(not(debug_assertions))]
+fn report_removed(_node: &Node) {}
+
+#[cfg(debug_assertions)]
+fn report_replaced(node: &Node, replaced: &Node) {
+    super::backend::debug_log(
+        &format!(
+            "Hydration mismatch. The app rendered a new {} node where this existing node is:",
+            node.node_name()
+        ),
+        replaced,
+    );
+}
+
+/// Report how the app changed an adopted node.
+#[cfg(debug_assertions)]
+fn report_changed(node: &Node, server: &Server) {
+    match server {
+        Server::Text { data } => {
+            if node.text_content().as_deref() != Some(data) {
+                super::backend::debug_log(
+                    &format!(
+                        "Hydration mismatch. The text of this node was {data:?} on the server:"
+                    ),
+                    node,
+                );
+            }
+        }
+        Server::Element { attributes } => {
+            let element = unchecked_into_element(node.clone());
+            let mut names = attribute_names(&element);
+            names.extend(attributes.iter().map(|(name, _)| name.clone()));
+            names.sort();
+            names.dedup();
+            for name in names {
+                let before = attributes
+                    .iter()
+                    .find(|(server, _)| *server == name)
+                    .map(|(_, value)| value.as_str());
+                let after = element.get_attribute(&name);
+                let same = match (before, after.as_deref()) {
+                    // classes can be in any order
+                    (Some(before), Some(after)) if name == "class" => {
+                        let mut before = before.split_ascii_whitespace().collect::<Vec<_>>();
+                        let mut after = after.split_ascii_whitespace().collect::<Vec<_>>();
+                        before.sort_unstable();
+                        before.dedup();
+                        after.sort_unstable();
+                        after.dedup();
+                        before == after
+                    }
+                    (before, after) => before == after,
+                };
+                if !same {
+                    let describe = |value: Option<&str>| match value {
+                        Some(value) => format!("{value:?}"),
+                        None => "missing".to_owned(),
+                    };
+                    super::backend::debug_log(
+                        &format!(
+                            "Hydration mismatch. The `{name}` attribute of this element was {} on the server, but is {} in the app:",
+                            describe(before),
+                            describe(after.as_deref()),
+                        ),
+                        node,
+                    );
+                }
+            }
+        }
+    }
+}
+#[cfg(not(debug_assertions))]
+fn report_changed(_node: &Node, _server: &Server) {}
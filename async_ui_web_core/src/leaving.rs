@@ -0,0 +1,35 @@
+//! Delaying the removal of nodes, for exit animations.
+//!
+//! Normally, when the future that rendered a node is dropped, the node is
+//! removed right away. A *leave hook* set on the node is called instead,
+//! and becomes responsible for removing the node (for example, once an
+//! animation has finished).
+//!
+//! Leave hooks only run when the node itself is removed. When an ancestor is
+//! removed, its descendants go with it (see [DetachmentBlocker][crate::DetachmentBlocker]),
+//! and their hooks don't run.
+
+use std::cell::RefCell;
+
+use super::{dom::Node, node_map::NodeMap};
+
+type LeaveHook = Box<dyn FnOnce()>;
+
+thread_local! {
+    static LEAVE_HOOKS: RefCell<NodeMap<LeaveHook>> = RefCell::new(NodeMap::new());
+}
+
+/// Call `hook` instead of removing `node`, the next time `node` is removed.
+/// Replaces the hook previously set on `node`, if any.
+pub fn set_leave_hook(node: &Node, hook: impl FnOnce() + 'static) {
+    LEAVE_HOOKS.with(|hooks| hooks.borrow_mut().insert(node, Box::new(hook)));
+}
+
+/// Remove the hook set on `node` without calling it.
+pub fn remove_leave_hook(node: &Node) {
+    take_leave_hook(node);
+}
+
+pub(crate) fn take_leave_hook(node: &Node) -> Option<LeaveHook> {
+    LEAVE_HOOKS.with(|hooks| hooks.borrow_mut().remove(node))
+}
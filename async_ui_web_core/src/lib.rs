@@ -1,13 +1,38 @@
-pub mod combinators;
+pub mod dom;
 pub mod executor;
+pub mod hydration;
+pub mod leaving;
+#[cfg(feature = "ssr")]
+pub mod ssr;
 pub mod window;
 
-mod context;
-mod dropping;
-mod node_container;
-mod node_sibling;
-mod position;
+mod backend;
+mod node_map;
 
-pub use dropping::DetachmentBlocker;
-pub use node_container::ContainerNodeFuture;
-pub use node_sibling::SiblingNodeFuture;
+pub use async_ui::{
+    combinators, provide, use_context, with_context, DetachmentBlocker, Provide, TaskScope,
+};
+#[cfg(panic = "unwind")]
+pub use async_ui::{ErrorBoundary, PanicPayload};
+pub use backend::Web;
+
+/// Future wrapper where anything rendered in its child will appear as child of the node.
+/// All common components (`Div`, `Button`, etc.) uses this internally.
+///
+/// See [async_ui::ContainerNodeFuture].
+pub type ContainerNodeFuture<C> = async_ui::ContainerNodeFuture<Web, C>;
+/// Future wrapper where anything rendered in its child will appear as a sibling of a node.
+/// This is only used for making the `DynamicList` in `async_ui_web`.
+///
+/// See [async_ui::SiblingNodeFuture].
+pub type SiblingNodeFuture<C> = async_ui::SiblingNodeFuture<Web, C>;
+/// Future wrapper where anything rendered in its child will appear inside another node.
+/// This is used for making the `Portal` component in `async_ui_web`.
+///
+/// See [async_ui::PortalFuture].
+pub type PortalFuture<C> = async_ui::PortalFuture<Web, C>;
+/// A container outside of the page, for rendering futures out of sight.
+/// This is used for making `Suspense` and `KeepAlive` in `async_ui_web`.
+///
+/// See [async_ui::OffscreenRoot].
+pub type OffscreenRoot = async_ui::OffscreenRoot<Web>;
@@ -0,0 +1,59 @@
+//! Maps keyed by node identity.
+
+use crate::dom::Node;
+
+/// A map from nodes (compared with `is_same_node`) to values.
+///
+/// In the browser, nodes are looked up in a JavaScript `Map`, so that
+/// lookups don't need to compare against every node in the map.
+pub(crate) struct NodeMap<V> {
+    slots: js_sys::Map,
+    values: Vec<Option<V>>,
+    free: Vec<usize>,
+}
+
+impl<V> NodeMap<V> {
+    pub fn new() -> Self {
+        Self {
+            slots: js_sys::Map::new(),
+            values: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+    fn slot(&self, node: &Node) -> Option<usize> {
+        self.slots.get(node).as_f64().map(|slot| slot as usize)
+    }
+    pub fn contains(&self, node: &Node) -> bool {
+        self.slots.has(node)
+    }
+    pub fn get(&self, node: &Node) -> Option<&V> {
+        self.values[self.slot(node)?].as_ref()
+    }
+    pub fn get_mut(&mut self, node: &Node) -> Option<&mut V> {
+        let slot = self.slot(node)?;
+        self.values[slot].as_mut()
+    }
+    pub fn insert(&mut self, node: &Node, value: V) -> Option<V> {
+        if let Some(slot) = self.slot(node) {
+            return self.values[slot].replace(value);
+        }
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.values[slot] = Some(value);
+                slot
+            }
+            None => {
+                self.values.push(Some(value));
+                self.values.len() - 1
+            }
+        };
+        self.slots.set(node, &(slot as f64).into());
+        None
+    }
+    pub fn remove(&mut self, node: &Node) -> Option<V> {
+        let slot = self.slot(node)?;
+        self.slots.delete(node);
+        self.free.push(slot);
+        self.values[slot].take()
+    }
+}
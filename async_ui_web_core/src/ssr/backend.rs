@@ -0,0 +1,53 @@
+use async_ui::Backend;
+
+use super::{
+    dom::{Comment, Element, Node, Text},
+    hydration,
+    leaving::take_leave_hook,
+};
+
+/// The in-memory DOM as an Async UI [Backend].
+///
+/// This is the server-side counterpart of [Web][crate::Web].
+pub struct Ssr;
+
+impl Backend for Ssr {
+    type Node = Node;
+
+    fn insert_before(container: &Node, node: &Node, reference: Option<&Node>) {
+        // adopted nodes that are already in place aren't moved
+        if hydration::before_insert(container, node, reference) {
+            return;
+        }
+        container.insert_before(node, reference).unwrap();
+    }
+    fn remove_child(container: &Node, node: &Node) {
+        if let Some(hook) = take_leave_hook(node) {
+            hook();
+        } else {
+            container.remove_child(node).unwrap();
+        }
+    }
+    fn create_marker() -> Node {
+        Comment::new().unwrap().into()
+    }
+}
+
+// Helpers for the modules shared with the browser.
+
+pub(crate) fn unchecked_into_element(node: Node) -> Element {
+    node.unchecked_into_element()
+}
+pub(crate) fn unchecked_into_text(node: Node) -> Text {
+    node.unchecked_into_text()
+}
+pub(crate) fn containing_node() -> Option<Node> {
+    async_ui::containing_node::<Ssr>()
+}
+pub(crate) fn attribute_names(element: &Element) -> Vec<String> {
+    element.get_attribute_names()
+}
+#[cfg(debug_assertions)]
+pub(crate) fn debug_log(message: &str, node: &Node) {
+    Ssr::debug_log(message, node)
+}
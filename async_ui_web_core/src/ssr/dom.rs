@@ -0,0 +1,1063 @@
+//! An in-memory stand-in for the subset of the DOM API that Async UI uses.
+//!
+//! The types here mirror their [web_sys] counterparts (same names, same method
+//! signatures where possible) so that the same code can be compiled against
+//! both this and [crate::dom].
+//!
+//! All node types are handles to the same reference-counted tree node.
+//! Cloning a handle does not clone the node.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    ops::Deref,
+    rc::{Rc, Weak},
+};
+
+/// The error type returned by fallible DOM operations.
+///
+/// The browser DOM throws a `DOMException` in these situations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomError(pub &'static str);
+
+impl fmt::Display for DomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for DomError {}
+
+/// The namespace of HTML elements.
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+struct NodeData {
+    kind: NodeKind,
+    parent: RefCell<Weak<NodeData>>,
+    children: RefCell<Vec<Node>>,
+    listeners: RefCell<Vec<RegisteredListener>>,
+}
+
+struct RegisteredListener {
+    type_: String,
+    capture: bool,
+    listener: EventListener,
+}
+
+enum NodeKind {
+    Element {
+        tag_name: String,
+        namespace: Option<String>,
+        attributes: RefCell<Vec<(String, String)>>,
+    },
+    Text(RefCell<String>),
+    Comment(RefCell<String>),
+    DocumentFragment,
+}
+
+/// Like [web_sys::EventTarget]. The base of all node types.
+#[derive(Clone)]
+pub struct EventTarget {
+    data: Rc<NodeData>,
+}
+
+/// Like [web_sys::Node].
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Node {
+    target: EventTarget,
+}
+
+/// Like [web_sys::Element].
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Element {
+    node: Node,
+}
+
+/// Like [web_sys::HtmlElement].
+/// Every HTML element type (`HtmlDivElement`, `HtmlInputElement`, ...) is
+/// represented by this one type.
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct HtmlElement {
+    element: Element,
+}
+
+/// Like [web_sys::SvgElement].
+/// Every SVG element type (`SvgsvgElement`, `SvgPathElement`, ...) is
+/// represented by this one type.
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct SvgElement {
+    element: Element,
+}
+
+/// Like [web_sys::Text].
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Text {
+    node: Node,
+}
+
+/// Like [web_sys::Comment].
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct Comment {
+    node: Node,
+}
+
+/// Like [web_sys::DocumentFragment].
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct DocumentFragment {
+    node: Node,
+}
+
+/// Like [web_sys::Document]. Only node creation is supported.
+#[derive(Clone, Default)]
+pub struct Document {
+    _private: (),
+}
+
+/// Like [web_sys::Event].
+/// Every event type (`MouseEvent`, `KeyboardEvent`, ...) is represented by
+/// this one type.
+#[derive(Clone)]
+pub struct Event {
+    data: Rc<EventData>,
+}
+
+struct EventData {
+    type_: String,
+    bubbles: bool,
+    cancelable: bool,
+    default_prevented: Cell<bool>,
+    propagation_stopped: Cell<bool>,
+    target: RefCell<Option<EventTarget>>,
+    current_target: RefCell<Option<EventTarget>>,
+}
+
+/// Like [web_sys::EventInit].
+#[derive(Clone, Debug, Default)]
+pub struct EventInit {
+    bubbles: bool,
+    cancelable: bool,
+}
+
+/// An event listener callback.
+///
+/// In the browser, listeners are JavaScript functions
+/// (usually made with [Closure][wasm_bindgen::closure::Closure]).
+/// Listeners are compared by identity: to remove a listener, pass a clone of
+/// the same `EventListener` that was added.
+#[derive(Clone)]
+pub struct EventListener(Rc<dyn Fn(Event)>);
+
+/// Like [web_sys::DomTokenList]. Backed by the `class` attribute.
+pub struct DomTokenList {
+    element: Element,
+}
+
+/// Like [web_sys::CssStyleDeclaration]. Backed by the `style` attribute.
+pub struct CssStyleDeclaration {
+    element: Element,
+}
+
+macro_rules! impl_deref_and_conversions {
+    ($ty:ident, $field:ident: $target:ty, [$($ancestor:ty),*]) => {
+        impl Deref for $ty {
+            type Target = $target;
+            fn deref(&self) -> &Self::Target {
+                &self.$field
+            }
+        }
+        impl AsRef<$ty> for $ty {
+            fn as_ref(&self) -> &$ty {
+                self
+            }
+        }
+        $(
+            impl AsRef<$ancestor> for $ty {
+                fn as_ref(&self) -> &$ancestor {
+                    self
+                }
+            }
+            impl From<$ty> for $ancestor {
+                fn from(value: $ty) -> Self {
+                    let r: &$ancestor = &value;
+                    r.clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_deref_and_conversions!(Node, target: EventTarget, [EventTarget]);
+impl_deref_and_conversions!(Element, node: Node, [Node, EventTarget]);
+impl_deref_and_conversions!(HtmlElement, element: Element, [Element, Node, EventTarget]);
+impl_deref_and_conversions!(SvgElement, element: Element, [Element, Node, EventTarget]);
+impl_deref_and_conversions!(Text, node: Node, [Node, EventTarget]);
+impl_deref_and_conversions!(Comment, node: Node, [Node, EventTarget]);
+impl_deref_and_conversions!(DocumentFragment, node: Node, [Node, EventTarget]);
+
+impl AsRef<EventTarget> for EventTarget {
+    fn as_ref(&self) -> &EventTarget {
+        self
+    }
+}
+
+macro_rules! impl_debug_as_node {
+    ($($ty:ident),*) => {
+        $(
+            impl fmt::Debug for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let node: &Node = self;
+                    fmt::Debug::fmt(node, f)
+                }
+            }
+        )*
+    };
+}
+impl_debug_as_node!(
+    Element,
+    HtmlElement,
+    SvgElement,
+    Text,
+    Comment,
+    DocumentFragment
+);
+
+impl fmt::Debug for EventTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventTarget").finish_non_exhaustive()
+    }
+}
+
+impl Node {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            target: EventTarget {
+                data: Rc::new(NodeData {
+                    kind,
+                    parent: RefCell::new(Weak::new()),
+                    children: RefCell::new(Vec::new()),
+                    listeners: RefCell::new(Vec::new()),
+                }),
+            },
+        }
+    }
+    fn data(&self) -> &NodeData {
+        &self.target.data
+    }
+    fn index_in(&self, parent: &Node) -> Option<usize> {
+        parent
+            .data()
+            .children
+            .borrow()
+            .iter()
+            .position(|c| c.is_same_node(Some(self)))
+    }
+    fn detach(&self) {
+        if let Some(parent) = self.parent_node() {
+            if let Some(index) = self.index_in(&parent) {
+                parent.data().children.borrow_mut().remove(index);
+            }
+            *self.data().parent.borrow_mut() = Weak::new();
+        }
+    }
+    fn sibling(&self, offset: isize) -> Option<Node> {
+        let parent = self.parent_node()?;
+        let index = self.index_in(&parent)?;
+        let children = parent.data().children.borrow();
+        children.get(index.checked_add_signed(offset)?).cloned()
+    }
+
+    /// Insert `node` as a child of this node, before `child`
+    /// (or at the end, if `child` is `None`).
+    ///
+    /// If `node` is a [DocumentFragment], its children are moved in instead.
+    pub fn insert_before(&self, node: &Node, child: Option<&Node>) -> Result<Node, DomError> {
+        if let Some(child) = child {
+            if child.index_in(self).is_none() {
+                return Err(DomError("The node before which the new node is to be inserted is not a child of this node."));
+            }
+        }
+        if node.contains(Some(self)) {
+            return Err(DomError("The new child element contains the parent."));
+        }
+        // Inserting a node before itself leaves it where it is.
+        let child = match child {
+            Some(child) if child.is_same_node(Some(node)) => node.next_sibling(),
+            child => child.cloned(),
+        };
+        let to_insert = if matches!(node.data().kind, NodeKind::DocumentFragment) {
+            std::mem::take(&mut *node.data().children.borrow_mut())
+        } else {
+            node.detach();
+            vec![node.clone()]
+        };
+        for inserted in to_insert.iter() {
+            *inserted.data().parent.borrow_mut() = Rc::downgrade(&self.target.data);
+        }
+        let index = match child {
+            Some(child) => child.index_in(self).unwrap(),
+            None => self.data().children.borrow().len(),
+        };
+        self.data()
+            .children
+            .borrow_mut()
+            .splice(index..index, to_insert);
+        Ok(node.clone())
+    }
+    /// Add `node` as the last child of this node.
+    pub fn append_child(&self, node: &Node) -> Result<Node, DomError> {
+        self.insert_before(node, None)
+    }
+    /// Remove `child` from this node.
+    pub fn remove_child(&self, child: &Node) -> Result<Node, DomError> {
+        match child.index_in(self) {
+            Some(index) => {
+                self.data().children.borrow_mut().remove(index);
+                *child.data().parent.borrow_mut() = Weak::new();
+                Ok(child.clone())
+            }
+            None => Err(DomError(
+                "The node to be removed is not a child of this node.",
+            )),
+        }
+    }
+    pub fn parent_node(&self) -> Option<Node> {
+        self.data().parent.borrow().upgrade().map(|data| Node {
+            target: EventTarget { data },
+        })
+    }
+    pub fn parent_element(&self) -> Option<Element> {
+        self.parent_node()
+            .filter(|p| matches!(p.data().kind, NodeKind::Element { .. }))
+            .map(|node| Element { node })
+    }
+    pub fn first_child(&self) -> Option<Node> {
+        self.data().children.borrow().first().cloned()
+    }
+    pub fn last_child(&self) -> Option<Node> {
+        self.data().children.borrow().last().cloned()
+    }
+    pub fn next_sibling(&self) -> Option<Node> {
+        self.sibling(1)
+    }
+    pub fn previous_sibling(&self) -> Option<Node> {
+        self.sibling(-1)
+    }
+    pub fn has_child_nodes(&self) -> bool {
+        !self.data().children.borrow().is_empty()
+    }
+    pub fn is_same_node(&self, other: Option<&Node>) -> bool {
+        other.is_some_and(|other| Rc::ptr_eq(&self.target.data, &other.target.data))
+    }
+    /// Whether `other` is this node or one of its descendants.
+    pub fn contains(&self, other: Option<&Node>) -> bool {
+        let mut current = other.cloned();
+        while let Some(node) = current {
+            if self.is_same_node(Some(&node)) {
+                return true;
+            }
+            current = node.parent_node();
+        }
+        false
+    }
+    /// `1` for elements, `3` for text, `8` for comments, `11` for fragments.
+    pub fn node_type(&self) -> u16 {
+        match &self.data().kind {
+            NodeKind::Element { .. } => 1,
+            NodeKind::Text(_) => 3,
+            NodeKind::Comment(_) => 8,
+            NodeKind::DocumentFragment => 11,
+        }
+    }
+    pub fn node_name(&self) -> String {
+        match &self.data().kind {
+            NodeKind::Element {
+                tag_name,
+                namespace,
+                ..
+            } => {
+                // Only HTML elements have their names uppercased.
+                if namespace.as_deref() == Some(HTML_NAMESPACE) {
+                    tag_name.to_ascii_uppercase()
+                } else {
+                    tag_name.clone()
+                }
+            }
+            NodeKind::Text(_) => "#text".into(),
+            NodeKind::Comment(_) => "#comment".into(),
+            NodeKind::DocumentFragment => "#document-fragment".into(),
+        }
+    }
+    pub fn text_content(&self) -> Option<String> {
+        match &self.data().kind {
+            NodeKind::Text(data) | NodeKind::Comment(data) => Some(data.borrow().clone()),
+            NodeKind::Element { .. } | NodeKind::DocumentFragment => {
+                let mut out = String::new();
+                self.collect_text(&mut out);
+                Some(out)
+            }
+        }
+    }
+    fn collect_text(&self, out: &mut String) {
+        for child in self.data().children.borrow().iter() {
+            match &child.data().kind {
+                NodeKind::Text(data) => out.push_str(&data.borrow()),
+                NodeKind::Comment(_) => {}
+                _ => child.collect_text(out),
+            }
+        }
+    }
+    /// Replace all children with a single text node (or with nothing, if
+    /// the given content is empty).
+    pub fn set_text_content(&self, content: Option<&str>) {
+        match &self.data().kind {
+            NodeKind::Text(data) | NodeKind::Comment(data) => {
+                *data.borrow_mut() = content.unwrap_or_default().into();
+            }
+            NodeKind::Element { .. } | NodeKind::DocumentFragment => {
+                for child in std::mem::take(&mut *self.data().children.borrow_mut()) {
+                    *child.data().parent.borrow_mut() = Weak::new();
+                }
+                if let Some(content) = content.filter(|c| !c.is_empty()) {
+                    self.append_child(&Text::new_with_data(content).unwrap().node)
+                        .unwrap();
+                }
+            }
+        }
+    }
+    /// Serialize this node (and its descendants) to HTML.
+    ///
+    /// For a [DocumentFragment], this is the serialization of its children.
+    ///
+    /// This method does not exist in the browser DOM.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        super::serialize::write_node(self, &mut out);
+        out
+    }
+    pub(super) fn with_children<R>(&self, f: impl FnOnce(&[Node]) -> R) -> R {
+        f(&self.data().children.borrow())
+    }
+    pub(super) fn kind(&self) -> NodeRef<'_> {
+        match &self.data().kind {
+            NodeKind::Element {
+                tag_name,
+                attributes,
+                ..
+            } => NodeRef::Element {
+                tag_name,
+                attributes: attributes.borrow(),
+            },
+            NodeKind::Text(data) => NodeRef::Text(data.borrow()),
+            NodeKind::Comment(data) => NodeRef::Comment(data.borrow()),
+            NodeKind::DocumentFragment => NodeRef::DocumentFragment,
+        }
+    }
+}
+
+/// A borrowed view of a node's content, for serialization.
+pub(super) enum NodeRef<'a> {
+    Element {
+        tag_name: &'a str,
+        attributes: std::cell::Ref<'a, Vec<(String, String)>>,
+    },
+    Text(std::cell::Ref<'a, String>),
+    Comment(std::cell::Ref<'a, String>),
+    DocumentFragment,
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_html())
+    }
+}
+
+impl Element {
+    fn attributes(&self) -> &RefCell<Vec<(String, String)>> {
+        match &self.data().kind {
+            NodeKind::Element { attributes, .. } => attributes,
+            _ => unreachable!("Element handle to a non-element node"),
+        }
+    }
+    /// The tag name, in uppercase for HTML elements (like in the browser).
+    pub fn tag_name(&self) -> String {
+        self.node_name()
+    }
+    pub fn namespace_uri(&self) -> Option<String> {
+        match &self.data().kind {
+            NodeKind::Element { namespace, .. } => namespace.clone(),
+            _ => unreachable!("Element handle to a non-element node"),
+        }
+    }
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        self.attributes()
+            .borrow()
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+    }
+    /// The names of all the attributes, in the order they were added.
+    pub fn get_attribute_names(&self) -> Vec<String> {
+        self.attributes()
+            .borrow()
+            .iter()
+            .map(|(n, _)| n.clone())
+            .collect()
+    }
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes().borrow().iter().any(|(n, _)| n == name)
+    }
+    pub fn set_attribute(&self, name: &str, value: &str) -> Result<(), DomError> {
+        // Attribute names of SVG elements are case-sensitive (`viewBox`).
+        let name = if self.namespace_uri().as_deref() == Some(HTML_NAMESPACE) {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_owned()
+        };
+        let mut attributes = self.attributes().borrow_mut();
+        match attributes.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, v)) => *v = value.into(),
+            None => attributes.push((name, value.into())),
+        }
+        Ok(())
+    }
+    pub fn remove_attribute(&self, name: &str) -> Result<(), DomError> {
+        self.attributes().borrow_mut().retain(|(n, _)| n != name);
+        Ok(())
+    }
+    /// Add the attribute if `force` is true, remove it otherwise.
+    /// Returns `force`.
+    pub fn toggle_attribute_with_force(&self, name: &str, force: bool) -> Result<bool, DomError> {
+        if force {
+            if !self.has_attribute(name) {
+                self.set_attribute(name, "")?;
+            }
+        } else {
+            self.remove_attribute(name)?;
+        }
+        Ok(force)
+    }
+    pub fn id(&self) -> String {
+        self.get_attribute("id").unwrap_or_default()
+    }
+    pub fn set_id(&self, id: &str) {
+        self.set_attribute("id", id).unwrap();
+    }
+    pub fn class_name(&self) -> String {
+        self.get_attribute("class").unwrap_or_default()
+    }
+    pub fn set_class_name(&self, class_name: &str) {
+        self.set_attribute("class", class_name).unwrap();
+    }
+    pub fn class_list(&self) -> DomTokenList {
+        DomTokenList {
+            element: self.clone(),
+        }
+    }
+    /// Serialization of this element's children.
+    pub fn inner_html(&self) -> String {
+        let mut out = String::new();
+        self.with_children(|children| {
+            children
+                .iter()
+                .for_each(|child| super::serialize::write_node(child, &mut out))
+        });
+        out
+    }
+    /// Serialization of this element.
+    pub fn outer_html(&self) -> String {
+        self.to_html()
+    }
+}
+
+macro_rules! reflect_attributes {
+    ($($getter:ident $setter:ident $attr:literal),* ; $($bool_getter:ident $bool_setter:ident $bool_attr:literal),*) => {
+        $(
+            #[doc = concat!("Reflects the `", $attr, "` attribute.")]
+            pub fn $getter(&self) -> String {
+                self.get_attribute($attr).unwrap_or_default()
+            }
+            #[doc = concat!("Reflects the `", $attr, "` attribute.")]
+            pub fn $setter(&self, value: &str) {
+                self.set_attribute($attr, value).unwrap();
+            }
+        )*
+        $(
+            #[doc = concat!("Reflects the boolean `", $bool_attr, "` attribute.")]
+            pub fn $bool_getter(&self) -> bool {
+                self.has_attribute($bool_attr)
+            }
+            #[doc = concat!("Reflects the boolean `", $bool_attr, "` attribute.")]
+            pub fn $bool_setter(&self, value: bool) {
+                self.toggle_attribute_with_force($bool_attr, value).unwrap();
+            }
+        )*
+    };
+}
+
+impl HtmlElement {
+    reflect_attributes!(
+        title set_title "title",
+        lang set_lang "lang",
+        type_ set_type "type",
+        value set_value "value",
+        name set_name "name",
+        placeholder set_placeholder "placeholder",
+        href set_href "href",
+        src set_src "src",
+        alt set_alt "alt",
+        target set_target "target",
+        html_for set_html_for "for";
+        hidden set_hidden "hidden",
+        checked set_checked "checked",
+        disabled set_disabled "disabled",
+        selected set_selected "selected",
+        required set_required "required",
+        read_only set_read_only "readonly",
+        multiple set_multiple "multiple"
+    );
+    pub fn style(&self) -> CssStyleDeclaration {
+        CssStyleDeclaration {
+            element: self.element.clone(),
+        }
+    }
+}
+
+impl SvgElement {
+    pub fn style(&self) -> CssStyleDeclaration {
+        CssStyleDeclaration {
+            element: self.element.clone(),
+        }
+    }
+}
+
+impl DomTokenList {
+    fn tokens(&self) -> Vec<String> {
+        self.element
+            .class_name()
+            .split_ascii_whitespace()
+            .map(String::from)
+            .collect()
+    }
+    fn set_tokens(&self, tokens: Vec<String>) {
+        self.element.set_class_name(&tokens.join(" "));
+    }
+    pub fn contains(&self, token: &str) -> bool {
+        self.tokens().iter().any(|t| t == token)
+    }
+    pub fn add_1(&self, token: &str) -> Result<(), DomError> {
+        let mut tokens = self.tokens();
+        if !tokens.iter().any(|t| t == token) {
+            tokens.push(token.into());
+            self.set_tokens(tokens);
+        }
+        Ok(())
+    }
+    pub fn remove_1(&self, token: &str) -> Result<(), DomError> {
+        let mut tokens = self.tokens();
+        tokens.retain(|t| t != token);
+        self.set_tokens(tokens);
+        Ok(())
+    }
+    pub fn toggle_with_force(&self, token: &str, force: bool) -> Result<bool, DomError> {
+        if force {
+            self.add_1(token)?;
+        } else {
+            self.remove_1(token)?;
+        }
+        Ok(force)
+    }
+}
+
+impl CssStyleDeclaration {
+    fn declarations(&self) -> Vec<(String, String)> {
+        self.element
+            .get_attribute("style")
+            .unwrap_or_default()
+            .split(';')
+            .filter_map(|decl| {
+                let (prop, value) = decl.split_once(':')?;
+                Some((prop.trim().into(), value.trim().into()))
+            })
+            .collect()
+    }
+    fn set_declarations(&self, declarations: Vec<(String, String)>) {
+        if declarations.is_empty() {
+            self.element.remove_attribute("style").unwrap();
+        } else {
+            let text = declarations
+                .iter()
+                .map(|(prop, value)| format!("{prop}: {value};"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.element.set_attribute("style", &text).unwrap();
+        }
+    }
+    pub fn get_property_value(&self, property: &str) -> Result<String, DomError> {
+        Ok(self
+            .declarations()
+            .into_iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, v)| v)
+            .unwrap_or_default())
+    }
+    pub fn set_property(&self, property: &str, value: &str) -> Result<(), DomError> {
+        let mut declarations = self.declarations();
+        if value.is_empty() {
+            declarations.retain(|(p, _)| p != property);
+        } else {
+            match declarations.iter_mut().find(|(p, _)| p == property) {
+                Some((_, v)) => *v = value.into(),
+                None => declarations.push((property.into(), value.into())),
+            }
+        }
+        self.set_declarations(declarations);
+        Ok(())
+    }
+    pub fn remove_property(&self, property: &str) -> Result<String, DomError> {
+        let old = self.get_property_value(property)?;
+        self.set_property(property, "")?;
+        Ok(old)
+    }
+}
+
+impl Text {
+    pub fn new() -> Result<Self, DomError> {
+        Self::new_with_data("")
+    }
+    pub fn new_with_data(data: &str) -> Result<Self, DomError> {
+        Ok(Self {
+            node: Node::new(NodeKind::Text(RefCell::new(data.into()))),
+        })
+    }
+    pub fn data(&self) -> String {
+        self.text_content().unwrap_or_default()
+    }
+    pub fn set_data(&self, data: &str) {
+        self.set_text_content(Some(data));
+    }
+}
+
+impl Comment {
+    pub fn new() -> Result<Self, DomError> {
+        Self::new_with_data("")
+    }
+    pub fn new_with_data(data: &str) -> Result<Self, DomError> {
+        Ok(Self {
+            node: Node::new(NodeKind::Comment(RefCell::new(data.into()))),
+        })
+    }
+    pub fn data(&self) -> String {
+        self.text_content().unwrap_or_default()
+    }
+    pub fn set_data(&self, data: &str) {
+        self.set_text_content(Some(data));
+    }
+}
+
+impl DocumentFragment {
+    pub fn new() -> Result<Self, DomError> {
+        Ok(Self {
+            node: Node::new(NodeKind::DocumentFragment),
+        })
+    }
+}
+
+impl Document {
+    pub fn new() -> Result<Self, DomError> {
+        Ok(Self::default())
+    }
+    /// Create an HTML element.
+    pub fn create_element(&self, local_name: &str) -> Result<Element, DomError> {
+        self.create_element_ns(Some(HTML_NAMESPACE), &local_name.to_ascii_lowercase())
+    }
+    /// Create an element in the given namespace, such as an SVG element.
+    /// Unlike with [create_element][Self::create_element], the name is kept as is.
+    pub fn create_element_ns(
+        &self,
+        namespace: Option<&str>,
+        qualified_name: &str,
+    ) -> Result<Element, DomError> {
+        if qualified_name.is_empty() {
+            return Err(DomError("InvalidCharacterError"));
+        }
+        Ok(Element {
+            node: Node::new(NodeKind::Element {
+                tag_name: qualified_name.into(),
+                namespace: namespace.map(String::from),
+                attributes: RefCell::new(Vec::new()),
+            }),
+        })
+    }
+    pub fn create_text_node(&self, data: &str) -> Text {
+        Text::new_with_data(data).unwrap()
+    }
+    pub fn create_comment(&self, data: &str) -> Comment {
+        Comment::new_with_data(data).unwrap()
+    }
+    pub fn create_document_fragment(&self) -> DocumentFragment {
+        DocumentFragment::new().unwrap()
+    }
+}
+
+impl Element {
+    /// Like [JsCast::unchecked_into][wasm_bindgen::JsCast::unchecked_into]
+    /// from `Element` to `HtmlElement`.
+    pub fn unchecked_into_html_element(self) -> HtmlElement {
+        HtmlElement { element: self }
+    }
+    /// Like [JsCast::unchecked_into][wasm_bindgen::JsCast::unchecked_into]
+    /// from `Element` to `SvgElement`.
+    pub fn unchecked_into_svg_element(self) -> SvgElement {
+        SvgElement { element: self }
+    }
+}
+
+impl Node {
+    /// Like [JsCast::dyn_into][wasm_bindgen::JsCast::dyn_into]
+    /// from `Node` to `Element`.
+    pub fn dyn_into_element(self) -> Result<Element, Node> {
+        match self.data().kind {
+            NodeKind::Element { .. } => Ok(Element { node: self }),
+            _ => Err(self),
+        }
+    }
+    pub(crate) fn unchecked_into_element(self) -> Element {
+        Element { node: self }
+    }
+    pub(crate) fn unchecked_into_text(self) -> Text {
+        Text { node: self }
+    }
+}
+
+impl EventTarget {
+    /// Identifies the node as long as it is alive.
+    pub(crate) fn id(&self) -> usize {
+        Rc::as_ptr(&self.data) as usize
+    }
+}
+
+macro_rules! impl_query_selector {
+    ($($ty:ty),*) => {
+        $(
+            impl $ty {
+                /// The first descendant element matching the given selectors.
+                ///
+                /// Only simple selectors are supported. See [query_selector_all][Self::query_selector_all].
+                pub fn query_selector(&self, selectors: &str) -> Result<Option<Element>, DomError> {
+                    Ok(super::query::query_selector_all(self, selectors, true)?.pop())
+                }
+                /// All descendant elements matching the given selectors, in document order.
+                ///
+                /// Supported selectors are type (`div`), universal (`*`), id (`#id`),
+                /// class (`.class`), and attribute (`[name]`, `[name=value]`) selectors,
+                /// combined with descendant (` `) and child (`>`) combinators,
+                /// in comma-separated lists.
+                ///
+                /// Unlike in the browser, this returns a `Vec` instead of a `NodeList`.
+                pub fn query_selector_all(&self, selectors: &str) -> Result<Vec<Element>, DomError> {
+                    super::query::query_selector_all(self, selectors, false)
+                }
+            }
+        )*
+    };
+}
+impl_query_selector!(Element, DocumentFragment);
+
+impl Event {
+    pub fn new(type_: &str) -> Result<Self, DomError> {
+        Self::new_with_event_init_dict(type_, &EventInit::default())
+    }
+    pub fn new_with_event_init_dict(type_: &str, init: &EventInit) -> Result<Self, DomError> {
+        Ok(Self {
+            data: Rc::new(EventData {
+                type_: type_.into(),
+                bubbles: init.bubbles,
+                cancelable: init.cancelable,
+                default_prevented: Cell::new(false),
+                propagation_stopped: Cell::new(false),
+                target: RefCell::new(None),
+                current_target: RefCell::new(None),
+            }),
+        })
+    }
+    pub fn type_(&self) -> String {
+        self.data.type_.clone()
+    }
+    pub fn bubbles(&self) -> bool {
+        self.data.bubbles
+    }
+    pub fn cancelable(&self) -> bool {
+        self.data.cancelable
+    }
+    /// The target the event was dispatched to.
+    pub fn target(&self) -> Option<EventTarget> {
+        self.data.target.borrow().clone()
+    }
+    /// The target whose listener is currently being called.
+    pub fn current_target(&self) -> Option<EventTarget> {
+        self.data.current_target.borrow().clone()
+    }
+    pub fn prevent_default(&self) {
+        if self.data.cancelable {
+            self.data.default_prevented.set(true);
+        }
+    }
+    pub fn default_prevented(&self) -> bool {
+        self.data.default_prevented.get()
+    }
+    pub fn stop_propagation(&self) {
+        self.data.propagation_stopped.set(true);
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("type", &self.data.type_)
+            .field("bubbles", &self.data.bubbles)
+            .field("cancelable", &self.data.cancelable)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventInit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn bubbles(&mut self, val: bool) -> &mut Self {
+        self.bubbles = val;
+        self
+    }
+    pub fn cancelable(&mut self, val: bool) -> &mut Self {
+        self.cancelable = val;
+        self
+    }
+}
+
+impl EventListener {
+    pub fn new(callback: impl Fn(Event) + 'static) -> Self {
+        Self(Rc::new(callback))
+    }
+}
+
+impl fmt::Debug for EventListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventListener").finish_non_exhaustive()
+    }
+}
+
+impl EventTarget {
+    pub fn add_event_listener_with_callback(
+        &self,
+        type_: &str,
+        listener: &EventListener,
+    ) -> Result<(), DomError> {
+        self.add_event_listener_with_callback_and_bool(type_, listener, false)
+    }
+    /// `capture` is like the `capture` option of `addEventListener`.
+    pub fn add_event_listener_with_callback_and_bool(
+        &self,
+        type_: &str,
+        listener: &EventListener,
+        capture: bool,
+    ) -> Result<(), DomError> {
+        let mut listeners = self.data.listeners.borrow_mut();
+        // like in the browser, adding the same listener twice does nothing
+        if !listeners.iter().any(|l| l.is(type_, listener, capture)) {
+            listeners.push(RegisteredListener {
+                type_: type_.into(),
+                capture,
+                listener: listener.clone(),
+            });
+        }
+        Ok(())
+    }
+    pub fn remove_event_listener_with_callback(
+        &self,
+        type_: &str,
+        listener: &EventListener,
+    ) -> Result<(), DomError> {
+        self.remove_event_listener_with_callback_and_bool(type_, listener, false)
+    }
+    pub fn remove_event_listener_with_callback_and_bool(
+        &self,
+        type_: &str,
+        listener: &EventListener,
+        capture: bool,
+    ) -> Result<(), DomError> {
+        self.data
+            .listeners
+            .borrow_mut()
+            .retain(|l| !l.is(type_, listener, capture));
+        Ok(())
+    }
+    /// Dispatch the event to this target, calling listeners synchronously.
+    ///
+    /// Like in the browser, capturing listeners on ancestors are called first,
+    /// then listeners on this target, then (if the event bubbles)
+    /// non-capturing listeners on ancestors.
+    ///
+    /// Returns `false` if the event is cancelable and a listener called
+    /// [prevent_default][Event::prevent_default].
+    pub fn dispatch_event(&self, event: &Event) -> Result<bool, DomError> {
+        if event.data.target.borrow().is_some() {
+            return Err(DomError(
+                "The event is already being, or has been, dispatched.",
+            ));
+        }
+        *event.data.target.borrow_mut() = Some(self.clone());
+        let mut path = vec![self.clone()];
+        let mut current = self.data.parent.borrow().upgrade();
+        while let Some(data) = current {
+            current = data.parent.borrow().upgrade();
+            path.push(EventTarget { data });
+        }
+        let phases = path
+            .iter()
+            .skip(1)
+            .rev()
+            .map(|target| (target, Some(true)))
+            .chain(std::iter::once((self, None)))
+            .chain(
+                path.iter()
+                    .skip(1)
+                    .filter(|_| event.data.bubbles)
+                    .map(|target| (target, Some(false))),
+            );
+        for (target, capture) in phases {
+            if event.data.propagation_stopped.get() {
+                break;
+            }
+            *event.data.current_target.borrow_mut() = Some(target.clone());
+            // collect first so listeners can add or remove listeners
+            let listeners: Vec<_> = target
+                .data
+                .listeners
+                .borrow()
+                .iter()
+                .filter(|l| l.type_ == event.data.type_ && capture.is_none_or(|c| c == l.capture))
+                .map(|l| l.listener.clone())
+                .collect();
+            for listener in listeners {
+                (listener.0)(event.clone());
+            }
+        }
+        *event.data.current_target.borrow_mut() = None;
+        Ok(!event.default_prevented())
+    }
+}
+
+impl RegisteredListener {
+    fn is(&self, type_: &str, listener: &EventListener, capture: bool) -> bool {
+        self.type_ == type_
+            && self.capture == capture
+            && std::ptr::addr_eq(Rc::as_ptr(&self.listener.0), Rc::as_ptr(&listener.0))
+    }
+}
@@ -0,0 +1,672 @@
+/*!
+Server-side rendering.
+
+This module is a second [Backend][async_ui::Backend], [Ssr], next to the
+browser's [Web][crate::Web]. It renders into the in-memory DOM in
+[dom][self::dom] instead of the browser's, so components can run on a native
+target and be turned into an HTML string.
+The modules and type aliases here mirror those at the crate root, for the
+in-memory DOM.
+
+```rust
+# use async_ui_web_core::ssr::{render_to_string, dom::Text, ContainerNodeFuture};
+let text = Text::new_with_data("Hello World").unwrap();
+let html = render_to_string(ContainerNodeFuture::new(
+    std::future::pending::<()>(),
+    text.into(),
+));
+assert_eq!(html, "Hello World");
+```
+
+UI futures usually never complete, so rendering stops once the tree reaches
+a *quiescent* point: the root future has been polled, and nothing (not even
+tasks spawned on the app executor) has been woken since.
+
+There is no event loop or timers on the server. Futures waiting on events or
+timers simply stay pending, and whatever they rendered so far is included in
+the output.
+
+# Testing
+
+Because everything runs natively, the in-memory DOM is also useful for testing
+components with plain `cargo test`. Render with [SsrRoot], find nodes with
+`query_selector`, fire events with
+[dispatch_event][dom::EventTarget::dispatch_event], then call
+[run_until_stalled][SsrRoot::run_until_stalled] again to let the components react.
+*/
+
+mod backend;
+pub mod dom;
+// Shared with the crate root, compiled again for the in-memory DOM.
+#[allow(clippy::duplicate_mod)]
+#[path = "../hydration.rs"]
+pub mod hydration;
+#[allow(clippy::duplicate_mod)]
+#[path = "../leaving.rs"]
+pub mod leaving;
+mod node_map;
+mod query;
+mod serialize;
+pub mod window;
+
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+pub use backend::Ssr;
+
+use crate::executor::{RootExecutor, Scheduling};
+use hydration::Hydration;
+
+/// Like [ContainerNodeFuture][crate::ContainerNodeFuture], for the in-memory DOM.
+pub type ContainerNodeFuture<C> = async_ui::ContainerNodeFuture<Ssr, C>;
+/// Like [SiblingNodeFuture][crate::SiblingNodeFuture], for the in-memory DOM.
+pub type SiblingNodeFuture<C> = async_ui::SiblingNodeFuture<Ssr, C>;
+/// Like [PortalFuture][crate::PortalFuture], for the in-memory DOM.
+pub type PortalFuture<C> = async_ui::PortalFuture<Ssr, C>;
+/// Like [OffscreenRoot][crate::OffscreenRoot], for the in-memory DOM.
+pub type OffscreenRoot = async_ui::OffscreenRoot<Ssr>;
+
+/// Render the given future to HTML.
+///
+/// The future is polled until it completes or the tree becomes quiescent
+/// (see the [module documentation][self]), then dropped.
+/// Returns the HTML of everything it rendered.
+///
+/// This is a shorthand for [SsrRoot::new], [SsrRoot::run_until_stalled],
+/// and [SsrRoot::to_html].
+pub fn render_to_string<F: Future>(future: F) -> String {
+    let mut root = pin!(SsrRoot::new(future));
+    root.as_mut().run_until_stalled();
+    root.to_html()
+}
+
+/// A future being rendered on the server.
+///
+/// Use this instead of [render_to_string] if you need to render in multiple
+/// steps, for example when waiting for data to load between steps.
+#[pin_project::pin_project]
+pub struct SsrRoot<F: Future> {
+    #[pin]
+    future: ContainerNodeFuture<F>,
+    container: dom::DocumentFragment,
+    woken: Arc<WakeFlag>,
+    output: Option<F::Output>,
+    hydration: Option<Hydration>,
+    root: RootExecutor,
+}
+
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<F: Future> SsrRoot<F> {
+    /// Create a root for rendering the given future.
+    /// The future is not polled until [run_until_stalled][Self::run_until_stalled].
+    pub fn new(future: F) -> Self {
+        Self::with_hydration(future, dom::DocumentFragment::new().unwrap(), None)
+    }
+
+    /// Like [new][Self::new], but take over the nodes already in `container`
+    /// (see [hydration][crate::hydration]) instead of rendering after them.
+    ///
+    /// Existing nodes that haven't been rendered by the time
+    /// [run_until_stalled][Self::run_until_stalled] first returns are removed.
+    pub fn hydrate(future: F, container: dom::DocumentFragment) -> Self {
+        let hydration = Hydration::new(&container);
+        Self::with_hydration(future, container, Some(hydration))
+    }
+    fn with_hydration(
+        future: F,
+        container: dom::DocumentFragment,
+        hydration: Option<Hydration>,
+    ) -> Self {
+        // There is no event loop; we run the root ourselves.
+        let root = RootExecutor::new();
+        root.set_scheduling(Scheduling::Manual);
+        root.set_time_budget(None);
+        Self {
+            future: ContainerNodeFuture::new_root(future, container.clone().into()),
+            container,
+            woken: Default::default(),
+            output: None,
+            hydration,
+            root,
+        }
+    }
+
+    /// The [RootExecutor] that runs along with the future.
+    ///
+    /// Things woken while rendering are scheduled on this root, and it runs
+    /// whenever the future is polled. Give it a future with
+    /// [set_future][RootExecutor::set_future] (for example, one that drives
+    /// a task executor) to run that future alongside.
+    pub fn executor(&self) -> &RootExecutor {
+        &self.root
+    }
+
+    /// Poll the future (and the app executor) until the tree is quiescent
+    /// or the future completes.
+    ///
+    /// Returns `true` if the future has completed.
+    pub fn run_until_stalled(mut self: Pin<&mut Self>) -> bool {
+        match self.as_mut().project().hydration.take() {
+            Some(hydration) => {
+                let completed = hydration.enter(|| self.run());
+                hydration.finish();
+                completed
+            }
+            None => self.run(),
+        }
+    }
+    fn run(self: Pin<&mut Self>) -> bool {
+        let mut this = self.project();
+        if this.output.is_some() {
+            return true;
+        }
+        let waker = Waker::from(this.woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        // The first poll needs to happen regardless of wakes.
+        this.woken.0.store(true, Ordering::Relaxed);
+        this.root.schedule();
+        loop {
+            if this.woken.0.swap(false, Ordering::Relaxed) {
+                let poll = this.root.enter(|| this.future.as_mut().poll(&mut cx));
+                if let Poll::Ready(output) = poll {
+                    *this.output = Some(output);
+                    return true;
+                }
+            }
+            this.root.run_now();
+            if !this.woken.0.load(Ordering::Relaxed) && !this.root.is_scheduled() {
+                return false;
+            }
+        }
+    }
+
+    /// The output of the future, if it has completed.
+    pub fn take_output(self: Pin<&mut Self>) -> Option<F::Output> {
+        self.project().output.take()
+    }
+
+    /// The node everything is rendered into.
+    ///
+    /// Use this to inspect the rendered tree, or to find nodes to
+    /// [dispatch events][dom::EventTarget::dispatch_event] to.
+    pub fn container(&self) -> &dom::DocumentFragment {
+        &self.container
+    }
+
+    /// Serialize what has been rendered so far to HTML.
+    pub fn to_html(&self) -> String {
+        self.container.to_html()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{pending, ready};
+
+    use super::{window::DOCUMENT, *};
+    use crate::{
+        combinators::{join, race},
+        executor,
+    };
+
+    fn element(tag: &str) -> dom::Element {
+        DOCUMENT.with(|doc| doc.create_element(tag).unwrap())
+    }
+    fn text(content: &str) -> ContainerNodeFuture<std::future::Pending<()>> {
+        ContainerNodeFuture::new(pending(), dom::Text::new_with_data(content).unwrap().into())
+    }
+
+    #[test]
+    fn ordering() {
+        let html = render_to_string(async {
+            let div = element("div");
+            div.set_attribute("class", "wrapper").unwrap();
+            ContainerNodeFuture::new(
+                join((
+                    race((text("a"), text("b"))),
+                    join((text("c"), text("d"))),
+                    ContainerNodeFuture::new(text("e"), element("span").into()),
+                )),
+                div.into(),
+            )
+            .await;
+        });
+        assert_eq!(html, r#"<div class="wrapper">abcd<span>e</span></div>"#);
+    }
+
+    #[test]
+    fn removal() {
+        let html = render_to_string(join((
+            text("first"),
+            async {
+                race((text("gone"), ready(()))).await;
+                text("replaced").await;
+            },
+            text("last"),
+        )));
+        assert_eq!(html, "firstreplacedlast");
+    }
+
+    #[test]
+    fn escaping_and_void_elements() {
+        let html = render_to_string(async {
+            let input = element("input");
+            input.set_attribute("value", "\"quoted\" & <b>").unwrap();
+            join((
+                ContainerNodeFuture::new(pending::<()>(), input.into()),
+                text("1 < 2 & 3 > 2"),
+            ))
+            .await;
+        });
+        assert_eq!(
+            html,
+            r#"<input value="&quot;quoted&quot; &amp; <b>">1 &lt; 2 &amp; 3 &gt; 2"#
+        );
+    }
+
+    #[test]
+    fn svg_elements() {
+        const SVG: &str = "http://www.w3.org/2000/svg";
+        let html = render_to_string(async {
+            let svg = DOCUMENT.with(|doc| doc.create_element_ns(Some(SVG), "svg").unwrap());
+            svg.set_attribute("viewBox", "0 0 10 10").unwrap();
+            let gradient =
+                DOCUMENT.with(|doc| doc.create_element_ns(Some(SVG), "linearGradient").unwrap());
+            assert_eq!(gradient.tag_name(), "linearGradient");
+            assert_eq!(gradient.namespace_uri().as_deref(), Some(SVG));
+            assert_eq!(element("div").tag_name(), "DIV");
+            ContainerNodeFuture::new(
+                ContainerNodeFuture::new(pending::<()>(), gradient.into()),
+                svg.into(),
+            )
+            .await;
+        });
+        assert_eq!(
+            html,
+            r#"<svg viewBox="0 0 10 10"><linearGradient></linearGradient></svg>"#
+        );
+    }
+
+    #[test]
+    fn hydration() {
+        use super::hydration::{create_element, create_text_node};
+
+        let root = dom::DocumentFragment::new().unwrap();
+        let old_div = element("div");
+        let old_span = element("span");
+        let old_text = dom::Text::new_with_data("a").unwrap();
+        old_span.append_child(&old_text).unwrap();
+        old_div.append_child(&old_span).unwrap();
+        old_div
+            .append_child(&dom::Text::new_with_data("stale").unwrap())
+            .unwrap();
+        let old_p = element("p");
+        root.append_child(&dom::Text::new_with_data("\n  ").unwrap())
+            .unwrap();
+        root.append_child(&old_div).unwrap();
+        root.append_child(&dom::Comment::new().unwrap()).unwrap();
+        root.append_child(&old_p).unwrap();
+
+        let mut fut = pin!(SsrRoot::hydrate(
+            async {
+                let div = create_element("div");
+                let span = create_element("span");
+                let text = create_text_node("");
+                join((
+                    ContainerNodeFuture::new(
+                        ContainerNodeFuture::new(
+                            ContainerNodeFuture::new(pending::<()>(), text.into()),
+                            span.into(),
+                        ),
+                        div.into(),
+                    ),
+                    async {
+                        // rendered later, but before the first render settles
+                        executor::yield_now().await;
+                        ContainerNodeFuture::new(pending::<()>(), create_element("p").into()).await;
+                    },
+                ))
+                .await;
+            },
+            root.clone(),
+        ));
+        fut.as_mut().run_until_stalled();
+
+        assert_eq!(fut.to_html(), "<div><span>a</span></div><p></p>");
+        // the existing nodes themselves are used, not copies
+        assert!(root.first_child().unwrap().is_same_node(Some(&old_div)));
+        assert!(old_div.first_child().unwrap().is_same_node(Some(&old_span)));
+        assert!(old_span
+            .first_child()
+            .unwrap()
+            .is_same_node(Some(&old_text)));
+        assert!(root.last_child().unwrap().is_same_node(Some(&old_p)));
+
+        // once finished, nodes are created as usual
+        let html = render_to_string(async {
+            let div = create_element("div");
+            assert!(!div.is_same_node(Some(&old_div)));
+            ContainerNodeFuture::new(pending::<()>(), div.into()).await;
+        });
+        assert_eq!(html, "<div></div>");
+    }
+
+    #[test]
+    fn hydration_out_of_order() {
+        use super::hydration::create_element;
+
+        let root = dom::DocumentFragment::new().unwrap();
+        let (old_a, old_b) = (element("a"), element("b"));
+        root.append_child(&old_a).unwrap();
+        root.append_child(&old_b).unwrap();
+
+        let mut fut = pin!(SsrRoot::hydrate(
+            async {
+                // created in document order, but rendered in the opposite order
+                let a = create_element("a");
+                let b = create_element("b");
+                join((
+                    ContainerNodeFuture::new(pending::<()>(), b.into()),
+                    ContainerNodeFuture::new(pending::<()>(), a.into()),
+                ))
+                .await;
+            },
+            root.clone(),
+        ));
+        fut.as_mut().run_until_stalled();
+
+        assert_eq!(fut.to_html(), "<b></b><a></a>");
+        assert!(root.first_child().unwrap().is_same_node(Some(&old_b)));
+        assert!(root.last_child().unwrap().is_same_node(Some(&old_a)));
+    }
+
+    #[test]
+    fn hydration_per_container() {
+        use super::hydration::create_element;
+
+        let root = dom::DocumentFragment::new().unwrap();
+        let (old_ul, old_ol) = (element("ul"), element("ol"));
+        let old_items = [element("li"), element("li"), element("li")];
+        old_ul.append_child(&old_items[0]).unwrap();
+        old_ol.append_child(&old_items[1]).unwrap();
+        old_ol.append_child(&old_items[2]).unwrap();
+        root.append_child(&old_ul).unwrap();
+        root.append_child(&old_ol).unwrap();
+
+        let item = || async {
+            ContainerNodeFuture::new(pending::<()>(), create_element("li").into()).await;
+        };
+        let mut fut = pin!(SsrRoot::hydrate(
+            async {
+                let ul = create_element("ul");
+                let ol = create_element("ol");
+                join((
+                    // the server rendered an item here that the app doesn't
+                    ContainerNodeFuture::new(pending::<()>(), ul.into()),
+                    ContainerNodeFuture::new(join((item(), item())), ol.into()),
+                ))
+                .await;
+            },
+            root.clone(),
+        ));
+        fut.as_mut().run_until_stalled();
+
+        assert_eq!(fut.to_html(), "<ul></ul><ol><li></li><li></li></ol>");
+        // the second list takes over its own items, not the first list's
+        assert!(old_ol
+            .first_child()
+            .unwrap()
+            .is_same_node(Some(&old_items[1])));
+        assert!(old_ol
+            .last_child()
+            .unwrap()
+            .is_same_node(Some(&old_items[2])));
+        assert!(old_items[0].parent_node().is_none());
+    }
+
+    #[test]
+    fn hydration_reconciles_content() {
+        use super::hydration::{create_element, create_text_node};
+
+        let root = dom::DocumentFragment::new().unwrap();
+        let old_div = element("div");
+        old_div.set_attribute("class", "stale shared").unwrap();
+        old_div.set_attribute("data-stale", "").unwrap();
+        old_div.set_attribute("id", "kept").unwrap();
+        let old_text = dom::Text::new_with_data("server").unwrap();
+        old_div.append_child(&old_text).unwrap();
+        root.append_child(&old_div).unwrap();
+
+        let mut fut = pin!(SsrRoot::hydrate(
+            async {
+                let div = create_element("div");
+                div.set_attribute("id", "kept").unwrap();
+                div.set_attribute("class", "shared").unwrap();
+                let text = create_text_node("client");
+                ContainerNodeFuture::new(
+                    ContainerNodeFuture::new(pending::<()>(), text.into()),
+                    div.into(),
+                )
+                .await;
+            },
+            root.clone(),
+        ));
+        fut.as_mut().run_until_stalled();
+
+        // only what the app set is left, on the same nodes
+        assert_eq!(
+            fut.to_html(),
+            r#"<div id="kept" class="shared">client</div>"#
+        );
+        assert!(root.first_child().unwrap().is_same_node(Some(&old_div)));
+        assert!(old_div.first_child().unwrap().is_same_node(Some(&old_text)));
+    }
+
+    #[test]
+    fn event_dispatch() {
+        let outer = element("div");
+        let inner = element("span");
+        outer.append_child(&inner).unwrap();
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let logger = |name: &'static str| {
+            let log = log.clone();
+            dom::EventListener::new(move |_| log.borrow_mut().push(name))
+        };
+        let stopper = dom::EventListener::new(|ev| ev.stop_propagation());
+        outer
+            .add_event_listener_with_callback("click", &logger("outer bubble"))
+            .unwrap();
+        outer
+            .add_event_listener_with_callback_and_bool("click", &logger("outer capture"), true)
+            .unwrap();
+        inner
+            .add_event_listener_with_callback("click", &logger("inner"))
+            .unwrap();
+
+        let mut init = dom::EventInit::new();
+        init.bubbles(true);
+        let event = dom::Event::new_with_event_init_dict("click", &init).unwrap();
+        inner.dispatch_event(&event).unwrap();
+        assert_eq!(*log.borrow(), ["outer capture", "inner", "outer bubble"]);
+        assert!(inner.dispatch_event(&event).is_err());
+
+        log.borrow_mut().clear();
+        inner
+            .add_event_listener_with_callback("click", &stopper)
+            .unwrap();
+        inner
+            .dispatch_event(&dom::Event::new_with_event_init_dict("click", &init).unwrap())
+            .unwrap();
+        assert_eq!(*log.borrow(), ["outer capture", "inner"]);
+    }
+
+    #[test]
+    fn query_selector() {
+        let root = dom::DocumentFragment::new().unwrap();
+        let list = element("ul");
+        list.set_attribute("class", "items main").unwrap();
+        for id in ["a", "b"] {
+            let item = element("li");
+            item.set_id(id);
+            item.set_attribute("data-kind", "item").unwrap();
+            list.append_child(&item).unwrap();
+        }
+        root.append_child(&list).unwrap();
+        root.append_child(&element("li")).unwrap();
+
+        let ids = |selectors| {
+            root.query_selector_all(selectors)
+                .unwrap()
+                .iter()
+                .map(|el| el.id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("li"), ["a", "b", ""]);
+        assert_eq!(ids("ul.main > li"), ["a", "b"]);
+        assert_eq!(ids(".items #b"), ["b"]);
+        assert!(root.query_selector_all("li:not(#a)").is_err());
+        assert_eq!(ids("#b, ul [data-kind=item]"), ["a", "b"]);
+        assert_eq!(
+            root.query_selector("ul li").unwrap().map(|el| el.id()),
+            Some("a".into())
+        );
+    }
+
+    #[test]
+    fn completed_future() {
+        let mut root = pin!(SsrRoot::new(async { 42 }));
+        assert!(root.as_mut().run_until_stalled());
+        assert_eq!(root.as_mut().take_output(), Some(42));
+    }
+
+    #[test]
+    fn leave_hook() {
+        use super::leaving::set_leave_hook;
+
+        let span = element("span");
+        let hooked = span.clone();
+        set_leave_hook(&span.clone().into(), move || {
+            hooked.class_list().add_1("leaving").unwrap();
+        });
+        let html = render_to_string(async {
+            let span = ContainerNodeFuture::new(pending::<()>(), span.into());
+            race((span, ready(()))).await;
+            // not hooked, so removed right away
+            race((text("gone"), ready(()))).await;
+            text("replaced").await;
+        });
+        assert_eq!(html, r#"<span class="leaving"></span>replaced"#);
+    }
+
+    #[test]
+    fn deferred_leave_hook() {
+        use std::{cell::RefCell, rc::Rc};
+
+        use super::leaving::{remove_leave_hook, set_leave_hook};
+
+        let spans: Vec<_> = (0..3).map(|_| element("span")).collect();
+        // the hooks only note down how to remove their node, to do it later
+        let deferred = Rc::new(RefCell::new(Vec::new()));
+        for span in &spans {
+            let node: dom::Node = span.clone().into();
+            let deferred = deferred.clone();
+            set_leave_hook(&node.clone(), move || {
+                deferred.borrow_mut().push(node);
+            });
+        }
+        remove_leave_hook(&spans[1].clone().into());
+        let mut root = pin!(SsrRoot::new(async {
+            let spans = spans
+                .iter()
+                .map(|span| ContainerNodeFuture::new(pending::<()>(), span.clone().into()));
+            race((
+                async {
+                    join(spans.collect::<Vec<_>>()).await;
+                },
+                ready(()),
+            ))
+            .await;
+            text("after").await;
+        }));
+        root.as_mut().run_until_stalled();
+        // the unhooked span is gone; the others wait for their hooks
+        assert_eq!(root.to_html(), "<span></span><span></span>after");
+        assert_eq!(deferred.borrow().len(), 2);
+        for node in deferred.take() {
+            root.container().remove_child(&node).unwrap();
+        }
+        assert_eq!(root.to_html(), "after");
+    }
+
+    #[test]
+    fn yield_now() {
+        let html = render_to_string(join((
+            async {
+                executor::yield_now().await;
+                text("after yield").await;
+            },
+            text("first"),
+        )));
+        assert_eq!(html, "after yieldfirst");
+    }
+
+    #[test]
+    fn independent_roots() {
+        use std::{cell::Cell, rc::Rc};
+
+        /// Counts its polls, and keeps its waker for the test to wake.
+        fn counting(
+            polls: Rc<Cell<u32>>,
+            waker: Rc<Cell<Option<Waker>>>,
+        ) -> impl Future<Output = ()> {
+            std::future::poll_fn(move |cx| {
+                polls.set(polls.get() + 1);
+                waker.set(Some(cx.waker().clone()));
+                Poll::Pending
+            })
+        }
+        let (a, b) = (RootExecutor::new(), RootExecutor::new());
+        a.set_scheduling(Scheduling::Manual);
+        b.set_scheduling(Scheduling::Manual);
+        let (a_polls, b_polls) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let a_waker = Rc::new(Cell::new(None));
+        a.set_future(Box::new(counting(a_polls.clone(), a_waker.clone())));
+        b.set_future(Box::new(counting(b_polls.clone(), Default::default())));
+        a.schedule();
+        b.schedule();
+        a.run_now();
+        b.run_now();
+        assert_eq!((a_polls.get(), b_polls.get()), (1, 1));
+
+        a_waker.take().unwrap().wake();
+        assert!(a.is_scheduled());
+        assert!(!b.is_scheduled());
+        a.run_now();
+        b.run_now();
+        assert_eq!((a_polls.get(), b_polls.get()), (2, 1));
+
+        // dropping a root drops its future
+        drop(b);
+        assert_eq!(Rc::strong_count(&b_polls), 1);
+        assert!(a.take_future().is_some());
+        assert_eq!(Rc::strong_count(&a_polls), 1);
+    }
+}
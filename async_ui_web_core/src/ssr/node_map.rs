@@ -0,0 +1,36 @@
+//! Maps keyed by node identity, for the in-memory DOM.
+
+use std::collections::HashMap;
+
+use super::dom::Node;
+
+/// A map from nodes (compared with `is_same_node`) to values.
+pub(crate) struct NodeMap<V> {
+    /// Nodes are kept alive so that their ids aren't reused.
+    entries: HashMap<usize, (Node, V)>,
+}
+
+impl<V> NodeMap<V> {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+    pub fn contains(&self, node: &Node) -> bool {
+        self.entries.contains_key(&node.id())
+    }
+    pub fn get(&self, node: &Node) -> Option<&V> {
+        self.entries.get(&node.id()).map(|(_, value)| value)
+    }
+    pub fn get_mut(&mut self, node: &Node) -> Option<&mut V> {
+        self.entries.get_mut(&node.id()).map(|(_, value)| value)
+    }
+    pub fn insert(&mut self, node: &Node, value: V) -> Option<V> {
+        self.entries
+            .insert(node.id(), (node.clone(), value))
+            .map(|(_, value)| value)
+    }
+    pub fn remove(&mut self, node: &Node) -> Option<V> {
+        self.entries.remove(&node.id()).map(|(_, value)| value)
+    }
+}
@@ -0,0 +1,202 @@
+//! `querySelector` for the in-memory DOM.
+//!
+//! Supported selectors are type (`div`), universal (`*`), id (`#id`),
+//! class (`.class`), and attribute (`[name]`, `[name=value]`, `[name="value"]`)
+//! selectors, combined with descendant (` `) and child (`>`) combinators,
+//! in comma-separated lists.
+
+use super::dom::{DomError, Element, Node};
+
+const INVALID: DomError = DomError("The selector is invalid or not supported.");
+
+#[derive(Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+#[derive(Clone, Copy)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// A selector without commas, stored right to left.
+/// Each compound after the first is paired with the combinator to its right.
+struct Complex {
+    subject: Compound,
+    ancestors: Vec<(Combinator, Compound)>,
+}
+
+pub(super) fn query_selector_all(
+    root: &Node,
+    selectors: &str,
+    first_only: bool,
+) -> Result<Vec<Element>, DomError> {
+    let selectors = selectors
+        .split(',')
+        .map(parse_complex)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut found = Vec::new();
+    let mut stack: Vec<Node> = Vec::new();
+    root.with_children(|children| stack.extend(children.iter().rev().cloned()));
+    while let Some(node) = stack.pop() {
+        node.with_children(|children| stack.extend(children.iter().rev().cloned()));
+        let Ok(element) = node.dyn_into_element() else {
+            continue;
+        };
+        if selectors.iter().any(|s| s.matches(&element)) {
+            found.push(element);
+            if first_only {
+                break;
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn parse_complex(input: &str) -> Result<Complex, DomError> {
+    let mut compounds = Vec::new();
+    let mut combinators = Vec::new();
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(INVALID);
+    }
+    while !rest.is_empty() {
+        let (compound, after) = parse_compound(rest)?;
+        compounds.push(compound);
+        let trimmed = after.trim_start();
+        if trimmed.is_empty() {
+            break;
+        }
+        if let Some(after_gt) = trimmed.strip_prefix('>') {
+            combinators.push(Combinator::Child);
+            rest = after_gt.trim_start();
+        } else if trimmed.len() < after.len() {
+            combinators.push(Combinator::Descendant);
+            rest = trimmed;
+        } else {
+            return Err(INVALID);
+        }
+        if rest.is_empty() {
+            return Err(INVALID);
+        }
+    }
+    let subject = compounds.pop().ok_or(INVALID)?;
+    let ancestors = combinators
+        .into_iter()
+        .rev()
+        .zip(compounds.into_iter().rev());
+    Ok(Complex {
+        subject,
+        ancestors: ancestors.collect(),
+    })
+}
+
+fn parse_compound(input: &str) -> Result<(Compound, &str), DomError> {
+    let mut compound = Compound::default();
+    let mut rest = input;
+    if let Some(after) = rest.strip_prefix('*') {
+        rest = after;
+    } else {
+        let (tag, after) = split_ident(rest);
+        if !tag.is_empty() {
+            compound.tag = Some(tag.to_ascii_lowercase());
+        }
+        rest = after;
+    }
+    loop {
+        if let Some(after) = rest.strip_prefix('#') {
+            let (id, after) = split_ident(after);
+            if id.is_empty() {
+                return Err(INVALID);
+            }
+            compound.id = Some(id.into());
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let (class, after) = split_ident(after);
+            if class.is_empty() {
+                return Err(INVALID);
+            }
+            compound.classes.push(class.into());
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let (inside, after) = after.split_once(']').ok_or(INVALID)?;
+            let attribute = match inside.split_once('=') {
+                Some((name, value)) => {
+                    let value = value.trim();
+                    let value = value
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                        .unwrap_or(value);
+                    (name.trim(), Some(value.into()))
+                }
+                None => (inside.trim(), None),
+            };
+            if attribute.0.is_empty() || !split_ident(attribute.0).1.is_empty() {
+                return Err(INVALID);
+            }
+            compound.attributes.push((attribute.0.into(), attribute.1));
+            rest = after;
+        } else {
+            break;
+        }
+    }
+    if rest.len() == input.len() {
+        return Err(INVALID);
+    }
+    Ok((compound, rest))
+}
+
+fn split_ident(input: &str) -> (&str, &str) {
+    let end = input
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(input.len());
+    input.split_at(end)
+}
+
+impl Complex {
+    fn matches(&self, element: &Element) -> bool {
+        self.subject.matches(element) && matches_ancestors(element, &self.ancestors)
+    }
+}
+
+fn matches_ancestors(element: &Element, ancestors: &[(Combinator, Compound)]) -> bool {
+    let Some(((combinator, compound), rest)) = ancestors.split_first() else {
+        return true;
+    };
+    let mut current = element.parent_element();
+    while let Some(ancestor) = current {
+        if compound.matches(&ancestor) && matches_ancestors(&ancestor, rest) {
+            return true;
+        }
+        if let Combinator::Child = combinator {
+            return false;
+        }
+        current = ancestor.parent_element();
+    }
+    false
+}
+
+impl Compound {
+    fn matches(&self, element: &Element) -> bool {
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| element.tag_name().eq_ignore_ascii_case(tag))
+            && self.id.as_ref().is_none_or(|id| element.id() == *id)
+            && self
+                .classes
+                .iter()
+                .all(|class| element.class_list().contains(class))
+            && self.attributes.iter().all(|(name, value)| {
+                match (element.get_attribute(name), value) {
+                    (Some(actual), Some(value)) => actual == *value,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                }
+            })
+    }
+}
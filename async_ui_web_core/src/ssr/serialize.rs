@@ -0,0 +1,71 @@
+//! Turning the in-memory tree into HTML text.
+
+use super::dom::{Node, NodeRef};
+
+/// Elements that can't have children and must not have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose text content is written out without escaping.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+pub(super) fn write_node(node: &Node, out: &mut String) {
+    match node.kind() {
+        NodeRef::Element {
+            tag_name,
+            attributes,
+        } => {
+            out.push('<');
+            out.push_str(tag_name);
+            for (name, value) in attributes.iter() {
+                out.push(' ');
+                out.push_str(name);
+                if !value.is_empty() {
+                    out.push_str("=\"");
+                    escape_into(value, true, out);
+                    out.push('"');
+                }
+            }
+            out.push('>');
+            if VOID_ELEMENTS.contains(&tag_name) {
+                return;
+            }
+            let raw = RAW_TEXT_ELEMENTS.contains(&tag_name);
+            node.with_children(|children| {
+                for child in children {
+                    match (raw, child.kind()) {
+                        (true, NodeRef::Text(text)) => out.push_str(&text),
+                        _ => write_node(child, out),
+                    }
+                }
+            });
+            out.push_str("</");
+            out.push_str(tag_name);
+            out.push('>');
+        }
+        NodeRef::Text(text) => escape_into(&text, false, out),
+        NodeRef::Comment(text) => {
+            out.push_str("<!--");
+            out.push_str(&text);
+            out.push_str("-->");
+        }
+        NodeRef::DocumentFragment => node.with_children(|children| {
+            children.iter().for_each(|child| write_node(child, out));
+        }),
+    }
+}
+
+fn escape_into(text: &str, attribute: bool, out: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' if !attribute => out.push_str("&lt;"),
+            '>' if !attribute => out.push_str("&gt;"),
+            '"' if attribute => out.push_str("&quot;"),
+            '\u{A0}' => out.push_str("&nbsp;"),
+            ch => out.push(ch),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use super::dom::Document;
+
+thread_local! {
+    /// The in-memory document. It can only create nodes.
+    pub static DOCUMENT: Document = Document::default();
+}
@@ -4,19 +4,18 @@ use std::{
     ops::Deref,
 };
 
-use async_ui_web_core::{window::DOCUMENT, ContainerNodeFuture};
-use wasm_bindgen::prelude::{JsCast, UnwrapThrowExt};
+use super::platform::{create_element, dom, ContainerNodeFuture};
 
 macro_rules! component_impl {
-    ($ty:ident, $tag_name:literal, $elem_ty:ty, $link:tt) => {
+    ($ty:ident, $tag:ident, $elem_ty:ty, $link:tt) => {
         #[doc = "The HTML `"]
-        #[doc = $tag_name]
+        #[doc = stringify!($tag)]
         #[doc = "` tag."]
         #[doc = "See"]
         #[doc = $link]
         #[doc = "."]
         pub struct $ty {
-            pub element: $elem_ty,
+            pub element: element_type!($elem_ty),
         }
         impl $ty {
             #[doc = "Create a new instance of this type."]
@@ -25,7 +24,7 @@ macro_rules! component_impl {
             #[doc = "Use the `.render(_)` method to do that."]
             pub fn new() -> Self {
                 Self {
-                    element: create_element($tag_name),
+                    element: create_element(stringify!($tag)),
                 }
             }
         }
@@ -35,22 +34,22 @@ macro_rules! component_impl {
             }
         }
         impl Deref for $ty {
-            type Target = $elem_ty;
+            type Target = element_type!($elem_ty);
             fn deref(&self) -> &Self::Target {
                 &self.element
             }
         }
         impl<X> AsRef<X> for $ty
         where
-            $elem_ty: AsRef<X>,
+            element_type!($elem_ty): AsRef<X>,
         {
             fn as_ref(&self) -> &X {
                 self.element.as_ref()
             }
         }
     };
-    ($ty:ident, $tag_name:literal, $elem_ty:ty, $link:tt, childed) => {
-        component_impl!($ty, $tag_name, $elem_ty, $link);
+    ($ty:ident, $tag:ident, $elem_ty:ty, $link:tt, childed) => {
+        component_impl!($ty, $tag, $elem_ty, $link);
         impl $ty {
             #[doc = "Put this HTML element on the screen."]
             #[doc = ""]
@@ -61,12 +60,18 @@ macro_rules! component_impl {
             #[doc = ""]
             #[doc = "This method should only be called once. It may misbehave otherwise."]
             pub fn render<F: Future>(&self, c: F) -> ContainerNodeFuture<F> {
-                ContainerNodeFuture::new(c, AsRef::<web_sys::Node>::as_ref(&self.element).clone())
+                ContainerNodeFuture::new(c, AsRef::<dom::Node>::as_ref(&self.element).clone())
+            }
+        }
+        impl<F: Future> RenderView<F> for $ty {
+            type Future = ContainerNodeFuture<F>;
+            fn render_view(&self, children: F) -> Self::Future {
+                self.render(children)
             }
         }
     };
-    ($ty:ident, $tag_name:literal, $elem_ty:ty, $link:tt, childless) => {
-        component_impl!($ty, $tag_name, $elem_ty, $link);
+    ($ty:ident, $tag:ident, $elem_ty:ty, $link:tt, childless) => {
+        component_impl!($ty, $tag, $elem_ty, $link);
         impl $ty {
             #[doc = "Put this HTML element on the screen."]
             #[doc = ""]
@@ -78,102 +83,228 @@ macro_rules! component_impl {
             pub fn render(&self) -> ContainerNodeFuture<Pending<()>> {
                 ContainerNodeFuture::new(
                     pending(),
-                    AsRef::<web_sys::Node>::as_ref(&self.element).clone(),
+                    AsRef::<dom::Node>::as_ref(&self.element).clone(),
                 )
             }
         }
+        impl RenderView<Pending<()>> for $ty {
+            type Future = ContainerNodeFuture<Pending<()>>;
+            fn render_view(&self, _children: Pending<()>) -> Self::Future {
+                self.render()
+            }
+        }
+    };
+}
+
+/// Implement all the components, and list them by tag name in [tags].
+macro_rules! components {
+    ($(($ty:ident, $tag:ident, $elem_ty:ty, $link:tt, $children:ident);)*) => {
+        #[rustfmt::skip]
+        mod impls {
+            use super::*;
+            $(component_impl!($ty, $tag, $elem_ty, $link, $children);)*
+        }
+        pub use impls::*;
+
+        /// Every component, named by its tag. Used by the `view!` macro of `async_ui_web`.
+        #[doc(hidden)]
+        pub mod tags {
+            $(pub use super::impls::$ty as $tag;)*
+        }
     };
 }
 
+/// Rendering with or without children, for the `view!` macro of `async_ui_web`.
+///
+/// Elements that can't have children only take [std::future::pending()].
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(message = "`{Self}` can't have children")]
+pub trait RenderView<F> {
+    type Future: Future;
+    fn render_view(&self, children: F) -> Self::Future;
+}
+
 #[rustfmt::skip]
-mod impls {
-    use super::*;
-    component_impl!(Anchor, "a", web_sys::HtmlAnchorElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/a)", childed);
-    component_impl!(Area, "area", web_sys::HtmlAreaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/area)", childless);
-    component_impl!(Audio, "audio", web_sys::HtmlAudioElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/audio)", childed);
-    component_impl!(Bold, "b", web_sys::HtmlBrElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/b)", childed);
-    component_impl!(Br, "br", web_sys::HtmlBrElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/br)", childless);
-    component_impl!(Base, "base", web_sys::HtmlBaseElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/base)", childless);
-    component_impl!(Button, "button", web_sys::HtmlButtonElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/button)", childed);
-    component_impl!(Canvas, "canvas", web_sys::HtmlCanvasElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/canvas)", childed);
-    component_impl!(Dl, "dl", web_sys::HtmlDListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dl)", childed);
-    component_impl!(Data, "data", web_sys::HtmlDataElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/data)", childed);
-    component_impl!(DataList, "datalist", web_sys::HtmlDataListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/datalist)", childed);
-    component_impl!(Dialog, "dialog", web_sys::HtmlDialogElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dialog)", childed);
-    component_impl!(Div, "div", web_sys::HtmlDivElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/div)", childed);
-    component_impl!(Embed, "embed", web_sys::HtmlEmbedElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/embed)", childless);
-    component_impl!(FieldSet, "fieldset", web_sys::HtmlFieldSetElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/fieldset)", childed);
-    component_impl!(Form, "form", web_sys::HtmlFormElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)", childed);
-    component_impl!(FrameSet, "frameset", web_sys::HtmlFrameSetElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/frameset)", childed);
-    component_impl!(Hr, "hr", web_sys::HtmlHrElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/hr)", childless);
-    component_impl!(H1, "h1", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h1)", childed);
-    component_impl!(H2, "h2", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h2)", childed);
-    component_impl!(H3, "h3", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h3)", childed);
-    component_impl!(H4, "h4", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h4)", childed);
-    component_impl!(H5, "h5", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h5)", childed);
-    component_impl!(H6, "h6", web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h6)", childed);
-    component_impl!(Italic, "i", web_sys::HtmlImageElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/i)", childed);
-    component_impl!(IFrame, "iframe", web_sys::HtmlIFrameElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/iframe)", childed);
-    component_impl!(Img, "img", web_sys::HtmlImageElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/img)", childed);
-    component_impl!(Input, "input", web_sys::HtmlInputElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input)", childless);
-    component_impl!(Li, "li", web_sys::HtmlLiElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/li)", childed);
-    component_impl!(Label, "label", web_sys::HtmlLabelElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/label)", childed);
-    component_impl!(Legend, "legend", web_sys::HtmlLegendElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/legend)", childed);
-    component_impl!(Link, "link", web_sys::HtmlLinkElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link)", childless);
-    component_impl!(Map, "map", web_sys::HtmlMapElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/map)", childed);
-    component_impl!(Meta, "meta", web_sys::HtmlMetaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta)", childless);
-    component_impl!(Meter, "meter", web_sys::HtmlMeterElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meter)", childed);
-    component_impl!(Ol, "ol", web_sys::HtmlOListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ol)", childed);
-    component_impl!(Object, "object", web_sys::HtmlObjectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/object)", childed);
-    component_impl!(OptGroup, "optgroup", web_sys::HtmlOptGroupElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/optgroup)", childed);
-    component_impl!(Option, "option", web_sys::HtmlOptionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/option)", childed);
-    component_impl!(Output, "output", web_sys::HtmlOutputElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/output)", childed);
-    component_impl!(Paragraph, "p", web_sys::HtmlParagraphElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/p)", childed);
-    component_impl!(Picture, "picture", web_sys::HtmlPictureElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/picture)", childed);
-    component_impl!(Pre, "pre", web_sys::HtmlPreElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/pre)", childed);
-    component_impl!(Progress, "progress", web_sys::HtmlProgressElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/progress)", childed);
-    component_impl!(Quote, "q", web_sys::HtmlQuoteElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/q)", childed);
-    component_impl!(Select, "select", web_sys::HtmlSelectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/select)", childed);
-    component_impl!(Source, "source", web_sys::HtmlSourceElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/source)", childless);
-    component_impl!(Span, "span", web_sys::HtmlSpanElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/span)", childed);
-    component_impl!(Style, "style", web_sys::HtmlStyleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/style)", childed);
-    component_impl!(Th, "th", web_sys::HtmlTableCellElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/th)", childed);
-    component_impl!(Td, "td", web_sys::HtmlTableCellElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/td)", childed);
-    component_impl!(Col, "col", web_sys::HtmlTableColElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/col)", childed);
-    component_impl!(ColGroup, "colgroup", web_sys::HtmlTableColElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/colgroup)", childed);
-    component_impl!(Table, "table", web_sys::HtmlTableElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/table)", childed);
-    component_impl!(Tr, "tr", web_sys::HtmlTableRowElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tr)", childed);
-    component_impl!(THead, "thead", web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/thead)", childed);
-    component_impl!(TFoot, "tfoot", web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tfoot)", childed);
-    component_impl!(TBody, "tbody", web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tbody)", childed);
-    component_impl!(Template, "template", web_sys::HtmlTemplateElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template)", childed);
-    component_impl!(TextArea, "textarea", web_sys::HtmlTextAreaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/textarea)", childed);
-    component_impl!(Time, "time", web_sys::HtmlTimeElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/time)", childed);
-    component_impl!(Track, "track", web_sys::HtmlTrackElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/track)", childless);
-    component_impl!(Ul, "ul", web_sys::HtmlUListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ul)", childed);
-    component_impl!(Video, "video", web_sys::HtmlVideoElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/video)", childed);
+components! {
+    (Anchor, a, web_sys::HtmlAnchorElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/a)", childed);
+    (Area, area, web_sys::HtmlAreaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/area)", childless);
+    (Audio, audio, web_sys::HtmlAudioElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/audio)", childed);
+    (Bold, b, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/b)", childed);
+    (Br, br, web_sys::HtmlBrElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/br)", childless);
+    (Base, base, web_sys::HtmlBaseElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/base)", childless);
+    (Button, button, web_sys::HtmlButtonElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/button)", childed);
+    (Canvas, canvas, web_sys::HtmlCanvasElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/canvas)", childed);
+    (Dl, dl, web_sys::HtmlDListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dl)", childed);
+    (Data, data, web_sys::HtmlDataElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/data)", childed);
+    (DataList, datalist, web_sys::HtmlDataListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/datalist)", childed);
+    (Dialog, dialog, web_sys::HtmlDialogElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dialog)", childed);
+    (Div, div, web_sys::HtmlDivElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/div)", childed);
+    (Embed, embed, web_sys::HtmlEmbedElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/embed)", childless);
+    (FieldSet, fieldset, web_sys::HtmlFieldSetElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/fieldset)", childed);
+    (Form, form, web_sys::HtmlFormElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)", childed);
+    (FrameSet, frameset, web_sys::HtmlFrameSetElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/frameset)", childed);
+    (Hr, hr, web_sys::HtmlHrElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/hr)", childless);
+    (H1, h1, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h1)", childed);
+    (H2, h2, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h2)", childed);
+    (H3, h3, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h3)", childed);
+    (H4, h4, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h4)", childed);
+    (H5, h5, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h5)", childed);
+    (H6, h6, web_sys::HtmlHeadingElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/h6)", childed);
+    (Italic, i, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/i)", childed);
+    (IFrame, iframe, web_sys::HtmlIFrameElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/iframe)", childed);
+    (Img, img, web_sys::HtmlImageElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/img)", childless);
+    (Input, input, web_sys::HtmlInputElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input)", childless);
+    (Li, li, web_sys::HtmlLiElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/li)", childed);
+    (Label, label, web_sys::HtmlLabelElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/label)", childed);
+    (Legend, legend, web_sys::HtmlLegendElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/legend)", childed);
+    (Link, link, web_sys::HtmlLinkElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link)", childless);
+    (Map, map, web_sys::HtmlMapElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/map)", childed);
+    (Meta, meta, web_sys::HtmlMetaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta)", childless);
+    (Meter, meter, web_sys::HtmlMeterElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meter)", childed);
+    (Ol, ol, web_sys::HtmlOListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ol)", childed);
+    (Object, object, web_sys::HtmlObjectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/object)", childed);
+    (OptGroup, optgroup, web_sys::HtmlOptGroupElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/optgroup)", childed);
+    (Option, option, web_sys::HtmlOptionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/option)", childed);
+    (Output, output, web_sys::HtmlOutputElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/output)", childed);
+    (Paragraph, p, web_sys::HtmlParagraphElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/p)", childed);
+    (Picture, picture, web_sys::HtmlPictureElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/picture)", childed);
+    (Pre, pre, web_sys::HtmlPreElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/pre)", childed);
+    (Progress, progress, web_sys::HtmlProgressElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/progress)", childed);
+    (Quote, q, web_sys::HtmlQuoteElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/q)", childed);
+    (Select, select, web_sys::HtmlSelectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/select)", childed);
+    (Source, source, web_sys::HtmlSourceElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/source)", childless);
+    (Span, span, web_sys::HtmlSpanElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/span)", childed);
+    (Style, style, web_sys::HtmlStyleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/style)", childed);
+    (Th, th, web_sys::HtmlTableCellElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/th)", childed);
+    (Td, td, web_sys::HtmlTableCellElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/td)", childed);
+    (Col, col, web_sys::HtmlTableColElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/col)", childless);
+    (ColGroup, colgroup, web_sys::HtmlTableColElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/colgroup)", childed);
+    (Table, table, web_sys::HtmlTableElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/table)", childed);
+    (Tr, tr, web_sys::HtmlTableRowElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tr)", childed);
+    (THead, thead, web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/thead)", childed);
+    (TFoot, tfoot, web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tfoot)", childed);
+    (TBody, tbody, web_sys::HtmlTableSectionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/tbody)", childed);
+    (Template, template, web_sys::HtmlTemplateElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template)", childed);
+    (TextArea, textarea, web_sys::HtmlTextAreaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/textarea)", childed);
+    (Time, time, web_sys::HtmlTimeElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/time)", childed);
+    (Track, track, web_sys::HtmlTrackElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/track)", childless);
+    (Ul, ul, web_sys::HtmlUListElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ul)", childed);
+    (Video, video, web_sys::HtmlVideoElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/video)", childed);
+    (Abbr, abbr, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/abbr)", childed);
+    (Address, address, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/address)", childed);
+    (Article, article, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/article)", childed);
+    (Aside, aside, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/aside)", childed);
+    (Bdi, bdi, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/bdi)", childed);
+    (Bdo, bdo, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/bdo)", childed);
+    (BlockQuote, blockquote, web_sys::HtmlQuoteElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/blockquote)", childed);
+    (Body, body, web_sys::HtmlBodyElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/body)", childed);
+    (Caption, caption, web_sys::HtmlTableCaptionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/caption)", childed);
+    (Cite, cite, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/cite)", childed);
+    (Code, code, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/code)", childed);
+    (Dd, dd, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dd)", childed);
+    (Del, del, web_sys::HtmlModElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/del)", childed);
+    (Details, details, web_sys::HtmlDetailsElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/details)", childed);
+    (Dfn, dfn, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dfn)", childed);
+    (Dt, dt, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/dt)", childed);
+    (Em, em, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/em)", childed);
+    (FigCaption, figcaption, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/figcaption)", childed);
+    (Figure, figure, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/figure)", childed);
+    (Footer, footer, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/footer)", childed);
+    (Head, head, web_sys::HtmlHeadElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/head)", childed);
+    (Header, header, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/header)", childed);
+    (HGroup, hgroup, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/hgroup)", childed);
+    (Html, html, web_sys::HtmlHtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/html)", childed);
+    (Ins, ins, web_sys::HtmlModElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ins)", childed);
+    (Kbd, kbd, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/kbd)", childed);
+    (Main, main, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/main)", childed);
+    (Mark, mark, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/mark)", childed);
+    (Menu, menu, web_sys::HtmlMenuElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/menu)", childed);
+    (Nav, nav, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/nav)", childed);
+    (NoScript, noscript, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/noscript)", childed);
+    (Rp, rp, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/rp)", childed);
+    (Rt, rt, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/rt)", childed);
+    (Ruby, ruby, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/ruby)", childed);
+    (Samp, samp, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/samp)", childed);
+    (Script, script, web_sys::HtmlScriptElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/script)", childed);
+    (Search, search, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/search)", childed);
+    (Section, section, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/section)", childed);
+    (Slot, slot, web_sys::HtmlSlotElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/slot)", childed);
+    (Small, small, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/small)", childed);
+    (Strikethrough, s, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/s)", childed);
+    (Strong, strong, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/strong)", childed);
+    (Sub, sub, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/sub)", childed);
+    (Summary, summary, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/summary)", childed);
+    (Sup, sup, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/sup)", childed);
+    (Title, title, web_sys::HtmlTitleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/title)", childed);
+    (Underline, u, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/u)", childed);
+    (Var, var, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/var)", childed);
+    (Wbr, wbr, web_sys::HtmlElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/wbr)", childless);
 }
-pub use impls::*;
 
-/// Make an HTML element with your own tag.
-pub struct CustomElement {
-    pub element: web_sys::HtmlElement,
+/// An HTML element with any tag.
+///
+/// Use this for tags that don't have their own type here, such as custom elements.
+/// ```rust
+/// # use async_ui_web_html::nodes::Element;
+/// # let _ = async {
+/// let widget = Element::new_tag("my-widget");
+/// widget.render(std::future::pending::<()>()).await;
+/// # };
+/// ```
+pub struct Element {
+    pub element: dom::HtmlElement,
 }
 
-impl CustomElement {
-    pub fn new(tag_name: Cow<'static, str>) -> Self {
+/// The old name of [Element].
+pub type CustomElement = Element;
+
+impl Element {
+    /// Create an element with the given tag name.
+    ///
+    /// This creates the HTML node, but doesn't put it on the screen yet.
+    /// Use the `.render(_)` method to do that.
+    pub fn new_tag(tag_name: &str) -> Self {
         Self {
-            element: create_element(&tag_name),
+            element: create_element(tag_name),
         }
     }
 
+    /// Same as [new_tag][Self::new_tag].
+    pub fn new(tag_name: Cow<'static, str>) -> Self {
+        Self::new_tag(&tag_name)
+    }
+
+    /// Put this HTML element on the screen.
+    ///
+    /// The return Future completes when the given argument Future finishes.
+    /// Anything the argument Future renders will be in the HTML tag of this element.
+    /// Elements that can't have children can be given [std::future::pending()].
+    ///
+    /// When the returned Future is dropped, the HTML element will be removed.
     pub fn render<F: Future>(&self, c: F) -> ContainerNodeFuture<F> {
-        ContainerNodeFuture::new(c, AsRef::<web_sys::Node>::as_ref(&self.element).clone())
+        ContainerNodeFuture::new(c, AsRef::<dom::Node>::as_ref(&self.element).clone())
+    }
+}
+
+impl<F: Future> RenderView<F> for Element {
+    type Future = ContainerNodeFuture<F>;
+    fn render_view(&self, children: F) -> Self::Future {
+        self.render(children)
+    }
+}
+
+impl Deref for Element {
+    type Target = dom::HtmlElement;
+    fn deref(&self) -> &Self::Target {
+        &self.element
     }
 }
 
-fn create_element<E: JsCast>(tag_name: &str) -> E {
-    DOCUMENT
-        .with(|doc| doc.create_element(tag_name).unwrap_throw())
-        .unchecked_into()
+impl<X> AsRef<X> for Element
+where
+    dom::HtmlElement: AsRef<X>,
+{
+    fn as_ref(&self) -> &X {
+        self.element.as_ref()
+    }
 }
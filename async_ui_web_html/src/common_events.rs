@@ -1,5 +1,7 @@
-use crate::events::{EmitEvent, EventFutureStream};
-use web_sys::{Element, HtmlElement};
+use super::{
+    events::{EmitEvent, EventFutureStream},
+    platform::dom::{Element, HtmlElement, SvgElement},
+};
 
 macro_rules! make_event_impl {
     ($ev_name:literal, $func_name:ident, $ty:ty, $link:tt) => {
@@ -10,7 +12,7 @@ macro_rules! make_event_impl {
         #[doc = "See"]
         #[doc = $link]
         #[doc = "."]
-        fn $func_name(&self) -> EventFutureStream<$ty> {
+        fn $func_name(&self) -> EventFutureStream<event_type!($ty)> {
             self.as_ref().until_event($ev_name.into())
         }
     };
@@ -88,3 +90,18 @@ pub trait EmitHtmlElementEvent: AsRef<HtmlElement> {
 }
 
 impl EmitHtmlElementEvent for HtmlElement {}
+
+/// Subscribe to events emitted by `SVGElement`s, such as `load`
+/// or the events of [SVG animations](crate::svg::Animate).
+///
+/// Other events, like `click`, are in [EmitElementEvent].
+#[rustfmt::skip]
+pub trait EmitSvgElementEvent: AsRef<SvgElement> {
+    make_event_impl!("load", until_load, web_sys::Event, "[MDN documentation for this event](https://developer.mozilla.org/en-US/docs/Web/API/SVGElement/load_event)");
+
+    make_event_impl!("beginEvent", until_begin_event, web_sys::TimeEvent, "[MDN documentation for this event](https://developer.mozilla.org/en-US/docs/Web/API/SVGAnimationElement/beginEvent_event)");
+    make_event_impl!("endEvent", until_end_event, web_sys::TimeEvent, "[MDN documentation for this event](https://developer.mozilla.org/en-US/docs/Web/API/SVGAnimationElement/endEvent_event)");
+    make_event_impl!("repeatEvent", until_repeat_event, web_sys::TimeEvent, "[MDN documentation for this event](https://developer.mozilla.org/en-US/docs/Web/API/SVGAnimationElement/repeatEvent_event)");
+}
+
+impl EmitSvgElementEvent for SvgElement {}
@@ -8,8 +8,16 @@ use std::{
 
 use async_ui_internal_utils::dummy_waker::dummy_waker;
 use futures_core::Stream;
-use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
-use web_sys::{AddEventListenerOptions, EventTarget};
+
+use super::platform::{dom::EventTarget, EventBound, Listener};
+
+/// Types that events can be received as.
+///
+/// In the browser, this is implemented for every `JsCast` type.
+/// On the server (in the `ssr` module), it is implemented for types that can
+/// be made from the in-memory `Event`.
+pub trait EventType: EventBound + 'static {}
+impl<E: EventBound + 'static> EventType for E {}
 
 /// A struct implementing both [Future] and [Stream].
 /// Yields [Event][web_sys::Event] objects.
@@ -24,23 +32,28 @@ use web_sys::{AddEventListenerOptions, EventTarget};
 ///     This means if you use some custom manually-implemented wrapper futures and
 ///     fail to poll the Stream upon `wake`, you might miss some
 ///     in-between events.
+///
+/// On the server, the stream only fires for events dispatched with
+/// `dispatch_event` on the in-memory DOM.
 pub struct EventFutureStream<E> {
     target: EventTarget,
-    closure: Option<Closure<dyn Fn(web_sys::Event)>>,
+    listener: Option<Listener>,
     shared: Rc<RefCell<(Option<E>, Waker)>>,
-    options: Option<AddEventListenerOptions>,
+    capture: Option<bool>,
+    passive: Option<bool>,
     event_name: Cow<'static, str>,
 }
 
-impl<E: JsCast> EventFutureStream<E> {
+impl<E: EventType> EventFutureStream<E> {
     /// Prefer to use [until_event][crate::events::EmitEvent::until_event] or other until_*
     /// methods instead of this.
     pub fn new(target: EventTarget, event_name: Cow<'static, str>) -> Self {
         Self {
             target,
-            closure: None,
+            listener: None,
             shared: Rc::new(RefCell::new((None, dummy_waker()))),
-            options: None,
+            capture: None,
+            passive: None,
             event_name,
         }
     }
@@ -53,9 +66,7 @@ impl<E: JsCast> EventFutureStream<E> {
     ///
     /// This needs to be set *before* you first poll the stream.
     pub fn set_capture(&mut self, capture: bool) {
-        self.options
-            .get_or_insert_with(AddEventListenerOptions::new)
-            .capture(capture);
+        self.capture = Some(capture);
     }
     /// The `passive` option indicates that the function specified by listener
     /// will never call `preventDefault()`.
@@ -68,13 +79,11 @@ impl<E: JsCast> EventFutureStream<E> {
     ///
     /// This needs to be set *before* you first poll the stream.
     pub fn set_passive(&mut self, passive: bool) {
-        self.options
-            .get_or_insert_with(AddEventListenerOptions::new)
-            .passive(passive);
+        self.passive = Some(passive);
     }
 }
 
-impl<E: JsCast + 'static> Future for EventFutureStream<E> {
+impl<E: EventType> Future for EventFutureStream<E> {
     type Output = E;
 
     fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
@@ -84,7 +93,7 @@ impl<E: JsCast + 'static> Future for EventFutureStream<E> {
         }
     }
 }
-impl<E: JsCast + 'static> Stream for EventFutureStream<E> {
+impl<E: EventType> Stream for EventFutureStream<E> {
     type Item = E;
 
     fn poll_next(
@@ -101,31 +110,21 @@ impl<E: JsCast + 'static> Stream for EventFutureStream<E> {
             }
         }
 
-        if this.closure.is_none() {
+        if this.listener.is_none() {
             let shared_weak = Rc::downgrade(&this.shared);
-            let closure = Closure::new(move |ev: web_sys::Event| {
-                if let Some(strong) = shared_weak.upgrade() {
-                    let inner = &mut *strong.borrow_mut();
-                    inner.0 = Some(ev.unchecked_into());
-                    inner.1.wake_by_ref();
-                }
-                async_ui_web_core::executor::run_now();
-            });
-            let listener = closure.as_ref().unchecked_ref();
-            if let Some(options) = &this.options {
-                this.target
-                    .add_event_listener_with_callback_and_add_event_listener_options(
-                        &this.event_name,
-                        listener,
-                        options,
-                    )
-                    .unwrap_throw();
-            } else {
-                this.target
-                    .add_event_listener_with_callback(&this.event_name, listener)
-                    .unwrap_throw();
-            }
-            this.closure = Some(closure);
+            this.listener = Some(Listener::add(
+                &this.target,
+                this.event_name.clone(),
+                this.capture,
+                this.passive,
+                move |ev| {
+                    if let Some(strong) = shared_weak.upgrade() {
+                        let inner = &mut *strong.borrow_mut();
+                        inner.0 = Some(E::from_event(ev));
+                        inner.1.wake_by_ref();
+                    }
+                },
+            ));
             Poll::Pending
         } else if let Some(ev) = this.shared.borrow_mut().0.take() {
             Poll::Ready(Some(ev))
@@ -147,24 +146,11 @@ impl<E: JsCast + 'static> Stream for EventFutureStream<E> {
 pub trait EmitEvent {
     /// Wait until an event with the specified name is fired.
     /// The return type is both a [Future] and a [Stream] that yields the event object.
-    fn until_event<E: JsCast + 'static>(&self, name: Cow<'static, str>) -> EventFutureStream<E>;
+    fn until_event<E: EventType>(&self, name: Cow<'static, str>) -> EventFutureStream<E>;
 }
 
 impl EmitEvent for EventTarget {
-    fn until_event<E: JsCast + 'static>(&self, name: Cow<'static, str>) -> EventFutureStream<E> {
+    fn until_event<E: EventType>(&self, name: Cow<'static, str>) -> EventFutureStream<E> {
         EventFutureStream::new(self.to_owned(), name)
     }
 }
-
-impl<E> Drop for EventFutureStream<E> {
-    fn drop(&mut self) {
-        if let Some(callback) = self.closure.take() {
-            self.target
-                .remove_event_listener_with_callback(
-                    &self.event_name,
-                    callback.as_ref().unchecked_ref(),
-                )
-                .unwrap_throw();
-        }
-    }
-}
@@ -1,7 +1,13 @@
+#[macro_use]
+mod platform;
+
 mod common_components;
 mod common_events;
 mod event_handling;
 mod input_types;
+#[cfg(feature = "ssr")]
+pub mod ssr;
+mod svg_components;
 mod text_node;
 
 pub mod events {
@@ -30,8 +36,8 @@ pub mod events {
     ```
     */
 
-    pub use super::common_events::{EmitElementEvent, EmitHtmlElementEvent};
-    pub use super::event_handling::{EmitEvent, EventFutureStream};
+    pub use super::common_events::{EmitElementEvent, EmitHtmlElementEvent, EmitSvgElementEvent};
+    pub use super::event_handling::{EmitEvent, EventFutureStream, EventType};
 }
 pub mod nodes {
     /*!
@@ -48,7 +54,34 @@ pub mod nodes {
     Most types here are named after the HTML tag they represent, for example
     [Input] corresponds to HTML `<input>`. There are some exceptions such as
     [Anchor] corresponding to `<a>` and [Bold] corresponding to `<b>`.
+
+    For tags that don't have a type here, use [Element::new_tag].
     */
     pub use super::common_components::*;
     pub use super::text_node::Text;
 }
+pub mod svg {
+    /*!
+    For creating SVG elements.
+
+    ```rust
+    # use async_ui_web_html::svg::{Circle, Svg};
+    # let _ = async {
+    let icon = Svg::new();
+    icon.set_attribute("viewBox", "0 0 10 10").unwrap();
+    let dot = Circle::new();
+    dot.set_attribute("r", "5").unwrap();
+    icon.render(dot.render(std::future::pending::<()>())).await;
+    # };
+    ```
+
+    Each type here is named after the SVG tag it represents, for example
+    [Path] corresponds to `<path>`, except for [Anchor] which corresponds to `<a>`.
+    For tags that don't have a type here, use [Element::new_tag].
+
+    These are created in the SVG namespace, so they draw properly when put in an [Svg].
+    Listen to their events with [EmitElementEvent][crate::events::EmitElementEvent]
+    and [EmitSvgElementEvent][crate::events::EmitSvgElementEvent].
+    */
+    pub use super::svg_components::*;
+}
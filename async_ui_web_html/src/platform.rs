@@ -0,0 +1,119 @@
+//! What the modules shared with `ssr` need from the browser.
+//!
+//! The `ssr` module compiles the same modules again, against the
+//! `platform` module next to it, which has the same items for the in-memory DOM.
+
+use std::borrow::Cow;
+
+use async_ui_web_core::executor::RootExecutor;
+pub(crate) use async_ui_web_core::{dom, hydration, ContainerNodeFuture};
+use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+use web_sys::AddEventListenerOptions;
+
+use super::svg_components::SVG_NAMESPACE;
+
+/// The type to use for an element.
+macro_rules! element_type {
+    ($ty:ty) => {
+        $ty
+    };
+}
+
+/// The type to use for an event.
+macro_rules! event_type {
+    ($ty:ty) => {
+        $ty
+    };
+}
+
+/// The type to use for an SVG element.
+macro_rules! svg_element_type {
+    ($ty:ty) => {
+        $ty
+    };
+}
+
+pub(crate) fn create_element<E: JsCast>(tag_name: &str) -> E {
+    hydration::create_element(tag_name).unchecked_into()
+}
+
+pub(crate) fn create_svg_element<E: JsCast>(tag_name: &str) -> E {
+    hydration::create_element_ns(Some(SVG_NAMESPACE), tag_name).unchecked_into()
+}
+
+/// Implemented for every `JsCast` type.
+pub trait EventBound: Sized {
+    fn from_event(event: dom::Event) -> Self;
+}
+impl<E: JsCast> EventBound for E {
+    fn from_event(event: dom::Event) -> Self {
+        event.unchecked_into()
+    }
+}
+
+/// An event listener, removed when dropped.
+pub(crate) struct Listener {
+    target: dom::EventTarget,
+    event_name: Cow<'static, str>,
+    capture: bool,
+    closure: Closure<dyn Fn(dom::Event)>,
+}
+
+impl Listener {
+    /// Options that are `None` are left to the browser's default.
+    // `set_capture` and `set_passive` aren't in the web-sys versions we support.
+    #[allow(deprecated)]
+    pub fn add(
+        target: &dom::EventTarget,
+        event_name: Cow<'static, str>,
+        capture: Option<bool>,
+        passive: Option<bool>,
+        callback: impl Fn(dom::Event) + 'static,
+    ) -> Self {
+        // run the app this listener belongs to
+        let root = RootExecutor::current();
+        let closure = Closure::<dyn Fn(dom::Event)>::new(move |ev| {
+            callback(ev);
+            root.run_now();
+        });
+        let listener = closure.as_ref().unchecked_ref();
+        if capture.is_some() || passive.is_some() {
+            let mut options = AddEventListenerOptions::new();
+            if let Some(capture) = capture {
+                options.capture(capture);
+            }
+            if let Some(passive) = passive {
+                options.passive(passive);
+            }
+            target
+                .add_event_listener_with_callback_and_add_event_listener_options(
+                    &event_name,
+                    listener,
+                    &options,
+                )
+                .unwrap_throw();
+        } else {
+            target
+                .add_event_listener_with_callback(&event_name, listener)
+                .unwrap_throw();
+        }
+        Self {
+            target: target.clone(),
+            event_name,
+            capture: capture.unwrap_or(false),
+            closure,
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.target
+            .remove_event_listener_with_callback_and_bool(
+                &self.event_name,
+                self.closure.as_ref().unchecked_ref(),
+                self.capture,
+            )
+            .unwrap_throw();
+    }
+}
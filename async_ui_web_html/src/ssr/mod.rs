@@ -0,0 +1,47 @@
+/*!
+The same elements and events, for server-side rendering.
+
+Everything here is like its counterpart at the crate root, but made for the
+in-memory DOM of `async_ui_web_core::ssr`.
+In the in-memory DOM, all HTML elements have the same type
+([HtmlElement][async_ui_web_core::ssr::dom::HtmlElement]), and so do
+all SVG elements and all events.
+*/
+
+#[macro_use]
+mod platform;
+
+// Shared with the crate root, compiled again for the in-memory DOM.
+#[allow(clippy::duplicate_mod)]
+#[path = "../common_components.rs"]
+mod common_components;
+#[allow(clippy::duplicate_mod)]
+#[path = "../common_events.rs"]
+mod common_events;
+#[allow(clippy::duplicate_mod)]
+#[path = "../event_handling.rs"]
+mod event_handling;
+#[allow(clippy::duplicate_mod)]
+#[path = "../input_types.rs"]
+mod input_types;
+#[allow(clippy::duplicate_mod)]
+#[path = "../svg_components.rs"]
+mod svg_components;
+#[allow(clippy::duplicate_mod)]
+#[path = "../text_node.rs"]
+mod text_node;
+
+pub mod events {
+    //! Like [events][crate::events], for the in-memory DOM.
+    pub use super::common_events::{EmitElementEvent, EmitHtmlElementEvent, EmitSvgElementEvent};
+    pub use super::event_handling::{EmitEvent, EventFutureStream, EventType};
+}
+pub mod nodes {
+    //! Like [nodes][crate::nodes], for the in-memory DOM.
+    pub use super::common_components::*;
+    pub use super::text_node::Text;
+}
+pub mod svg {
+    //! Like [svg][crate::svg], for the in-memory DOM.
+    pub use super::svg_components::*;
+}
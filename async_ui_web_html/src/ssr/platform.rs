@@ -0,0 +1,94 @@
+//! What the modules shared with the browser need from the in-memory DOM.
+//! See the `platform` module at the crate root.
+
+use std::borrow::Cow;
+
+pub(crate) use async_ui_web_core::ssr::{dom, hydration, ContainerNodeFuture};
+use dom::EventListener;
+
+use super::svg_components::SVG_NAMESPACE;
+
+/// The type to use for an element.
+/// All elements are represented by just one type.
+macro_rules! element_type {
+    ($ty:ty) => {
+        ::async_ui_web_core::ssr::dom::HtmlElement
+    };
+}
+
+/// The type to use for an event.
+/// All events are represented by just one type.
+macro_rules! event_type {
+    ($ty:ty) => {
+        ::async_ui_web_core::ssr::dom::Event
+    };
+}
+
+/// The type to use for an SVG element.
+/// All SVG elements are represented by just one type.
+macro_rules! svg_element_type {
+    ($ty:ty) => {
+        ::async_ui_web_core::ssr::dom::SvgElement
+    };
+}
+
+pub(crate) fn create_element(tag_name: &str) -> dom::HtmlElement {
+    hydration::create_element(tag_name).unchecked_into_html_element()
+}
+
+pub(crate) fn create_svg_element(tag_name: &str) -> dom::SvgElement {
+    hydration::create_element_ns(Some(SVG_NAMESPACE), tag_name).unchecked_into_svg_element()
+}
+
+/// Implemented for types that can be made from [Event][dom::Event].
+pub trait EventBound: Sized {
+    fn from_event(event: dom::Event) -> Self;
+}
+impl<E: From<dom::Event>> EventBound for E {
+    fn from_event(event: dom::Event) -> Self {
+        event.into()
+    }
+}
+
+/// An event listener, removed when dropped.
+pub(crate) struct Listener {
+    target: dom::EventTarget,
+    event_name: Cow<'static, str>,
+    capture: bool,
+    listener: EventListener,
+}
+
+impl Listener {
+    /// Passive listeners make no difference here.
+    pub fn add(
+        target: &dom::EventTarget,
+        event_name: Cow<'static, str>,
+        capture: Option<bool>,
+        _passive: Option<bool>,
+        callback: impl Fn(dom::Event) + 'static,
+    ) -> Self {
+        let capture = capture.unwrap_or(false);
+        let listener = EventListener::new(callback);
+        target
+            .add_event_listener_with_callback_and_bool(&event_name, &listener, capture)
+            .unwrap();
+        Self {
+            target: target.clone(),
+            event_name,
+            capture,
+            listener,
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.target
+            .remove_event_listener_with_callback_and_bool(
+                &self.event_name,
+                &self.listener,
+                self.capture,
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use std::{future::Future, ops::Deref};
+
+use super::platform::{create_svg_element, dom, ContainerNodeFuture};
+
+/// The namespace SVG elements must be created in.
+/// Elements created without it are HTML elements, which don't draw anything.
+pub(crate) const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+macro_rules! svg_component_impl {
+    ($ty:ident, $tag_name:literal, $elem_ty:ty, $link:tt) => {
+        #[doc = "The SVG `"]
+        #[doc = $tag_name]
+        #[doc = "` element."]
+        #[doc = "See"]
+        #[doc = $link]
+        #[doc = "."]
+        pub struct $ty {
+            pub element: svg_element_type!($elem_ty),
+        }
+        impl $ty {
+            #[doc = "Create a new instance of this type."]
+            #[doc = ""]
+            #[doc = "This creates the SVG node, but doesn't put it on the screen yet."]
+            #[doc = "Use the `.render(_)` method to do that."]
+            pub fn new() -> Self {
+                Self {
+                    element: create_svg_element($tag_name),
+                }
+            }
+            #[doc = "Put this SVG element on the screen."]
+            #[doc = ""]
+            #[doc = "The return Future completes when the given argument Future finishes."]
+            #[doc = "Anything the argument Future renders will be in this element."]
+            #[doc = ""]
+            #[doc = "When the returned Future is dropped, the SVG element will be removed."]
+            #[doc = ""]
+            #[doc = "This method should only be called once. It may misbehave otherwise."]
+            pub fn render<F: Future>(&self, c: F) -> ContainerNodeFuture<F> {
+                ContainerNodeFuture::new(c, AsRef::<dom::Node>::as_ref(&self.element).clone())
+            }
+        }
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        impl Deref for $ty {
+            type Target = svg_element_type!($elem_ty);
+            fn deref(&self) -> &Self::Target {
+                &self.element
+            }
+        }
+        impl<X> AsRef<X> for $ty
+        where
+            svg_element_type!($elem_ty): AsRef<X>,
+        {
+            fn as_ref(&self) -> &X {
+                self.element.as_ref()
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+mod impls {
+    use super::*;
+    svg_component_impl!(Svg, "svg", web_sys::SvgsvgElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/svg)");
+    svg_component_impl!(G, "g", web_sys::SvggElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/g)");
+    svg_component_impl!(Path, "path", web_sys::SvgPathElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/path)");
+    svg_component_impl!(Circle, "circle", web_sys::SvgCircleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/circle)");
+    svg_component_impl!(Ellipse, "ellipse", web_sys::SvgEllipseElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/ellipse)");
+    svg_component_impl!(Rect, "rect", web_sys::SvgRectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/rect)");
+    svg_component_impl!(Line, "line", web_sys::SvgLineElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/line)");
+    svg_component_impl!(Polyline, "polyline", web_sys::SvgPolylineElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/polyline)");
+    svg_component_impl!(Polygon, "polygon", web_sys::SvgPolygonElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/polygon)");
+    svg_component_impl!(Text, "text", web_sys::SvgTextElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/text)");
+    svg_component_impl!(TSpan, "tspan", web_sys::SvgtSpanElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/tspan)");
+    svg_component_impl!(TextPath, "textPath", web_sys::SvgTextPathElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/textPath)");
+    svg_component_impl!(Defs, "defs", web_sys::SvgDefsElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/defs)");
+    svg_component_impl!(Use, "use", web_sys::SvgUseElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/use)");
+    svg_component_impl!(Symbol, "symbol", web_sys::SvgSymbolElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/symbol)");
+    svg_component_impl!(LinearGradient, "linearGradient", web_sys::SvgLinearGradientElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/linearGradient)");
+    svg_component_impl!(RadialGradient, "radialGradient", web_sys::SvgRadialGradientElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/radialGradient)");
+    svg_component_impl!(Stop, "stop", web_sys::SvgStopElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/stop)");
+    svg_component_impl!(ClipPath, "clipPath", web_sys::SvgClipPathElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/clipPath)");
+    svg_component_impl!(Mask, "mask", web_sys::SvgMaskElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/mask)");
+    svg_component_impl!(Pattern, "pattern", web_sys::SvgPatternElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/pattern)");
+    svg_component_impl!(Marker, "marker", web_sys::SvgMarkerElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/marker)");
+    svg_component_impl!(Image, "image", web_sys::SvgImageElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/image)");
+    svg_component_impl!(ForeignObject, "foreignObject", web_sys::SvgForeignObjectElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/foreignObject)");
+    svg_component_impl!(Anchor, "a", web_sys::SvgaElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/a)");
+    svg_component_impl!(Switch, "switch", web_sys::SvgSwitchElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/switch)");
+    svg_component_impl!(Filter, "filter", web_sys::SvgFilterElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/filter)");
+    svg_component_impl!(Title, "title", web_sys::SvgTitleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/title)");
+    svg_component_impl!(Desc, "desc", web_sys::SvgDescElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/desc)");
+    svg_component_impl!(Metadata, "metadata", web_sys::SvgMetadataElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/metadata)");
+    svg_component_impl!(View, "view", web_sys::SvgViewElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/view)");
+    svg_component_impl!(Style, "style", web_sys::SvgStyleElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/style)");
+    svg_component_impl!(Script, "script", web_sys::SvgScriptElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/script)");
+    svg_component_impl!(Animate, "animate", web_sys::SvgAnimateElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/animate)");
+    svg_component_impl!(AnimateMotion, "animateMotion", web_sys::SvgAnimateMotionElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/animateMotion)");
+    svg_component_impl!(AnimateTransform, "animateTransform", web_sys::SvgAnimateTransformElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/animateTransform)");
+    svg_component_impl!(MPath, "mpath", web_sys::SvgmPathElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/mpath)");
+    svg_component_impl!(Set, "set", web_sys::SvgSetElement, "[the documentation on MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/set)");
+}
+pub use impls::*;
+
+/// An SVG element with any tag, such as `feGaussianBlur`.
+pub struct Element {
+    pub element: dom::SvgElement,
+}
+
+impl Element {
+    /// Create an SVG element with the given tag name.
+    ///
+    /// This creates the SVG node, but doesn't put it on the screen yet.
+    /// Use the `.render(_)` method to do that.
+    pub fn new_tag(tag_name: &str) -> Self {
+        Self {
+            element: create_svg_element(tag_name),
+        }
+    }
+
+    /// Put this SVG element on the screen.
+    ///
+    /// When the returned Future is dropped, the SVG element will be removed.
+    pub fn render<F: Future>(&self, c: F) -> ContainerNodeFuture<F> {
+        ContainerNodeFuture::new(c, AsRef::<dom::Node>::as_ref(&self.element).clone())
+    }
+}
+
+impl Deref for Element {
+    type Target = dom::SvgElement;
+    fn deref(&self) -> &Self::Target {
+        &self.element
+    }
+}
+
+impl<X> AsRef<X> for Element
+where
+    dom::SvgElement: AsRef<X>,
+{
+    fn as_ref(&self) -> &X {
+        self.element.as_ref()
+    }
+}
@@ -3,17 +3,17 @@ use std::{
     ops::Deref,
 };
 
-use async_ui_web_core::{window::DOCUMENT, ContainerNodeFuture};
+use super::platform::{dom, hydration, ContainerNodeFuture};
 
 /// An HTML text node.
 pub struct Text {
-    pub node: web_sys::Text,
+    pub node: dom::Text,
 }
 
 impl Text {
     pub fn new() -> Self {
         Self {
-            node: DOCUMENT.with(|doc| doc.create_text_node("")),
+            node: hydration::create_text_node(""),
         }
     }
     pub fn render(&self) -> ContainerNodeFuture<Pending<()>> {
@@ -28,7 +28,7 @@ impl Default for Text {
 }
 
 impl Deref for Text {
-    type Target = web_sys::Text;
+    type Target = dom::Text;
 
     fn deref(&self) -> &Self::Target {
         &self.node
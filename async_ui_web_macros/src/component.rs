@@ -0,0 +1,497 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt,
+    parse_macro_input, parse_quote,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Attribute, Expr, FnArg, GenericArgument, GenericParam, Generics, Ident, ItemFn, Lifetime, Pat,
+    PathArguments, ReturnType, Token, Type,
+};
+
+pub fn component_macro(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "`#[component]` doesn't take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let item = parse_macro_input!(input as ItemFn);
+    match generate(item) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
+struct Prop {
+    name: Ident,
+    docs: Vec<Attribute>,
+    ty: Type,
+    kind: PropKind,
+}
+
+enum PropKind {
+    /// Must be set before rendering.
+    Required,
+    /// `Option<T>`: set with a `T`, or left as `None`.
+    Optional(Type),
+    /// `#[prop(default)]` or `#[prop(default = ...)]`.
+    Default(Expr),
+    /// The `children` argument: given to `render` instead of a setter.
+    Children,
+}
+
+/// Gives elided lifetimes in the type a name, so that the type can go in the props struct.
+struct NameElidedLifetimes {
+    lifetime: Lifetime,
+    used: bool,
+}
+
+impl VisitMut for NameElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.lifetime.clone());
+            self.used = true;
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime.clone();
+            self.used = true;
+        }
+    }
+}
+
+/// Finds `impl Trait` types, which can't be stored in a struct.
+struct FindImplTrait(Option<Span>);
+
+impl<'ast> Visit<'ast> for FindImplTrait {
+    fn visit_type_impl_trait(&mut self, ty: &'ast syn::TypeImplTrait) {
+        self.0.get_or_insert(ty.impl_token.span);
+        visit::visit_type_impl_trait(self, ty);
+    }
+}
+
+/// `my_card` -> `MyCard`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars))
+                .into_iter()
+                .flatten()
+        })
+        .collect()
+}
+
+/// `Some(T)` if the type is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// Parse `#[prop(default)]` or `#[prop(default = expr)]`.
+fn parse_prop_attribute(attr: &Attribute) -> syn::Result<Expr> {
+    let mut default = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("default") {
+            default = Some(if meta.input.peek(Token![=]) {
+                meta.value()?.parse()?
+            } else {
+                parse_quote!(::core::default::Default::default())
+            });
+            Ok(())
+        } else {
+            Err(meta.error("unknown prop option; expected `default` or `default = ...`"))
+        }
+    })?;
+    default.ok_or_else(|| syn::Error::new_spanned(attr, "expected `#[prop(default)]`"))
+}
+
+fn generate(mut item: ItemFn) -> syn::Result<TokenStream> {
+    let sig = &mut item.sig;
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            sig.fn_token,
+            "`#[component]` goes on an `async fn`",
+        ));
+    }
+    let lifetime = Lifetime::new("'props", Span::mixed_site());
+    let mut name_lifetimes = NameElidedLifetimes {
+        lifetime: lifetime.clone(),
+        used: false,
+    };
+
+    let mut props = Vec::new();
+    for input in sig.inputs.iter_mut() {
+        let FnArg::Typed(input) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "components can't take `self`",
+            ));
+        };
+        let Pat::Ident(pat) = &*input.pat else {
+            return Err(syn::Error::new_spanned(
+                &input.pat,
+                "component arguments must be plain names, so that they can be props",
+            ));
+        };
+        let name = pat.ident.clone();
+        if name == "props" || name == "render" {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("`{name}` can't be used as a prop name"),
+            ));
+        }
+        // Take our attributes (and docs) off the argument.
+        let mut default = None;
+        let mut docs = Vec::new();
+        let mut error = None;
+        input.attrs.retain(|attr| {
+            if attr.path().is_ident("prop") {
+                match parse_prop_attribute(attr) {
+                    Ok(expr) => default = Some(expr),
+                    Err(e) => error = Some(e),
+                }
+                false
+            } else if attr.path().is_ident("doc") {
+                docs.push(attr.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let mut ty = (*input.ty).clone();
+        let kind = if name == "children" {
+            if default.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    "`children` is given to `render`, so it can't have a default",
+                ));
+            }
+            PropKind::Children
+        } else {
+            let mut find = FindImplTrait(None);
+            find.visit_type(&ty);
+            if let Some(span) = find.0 {
+                return Err(syn::Error::new(
+                    span,
+                    "props can't be `impl Trait`; use a type parameter instead",
+                ));
+            }
+            name_lifetimes.visit_type_mut(&mut ty);
+            match (default, option_inner(&ty)) {
+                (Some(default), _) => PropKind::Default(default),
+                (None, Some(inner)) => PropKind::Optional(inner.clone()),
+                (None, None) => PropKind::Required,
+            }
+        };
+        props.push(Prop {
+            name,
+            docs,
+            ty,
+            kind,
+        });
+    }
+
+    let sig = &item.sig;
+    let vis = &item.vis;
+    let fn_name = &sig.ident;
+    let struct_name = format_ident!("{}", pascal_case(&fn_name.unraw().to_string()));
+    let output = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+
+    let (mut generics, render_generics) = split_generics(&sig.generics, &props);
+    if name_lifetimes.used {
+        generics.params.insert(0, parse_quote!(#lifetime));
+    }
+    let render_params = &render_generics.params;
+    let render_where_clause = &render_generics.where_clause;
+
+    // Each required prop gets a type parameter: `MissingProp` until it is set,
+    // then the prop's type. `render` only exists once all of them are set.
+    let required: Vec<_> = props
+        .iter()
+        .filter(|prop| matches!(prop.kind, PropKind::Required))
+        .map(|prop| {
+            let state = Ident::new(
+                &format!("{}Prop", pascal_case(&prop.name.unraw().to_string())),
+                Span::mixed_site(),
+            );
+            (prop, state)
+        })
+        .collect();
+    let states: Vec<_> = required.iter().map(|(_, state)| state).collect();
+    let required_tys: Vec<_> = required.iter().map(|(prop, _)| &prop.ty).collect();
+    let missing = quote!(::async_ui_web::__private_macro_only::MissingProp);
+    let where_clause = &generics.where_clause;
+    let ty_params = generic_arguments(&generics);
+    let mut struct_generics = generics.clone();
+    let mut impl_generics = generics.clone();
+    for state in states.iter() {
+        struct_generics.params.push(parse_quote!(#state = #missing));
+        impl_generics.params.push(parse_quote!(#state));
+    }
+    let (struct_generics, _, _) = struct_generics.split_for_impl();
+    let (setters_impl_generics, _, _) = impl_generics.split_for_impl();
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let with_states = |states: &[TokenStream]| {
+        let params = ty_params.iter().cloned().chain(states.iter().cloned());
+        quote!(#struct_name<#(#params),*>)
+    };
+    let any_states: Vec<_> = states.iter().map(|state| quote!(#state)).collect();
+    let missing_states: Vec<_> = states.iter().map(|_| missing.clone()).collect();
+    let set_states: Vec<_> = required_tys.iter().map(|ty| quote!(#ty)).collect();
+    let any_struct = with_states(&any_states);
+    let missing_struct = with_states(&missing_states);
+    let set_struct = with_states(&set_states);
+
+    // The struct doesn't hold the props' types directly when they are required,
+    // so it refers to its type parameters here.
+    let marker = Ident::new("marker", Span::mixed_site());
+    let phantom = {
+        let lifetimes = generics.lifetimes().map(|param| &param.lifetime);
+        let types = generics.type_params().map(|param| &param.ident);
+        quote!(::core::marker::PhantomData<fn() -> (#(&#lifetimes (),)* #(#types,)*)>)
+    };
+
+    let fields = props.iter().filter_map(|prop| {
+        let Prop { name, ty, .. } = prop;
+        match &prop.kind {
+            PropKind::Required => {
+                let (_, state) = required.iter().find(|(p, _)| p.name == *name)?;
+                Some(quote!(#name: #state))
+            }
+            PropKind::Optional(_) | PropKind::Default(_) => Some(quote!(#name: #ty)),
+            PropKind::Children => None,
+        }
+    });
+    let field_names: Vec<_> = props
+        .iter()
+        .filter(|prop| !matches!(prop.kind, PropKind::Children))
+        .map(|prop| &prop.name)
+        .collect();
+    let initializers = props.iter().filter_map(|prop| {
+        let name = &prop.name;
+        match &prop.kind {
+            PropKind::Required => Some(quote!(#name: #missing)),
+            PropKind::Optional(_) => Some(quote!(#name: ::core::option::Option::None)),
+            PropKind::Default(default) => Some(quote!(#name: #default)),
+            PropKind::Children => None,
+        }
+    });
+    let setters = props.iter().filter_map(|prop| {
+        let Prop { name, docs, ty, .. } = prop;
+        Some(match &prop.kind {
+            PropKind::Required => {
+                let states = required.iter().map(|(p, state)| {
+                    if p.name == *name {
+                        quote!(#ty)
+                    } else {
+                        quote!(#state)
+                    }
+                });
+                let returned = with_states(&states.collect::<Vec<_>>());
+                let others = field_names.iter().filter(|other| **other != name);
+                quote!(
+                    #(#docs)*
+                    #vis fn #name(self, #name: #ty) -> #returned {
+                        #struct_name {
+                            #name,
+                            #(#others: self.#others,)*
+                            #marker: ::core::marker::PhantomData,
+                        }
+                    }
+                )
+            }
+            PropKind::Optional(inner) => quote!(
+                #(#docs)*
+                #vis fn #name(mut self, #name: #inner) -> Self {
+                    self.#name = ::core::option::Option::Some(#name);
+                    self
+                }
+            ),
+            PropKind::Default(_) => quote!(
+                #(#docs)*
+                #vis fn #name(mut self, #name: #ty) -> Self {
+                    self.#name = #name;
+                    self
+                }
+            ),
+            PropKind::Children => return None,
+        })
+    });
+    let children_param = props
+        .iter()
+        .find(|prop| matches!(prop.kind, PropKind::Children))
+        .map(|Prop { name, ty, .. }| quote!(#name: #ty));
+    let arguments = props.iter().map(|prop| {
+        let name = &prop.name;
+        match &prop.kind {
+            PropKind::Children => quote!(#name),
+            _ => quote!(self.#name),
+        }
+    });
+
+    let struct_doc = format!(
+        "Props for [`{fn_name}`]. Start with [`{struct_name}::props`], set the props, then `.render(...)`."
+    );
+    Ok(quote!(
+        #item
+
+        #[doc = #struct_doc]
+        #vis struct #struct_name #struct_generics #where_clause {
+            #(#fields,)*
+            #marker: #phantom,
+        }
+
+        impl #impl_generics #missing_struct #where_clause {
+            /// Start setting the props.
+            /// Props that aren't `Option`s and don't have defaults must be set before rendering.
+            #vis fn props() -> Self {
+                Self {
+                    #(#initializers,)*
+                    #marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #setters_impl_generics #any_struct #where_clause {
+            #(#setters)*
+        }
+
+        impl #impl_generics #set_struct #where_clause {
+            /// Render the component with the props.
+            ///
+            /// This only exists once every required prop has been set.
+            #vis async fn render<#render_params>(self, #children_param) -> #output #render_where_clause {
+                #fn_name(#(#arguments),*).await
+            }
+        }
+    ))
+}
+
+/// Collects the names (identifiers and lifetimes) that appear in a piece of syntax.
+#[derive(Default)]
+struct Names(HashSet<String>);
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        self.0.insert(ident.to_string());
+    }
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.0.insert(lifetime.to_string());
+    }
+}
+
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Lifetime(param) => param.lifetime.to_string(),
+        GenericParam::Type(param) => param.ident.to_string(),
+        GenericParam::Const(param) => param.ident.to_string(),
+    }
+}
+
+/// Split the function's generics into those the props struct needs
+/// (those used by props other than `children`, and those their bounds use)
+/// and the rest, which go on `render`.
+///
+/// Where-clause predicates go on the struct if they only mention its parameters.
+fn split_generics(generics: &Generics, props: &[Prop]) -> (Generics, Generics) {
+    let mut used = Names::default();
+    for prop in props {
+        if !matches!(prop.kind, PropKind::Children) {
+            used.visit_type(&prop.ty);
+        }
+    }
+    loop {
+        let before = used.0.len();
+        for param in generics.params.iter() {
+            if used.0.contains(&param_name(param)) {
+                used.visit_generic_param(param);
+            }
+        }
+        if used.0.len() == before {
+            break;
+        }
+    }
+
+    let mut struct_generics = Generics::default();
+    let mut render_generics = Generics::default();
+    for param in generics.params.iter() {
+        if used.0.contains(&param_name(param)) {
+            struct_generics.params.push(param.clone());
+        } else {
+            render_generics.params.push(param.clone());
+        }
+    }
+    let render_names: HashSet<_> = render_generics.params.iter().map(param_name).collect();
+    for predicate in generics
+        .where_clause
+        .iter()
+        .flat_map(|clause| clause.predicates.iter())
+    {
+        let mut names = Names::default();
+        names.visit_where_predicate(predicate);
+        let generics = if names.0.is_disjoint(&render_names) {
+            &mut struct_generics
+        } else {
+            &mut render_generics
+        };
+        generics
+            .make_where_clause()
+            .predicates
+            .push(predicate.clone());
+    }
+    (struct_generics, render_generics)
+}
+
+/// The generic parameters as arguments: `<'a, T, N>` for `<'a, T: Trait, const N: usize>`.
+fn generic_arguments(generics: &Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+        })
+        .collect()
+}
@@ -1,7 +1,11 @@
+mod component;
+use component::component_macro;
 mod css;
 use css::css_macro;
 mod select;
 use select::select_macro;
+mod view;
+use view::view_macro;
 
 /// Register CSS to be bundled and generate postfixed classnames.
 #[proc_macro]
@@ -10,8 +14,113 @@ pub fn css(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 /// See explanation of `select!` [here](https://rust-lang.github.io/async-book/06_multiple_futures/03_select.html).
-/// Note that our variant does not support `complete` or `default` branch.
+///
+/// Unlike the `futures` crate's version, the futures don't need to be fused:
+/// each future is created anew every time the `select!` is run.
+///
+/// *   If the output of a future doesn't match the pattern of its branch,
+///     that branch is disabled and the others keep running.
+///     Once all branches are disabled, the `complete => ...` branch runs
+///     (or the `select!` panics if there is none).
+/// *   The `default => ...` branch runs if none of the futures is ready right away.
+/// *   Normally, if more than one future become ready at the same time, any one of them may win.
+///     Put `biased;` before the branches to poll the futures in order,
+///     so that the first ready branch always wins.
+///
+/// ```ignore
+/// loop {
+///     select! {
+///         biased;
+///         Some(key) = keys.next() => handle_key(key),
+///         Some(click) = clicks.next() => handle_click(click),
+///         complete => break,
+///     }
+/// }
+/// ```
 #[proc_macro]
 pub fn select(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     select_macro(input)
 }
+
+/// Build a tree of HTML elements.
+///
+/// ```ignore
+/// view! {
+///     div(class = "card", id = "greeter") {
+///         h1 { "Hello" }
+///         input(type = "text", placeholder = "Your name") as name_input
+///         button(class = "primary", disabled) as button { "Greet" }
+///         {
+///             async {
+///                 button.until_click().await;
+///                 // ...
+///             }
+///         }
+///     }
+/// }
+/// .await;
+/// ```
+///
+/// The macro returns a future that renders the elements, like `.render(...)` would.
+///
+/// *   An element is written as its tag name, optionally followed by attributes in parentheses,
+///     `as name` to bind the element to a variable, and children in braces.
+///     Tags are mapped to types from [`html`](https://docs.rs/async_ui_web/latest/async_ui_web/html/index.html)
+///     (`div` is `Div`, `a` is `Anchor`, and so on), so tags without a type there don't compile.
+///     Names with a dash make custom elements.
+/// *   String literals are rendered as text.
+/// *   Anything in braces is a future to render there, as in `join((...))`.
+///     Elements bound with `as name` can be used in all these futures.
+/// *   `class = "a b"` adds classes, `class = [x, y]` adds several, and other attributes
+///     take anything that is `AsRef<str>`. An attribute without a value is set to the empty string.
+///     On standard elements, attributes must be known to HTML (or be `data-*` or `aria-*`).
+///     This catches typos, but doesn't check that the element itself takes the attribute:
+///     `div(href = "...")` compiles.
+#[proc_macro]
+pub fn view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    view_macro(input)
+}
+
+/// Turn an `async fn` into a component with a props builder.
+///
+/// ```ignore
+/// #[component]
+/// async fn my_card(
+///     /// Shown at the top of the card.
+///     title: &str,
+///     subtitle: Option<String>,
+///     #[prop(default = 1)] level: u8,
+///     children: impl Future<Output = ()>,
+/// ) {
+///     // ...
+/// }
+///
+/// MyCard::props()
+///     .title("Hello")
+///     .level(2)
+///     .render(children)
+///     .await;
+/// ```
+///
+/// The function stays as it is (and can still be called directly).
+/// Next to it, the macro generates a struct named after the function in `PascalCase`,
+/// with a setter method for each argument.
+///
+/// *   `Option<T>` arguments are optional: their setters take a `T`, and they are `None` if not set.
+/// *   `#[prop(default)]` arguments are [Default::default()] if not set.
+///     `#[prop(default = expr)]` uses the given expression instead.
+/// *   The `children` argument (usually an `impl Future`) is given to `.render(...)`.
+///     Without one, `.render()` takes no arguments.
+/// *   Other arguments are required. `.render(...)` only exists once all of them
+///     have been set, so forgetting one is a compile error.
+///
+/// Generic parameters only used by `children` go on `.render(...)` instead of the struct.
+///
+/// Adding an optional prop doesn't break existing call sites.
+#[proc_macro_attribute]
+pub fn component(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    component_macro(args, input)
+}
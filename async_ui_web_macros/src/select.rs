@@ -1,83 +1,244 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
-use syn::{braced, parse::Parse, parse_macro_input, Expr, Pat, Token};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    visit_mut::{self, VisitMut},
+    Expr, Pat, Token,
+};
 
 pub fn select_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     parse_macro_input!(input as MacroInput).generate().into()
 }
 
 struct MacroInput {
+    biased: bool,
     branches: Vec<Branch>,
+    complete: Option<TokenStream>,
+    default: Option<TokenStream>,
 }
 
-#[derive(Default)]
 struct Branch {
-    pattern: Option<Pat>,
-    future: Option<Expr>,
-    expr: Option<TokenStream>,
+    pattern: Pat,
+    future: Expr,
+    expr: TokenStream,
+}
+
+mod kw {
+    syn::custom_keyword!(biased);
+    syn::custom_keyword!(complete);
+    syn::custom_keyword!(default);
+}
+
+/// Parse the expression after `=>`, and the comma after it.
+fn parse_handler(input: ParseStream) -> syn::Result<TokenStream> {
+    let is_block = input.peek(syn::token::Brace);
+    let expr = if is_block {
+        let (tt, _next) = input.cursor().token_tree().unwrap();
+        let content;
+        braced!(content in input);
+        syn::Block::parse_within(&content)?;
+        tt.into_token_stream()
+    } else {
+        input.parse::<Expr>()?.into_token_stream()
+    };
+    if input.is_empty() {
+        return Ok(expr);
+    }
+    if is_block {
+        input.parse::<Option<Token![,]>>()?;
+    } else {
+        input.parse::<Token![,]>()?;
+    }
+    Ok(expr)
 }
 
 impl Parse for MacroInput {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        fn parse_one_branch(
-            input: syn::parse::ParseStream,
-            branch: &mut Branch,
-        ) -> syn::Result<()> {
-            branch.pattern = Some(Pat::parse_single(input)?);
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut this = Self {
+            biased: false,
+            branches: Vec::new(),
+            complete: None,
+            default: None,
+        };
+        if input.peek(kw::biased) && input.peek2(Token![;]) {
+            input.parse::<kw::biased>()?;
+            input.parse::<Token![;]>()?;
+            this.biased = true;
+        }
+        while !input.is_empty() {
+            if input.peek(kw::biased) && input.peek2(Token![;]) {
+                return Err(input.error("`biased;` must come before all the branches"));
+            }
+            if input.peek(kw::complete) && input.peek2(Token![=>]) {
+                let keyword = input.parse::<kw::complete>()?;
+                input.parse::<Token![=>]>()?;
+                let expr = parse_handler(input)?;
+                if this.complete.replace(expr).is_some() {
+                    return Err(syn::Error::new(
+                        keyword.span,
+                        "`select!` can only have one `complete` branch",
+                    ));
+                }
+                continue;
+            }
+            if input.peek(kw::default) && input.peek2(Token![=>]) {
+                let keyword = input.parse::<kw::default>()?;
+                input.parse::<Token![=>]>()?;
+                let expr = parse_handler(input)?;
+                if this.default.replace(expr).is_some() {
+                    return Err(syn::Error::new(
+                        keyword.span,
+                        "`select!` can only have one `default` branch",
+                    ));
+                }
+                continue;
+            }
+            let pattern = Pat::parse_multi_with_leading_vert(input)?;
             input.parse::<Token![=]>()?;
-            branch.future = Some(input.parse()?);
+            let future = input.parse()?;
             input.parse::<Token![=>]>()?;
-            let is_block = input.peek(syn::token::Brace);
-            branch.expr = Some(if is_block {
-                let (tt, _next) = input.cursor().token_tree().unwrap();
-                let content;
-                braced!(content in input);
-                let _ = syn::Block::parse_within(&content);
-                tt.into_token_stream()
-            } else {
-                input.parse::<Expr>()?.into_token_stream()
+            let expr = parse_handler(input)?;
+            this.branches.push(Branch {
+                pattern,
+                future,
+                expr,
             });
-            if (is_block || input.peek(Token![,])) && !input.is_empty() {
-                input.parse::<Token![,]>()?;
-            }
-            Ok(())
         }
-        let mut branches = Vec::new();
-        while !input.is_empty() {
-            let mut branch = Branch::default();
-            parse_one_branch(input, &mut branch).ok();
-            if branch.pattern.is_none() {
-                break;
+        if this.branches.is_empty() && this.default.is_none() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`select!` needs at least one `pattern = future => expression` branch",
+            ));
+        }
+        Ok(this)
+    }
+}
+
+/// Turns a pattern into one that can be checked against a reference to the value,
+/// so that checking it doesn't move the value.
+///
+/// Bindings then bind references, so `ref` and `mut` are removed from them,
+/// and so are reference patterns, since the value is already behind a reference.
+/// Whether a name is a binding or a constant is left to the compiler.
+struct ByReference;
+
+impl VisitMut for ByReference {
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        match pat {
+            Pat::Ident(ident) => {
+                ident.by_ref = None;
+                ident.mutability = None;
+            }
+            Pat::Reference(reference) => {
+                *pat = (*reference.pat).clone();
+                self.visit_pat_mut(pat);
+                return;
             }
-            branches.push(branch);
+            _ => {}
         }
-        Ok(Self { branches })
+        visit_mut::visit_pat_mut(self, pat);
     }
 }
+
 impl MacroInput {
     fn generate(self) -> TokenStream {
-        let Self { branches } = self;
+        let Self {
+            biased,
+            branches,
+            complete,
+            default,
+        } = self;
         let enum_name = Ident::new("SelectResult", Span::mixed_site());
-        let enum_variants = (0..branches.len())
+        let disabled = Ident::new("disabled", Span::mixed_site());
+        let output = Ident::new("output", Span::mixed_site());
+        let matched = Ident::new("matched", Span::mixed_site());
+        let num_branches = branches.len();
+        let enum_variants = (0..num_branches)
             .map(|i| format_ident!("{enum_name}{i}"))
             .collect::<Vec<_>>();
-        let exprs = branches.iter().map(|br| &br.expr);
-        let futures = branches.iter().map(|br| &br.future);
+
+        // When the output of a future doesn't match the pattern, the branch is disabled.
+        // Once every branch is disabled, we go to the `complete` branch.
+        let when_all_disabled = if complete.is_some() {
+            quote!(return #enum_name :: Complete)
+        } else {
+            quote!(::core::panic!(
+                "all branches of `select!` are disabled, and there is no `complete` branch"
+            ))
+        };
+        let branch_futures = branches
+            .iter()
+            .zip(enum_variants.iter())
+            .map(|(br, variant)| {
+                let future = &br.future;
+                let mut shape = br.pattern.clone();
+                ByReference.visit_pat_mut(&mut shape);
+                quote!(
+                    async {
+                        let #output = (#future).await;
+                        #[allow(unreachable_patterns, unused_variables)]
+                        let #matched = match &#output {
+                            #shape => true,
+                            _ => false,
+                        };
+                        if #matched {
+                            #enum_name :: #variant (#output)
+                        } else {
+                            #disabled.set(#disabled.get() + 1);
+                            if #disabled.get() == #num_branches {
+                                #when_all_disabled
+                            }
+                            ::core::future::pending().await
+                        }
+                    },
+                )
+            });
+        // Polled last, on the first poll, so this wins only if no other future is ready right away.
+        let default_future = default
+            .as_ref()
+            .map(|_| quote!(async { #enum_name :: Default },));
+
         let patterns = branches.iter().map(|br| &br.pattern);
+        let exprs = branches.iter().map(|br| &br.expr);
+        let complete_arm = complete
+            .as_ref()
+            .map(|expr| quote!(#enum_name :: Complete => #expr,));
+        let default_arm = default
+            .as_ref()
+            .map(|expr| quote!(#enum_name :: Default => #expr,));
+        let complete_variant = complete.as_ref().map(|_| quote!(Complete,));
+        let default_variant = default.as_ref().map(|_| quote!(Default,));
+
+        let declare_disabled =
+            (num_branches > 0).then(|| quote!(let #disabled = ::core::cell::Cell::new(0usize);));
+        let race = if biased {
+            quote!(::async_ui_web::__private_macro_only::race_biased)
+        } else {
+            quote!(::async_ui_web::race)
+        };
         quote!(
             {
                 enum #enum_name <#(#enum_variants),*> {
-                    #(#enum_variants (#enum_variants)),*
+                    #(#enum_variants (#enum_variants),)*
+                    #complete_variant
+                    #default_variant
                 }
-                match ::async_ui_web::race((
-                    #( async { #enum_name :: #enum_variants (#futures.await) },)*
+                #declare_disabled
+                match #race((
+                    #(#branch_futures)*
+                    #default_future
                 )).await {
                     #(
                         #enum_name :: #enum_variants ( #patterns ) => #exprs,
                     )*
+                    #complete_arm
+                    #default_arm
+                    #[allow(unreachable_patterns)]
+                    _ => ::core::unreachable!(),
                 }
             }
-
         )
     }
 }
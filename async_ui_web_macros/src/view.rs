@@ -0,0 +1,483 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Block, Expr, LitStr, Token,
+};
+
+pub fn view_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as MacroInput).generate().into()
+}
+
+/// Attributes known to HTML: the global ones, and those of any standard element.
+/// This only catches typos; it doesn't check that the element takes the attribute.
+/// `data-*` and `aria-*` attributes are always accepted,
+/// and so is anything on custom elements.
+const ATTRIBUTES: &[&str] = &[
+    // global attributes
+    "accesskey",
+    "autocapitalize",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "part",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    // element-specific attributes
+    "accept",
+    "accept-charset",
+    "action",
+    "allow",
+    "alt",
+    "as",
+    "async",
+    "autocomplete",
+    "autoplay",
+    "charset",
+    "checked",
+    "cite",
+    "cols",
+    "colspan",
+    "content",
+    "controls",
+    "coords",
+    "crossorigin",
+    "datetime",
+    "decoding",
+    "default",
+    "defer",
+    "dirname",
+    "disabled",
+    "download",
+    "enctype",
+    "for",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "headers",
+    "height",
+    "high",
+    "href",
+    "hreflang",
+    "http-equiv",
+    "integrity",
+    "kind",
+    "label",
+    "list",
+    "loading",
+    "loop",
+    "low",
+    "max",
+    "maxlength",
+    "media",
+    "method",
+    "min",
+    "minlength",
+    "multiple",
+    "muted",
+    "name",
+    "novalidate",
+    "open",
+    "optimum",
+    "pattern",
+    "placeholder",
+    "playsinline",
+    "poster",
+    "preload",
+    "readonly",
+    "referrerpolicy",
+    "rel",
+    "required",
+    "reversed",
+    "rows",
+    "rowspan",
+    "sandbox",
+    "scope",
+    "selected",
+    "shape",
+    "size",
+    "sizes",
+    "span",
+    "src",
+    "srcdoc",
+    "srclang",
+    "srcset",
+    "start",
+    "step",
+    "target",
+    "type",
+    "usemap",
+    "value",
+    "width",
+    "wrap",
+];
+
+/// `join` takes tuples of at most this many futures.
+const MAX_JOIN: usize = 12;
+
+struct MacroInput {
+    /// Where the element types and helpers come from.
+    /// `async_ui_web::ssr::view!` sets this to its own module with a hidden
+    /// `in path;` prefix; otherwise it is `::async_ui_web`.
+    platform: TokenStream,
+    nodes: Vec<Node>,
+}
+
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Future(Block),
+}
+
+struct Element {
+    tag: Tag,
+    span: Span,
+    attributes: Vec<Attribute>,
+    binding: Option<Ident>,
+    children: Vec<Node>,
+}
+
+enum Tag {
+    /// A standard element, with a type in `async_ui_web::html`.
+    /// If there's no such type, the compiler complains about the generated code.
+    Standard(Ident),
+    Custom(String),
+}
+
+struct Attribute {
+    name: String,
+    span: Span,
+    value: Option<Expr>,
+}
+
+/// Parse a name like `div`, `aria-label`, or `my-element`.
+/// Keywords are allowed, so that `type` and `for` work.
+fn parse_dashed_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let first = Ident::parse_any(input)?;
+    let span = first.span();
+    let mut name = first.unraw().to_string();
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        let part = Ident::parse_any(input)?;
+        name.push('-');
+        name.push_str(&part.unraw().to_string());
+    }
+    Ok((name, span))
+}
+
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+    }
+    Ok(nodes)
+}
+
+impl Parse for MacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let platform = if input.peek(Token![in]) {
+            input.parse::<Token![in]>()?;
+            let mut platform = TokenStream::new();
+            while !input.peek(Token![;]) {
+                platform.extend([input.parse::<proc_macro2::TokenTree>()?]);
+            }
+            input.parse::<Token![;]>()?;
+            platform
+        } else {
+            quote!(::async_ui_web)
+        };
+        let nodes = parse_nodes(input)?;
+        if nodes.is_empty() {
+            return Err(input.error("`view!` needs at least one node"));
+        }
+        Ok(Self { platform, nodes })
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(Self::Text(input.parse()?))
+        } else if input.peek(syn::token::Brace) {
+            Ok(Self::Future(input.parse()?))
+        } else if input.peek(Ident::peek_any) {
+            Ok(Self::Element(input.parse()?))
+        } else {
+            Err(input.error("expected an element, a string literal, or a `{ future }` block"))
+        }
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (tag_name, span) = parse_dashed_name(input)?;
+        let tag = if tag_name.contains('-') {
+            // Names of custom elements always have a dash in them.
+            Tag::Custom(tag_name)
+        } else {
+            match syn::parse_str::<Ident>(&tag_name) {
+                Ok(ident) => Tag::Standard(Ident::new(&ident.to_string(), span)),
+                Err(_) => {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("unknown HTML tag `{tag_name}`"),
+                    ))
+                }
+            }
+        };
+
+        let mut attributes = Vec::new();
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            attributes.extend(Punctuated::<Attribute, Token![,]>::parse_terminated(
+                &content,
+            )?);
+        }
+        if let Tag::Standard(_) = tag {
+            for attr in attributes.iter() {
+                let known = ATTRIBUTES.contains(&&*attr.name)
+                    || attr.name.starts_with("data-")
+                    || attr.name.starts_with("aria-");
+                if !known {
+                    return Err(syn::Error::new(
+                        attr.span,
+                        format!(
+                            "`{}` is not an HTML attribute; custom attributes should start with `data-`",
+                            attr.name
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let binding = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let mut children = Vec::new();
+        if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            children = parse_nodes(&content)?;
+        }
+        Ok(Self {
+            tag,
+            span,
+            attributes,
+            binding,
+            children,
+        })
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (name, span) = parse_dashed_name(input)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if name == "class" && value.is_none() {
+            return Err(syn::Error::new(span, "`class` needs a value"));
+        }
+        Ok(Self { name, span, value })
+    }
+}
+
+/// Collects the variable declarations for all the elements,
+/// so that every element handle is in scope for all the `{ future }` blocks.
+struct Generator {
+    platform: TokenStream,
+    declarations: Vec<TokenStream>,
+    bindings: HashSet<String>,
+    errors: Vec<syn::Error>,
+}
+
+impl MacroInput {
+    fn generate(self) -> TokenStream {
+        let mut generator = Generator {
+            platform: self.platform,
+            declarations: Vec::new(),
+            bindings: HashSet::new(),
+            errors: Vec::new(),
+        };
+        let root = generator.nodes(self.nodes);
+        if let Some(error) = generator.errors.into_iter().reduce(|mut a, b| {
+            a.combine(b);
+            a
+        }) {
+            return error.into_compile_error();
+        }
+        let declarations = generator.declarations;
+        quote!(
+            async {
+                #(#declarations)*
+                (#root).await
+            }
+        )
+    }
+}
+
+impl Generator {
+    /// Make a future that renders all the nodes, in order.
+    fn nodes(&mut self, nodes: Vec<Node>) -> TokenStream {
+        let futures = nodes.into_iter().map(|node| self.node(node)).collect();
+        join_all(futures)
+    }
+    fn node(&mut self, node: Node) -> TokenStream {
+        match node {
+            Node::Text(text) => {
+                let platform = &self.platform;
+                quote_spanned!(text.span()=>
+                    #platform::shortcut_traits::ShortcutRenderStr::render(#text)
+                )
+            }
+            Node::Future(block) => quote!(#block),
+            Node::Element(element) => self.element(element),
+        }
+    }
+    fn element(&mut self, element: Element) -> TokenStream {
+        let Element {
+            tag,
+            span,
+            attributes,
+            binding,
+            children,
+        } = element;
+        let var = match binding {
+            Some(binding) => {
+                if !self.bindings.insert(binding.to_string()) {
+                    self.errors.push(syn::Error::new(
+                        binding.span(),
+                        format!("`{binding}` is already bound to another element"),
+                    ));
+                }
+                binding
+            }
+            None => Ident::new(
+                &format!("element_{}", self.declarations.len()),
+                Span::mixed_site(),
+            ),
+        };
+        let platform = self.platform.clone();
+        let constructor = match &tag {
+            Tag::Standard(tag) => quote_spanned!(span=>
+                #platform::__private_macro_only::tags::#tag::new()
+            ),
+            Tag::Custom(name) => quote_spanned!(span=>
+                #platform::html::Element::new_tag(#name)
+            ),
+        };
+        let setters = attributes.into_iter().map(|attr| {
+            let Attribute { name, span, value } = attr;
+            match (name.as_str(), value) {
+                (
+                    "class",
+                    Some(Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })),
+                ) => {
+                    // Split up the classes now, since the class list only takes one at a time.
+                    let classes = lit.value();
+                    let classes = classes.split_whitespace();
+                    quote_spanned!(span=>
+                        #platform::shortcut_traits::ShortcutClassListBuilder::with_classes(
+                            &#var.element,
+                            [#(#classes),*],
+                        );
+                    )
+                }
+                ("class", Some(Expr::Array(classes))) => quote_spanned!(span=>
+                    #platform::shortcut_traits::ShortcutClassListBuilder::with_classes(
+                        &#var.element,
+                        #classes,
+                    );
+                ),
+                ("class", Some(class)) => quote_spanned!(span=>
+                    #platform::shortcut_traits::ShortcutClassListBuilder::with_class(
+                        &#var.element,
+                        ::core::convert::AsRef::<str>::as_ref(&(#class)),
+                    );
+                ),
+                (_, Some(value)) => quote_spanned!(span=>
+                    #platform::__private_macro_only::set_attribute(
+                        &#var.element,
+                        #name,
+                        ::core::convert::AsRef::<str>::as_ref(&(#value)),
+                    );
+                ),
+                (_, None) => quote_spanned!(span=>
+                    #platform::__private_macro_only::set_attribute(&#var.element, #name, "");
+                ),
+            }
+        });
+        let declaration = quote!(
+            let #var = #constructor;
+            #(#setters)*
+        );
+        self.declarations.push(declaration);
+
+        let children = if children.is_empty() {
+            // Elements that can't have children only take this.
+            quote!(::core::future::pending::<()>())
+        } else {
+            self.nodes(children)
+        };
+        quote_spanned!(span=>
+            #platform::__private_macro_only::RenderView::render_view(&#var, #children)
+        )
+    }
+}
+
+/// Join the futures, nesting the `join`s if there are too many for one tuple.
+fn join_all(mut futures: Vec<TokenStream>) -> TokenStream {
+    match futures.len() {
+        0 => quote!(::async_ui_web::NoChild),
+        1 => futures.pop().unwrap(),
+        len if len <= MAX_JOIN => quote!(::async_ui_web::join((#(#futures,)*))),
+        _ => join_all(
+            futures
+                .chunks(MAX_JOIN)
+                .map(|chunk| join_all(chunk.to_vec()))
+                .collect(),
+        ),
+    }
+}